@@ -0,0 +1,216 @@
+use std::{
+	alloc::{GlobalAlloc, Layout, System as SystemAllocator},
+	collections::BTreeMap,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, Ui};
+
+use crate::{
+	history::History,
+	tables::{export_buttons, Table},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static FREES: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting calls. Installed as the global allocator by the `alloc-tracker` feature.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+		SystemAllocator.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		FREES.fetch_add(1, Ordering::Relaxed);
+		SystemAllocator.dealloc(ptr, layout)
+	}
+
+	unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+		FREES.fetch_add(1, Ordering::Relaxed);
+		BYTES.fetch_add(new_size, Ordering::Relaxed);
+		SystemAllocator.realloc(ptr, layout, new_size)
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Allocation counters since the process started.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct AllocationCount {
+	pub allocations: usize,
+	pub frees: usize,
+	pub bytes: usize,
+}
+
+impl AllocationCount {
+	pub fn now() -> Self {
+		Self {
+			allocations: ALLOCATIONS.load(Ordering::Relaxed),
+			frees: FREES.load(Ordering::Relaxed),
+			bytes: BYTES.load(Ordering::Relaxed),
+		}
+	}
+
+	pub fn since(&self, earlier: &Self) -> Self {
+		Self {
+			allocations: self.allocations.wrapping_sub(earlier.allocations),
+			frees: self.frees.wrapping_sub(earlier.frees),
+			bytes: self.bytes.wrapping_sub(earlier.bytes),
+		}
+	}
+}
+
+/// Per-frame allocations, and per-system ones for systems wrapped in `Tracked`. Drawn by `allocations_window`.
+pub struct AllocationStats {
+	pub open: bool,
+	pub allocations: History,
+	pub frees: History,
+	pub bytes: History,
+	/// Allocations during the last run of each `Tracked` system.
+	pub systems: BTreeMap<String, AllocationCount>,
+	table: Table,
+	/// Result of the last "Save CSV".
+	status: Option<String>,
+	last: Option<AllocationCount>,
+}
+
+impl Default for AllocationStats {
+	fn default() -> Self {
+		Self {
+			open: false,
+			allocations: History::new(240),
+			frees: History::new(240),
+			bytes: History::new(240),
+			systems: BTreeMap::new(),
+			table: Table::default(),
+			status: None,
+			last: None,
+		}
+	}
+}
+
+/// Wraps a system to attribute the allocations made while it runs to it in `AllocationStats`.
+///
+/// The counters are process-wide, so systems running in parallel pick up each other's allocations;
+/// the numbers are exact with a single-threaded dispatcher.
+pub struct Tracked<S> {
+	name: String,
+	system: S,
+}
+
+impl<S> Tracked<S> {
+	pub fn new(name: &str, system: S) -> Self {
+		Self {
+			name: name.to_owned(),
+			system,
+		}
+	}
+}
+
+impl<'s, S: System<'s>> System<'s> for Tracked<S> {
+	type SystemData = (S::SystemData, Write<'s, AllocationStats>);
+
+	fn run(&mut self, (data, mut stats): Self::SystemData) {
+		let start = AllocationCount::now();
+		self.system.run(data);
+		let count = AllocationCount::now().since(&start);
+		stats.systems.insert(self.name.clone(), count);
+	}
+
+	fn setup(&mut self, res: &mut Resources) {
+		self.system.setup(res);
+		<Write<'s, AllocationStats> as SystemData>::setup(res);
+	}
+}
+
+fn plot(ui: &Ui, label: &str, history: &History) {
+	let values = history.to_vec();
+	ui.plot_lines(&im_str!("{}", label), &values)
+		.overlay_text(&im_str!("{:.0}", history.last().unwrap_or(0.)))
+		.scale_min(0.)
+		.graph_size((0., 50.))
+		.build();
+}
+
+/// Samples the counters once per frame and, while open, shows them.
+pub fn allocations_window(ui: &Ui, world: &World) {
+	let mut stats = match world.res.try_fetch_mut::<AllocationStats>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let now = AllocationCount::now();
+	if let Some(last) = stats.last {
+		let frame = now.since(&last);
+		stats.allocations.push(frame.allocations as f32);
+		stats.frees.push(frame.frees as f32);
+		stats.bytes.push(frame.bytes as f32);
+	}
+	stats.last = Some(now);
+	if !stats.open {
+		return;
+	}
+
+	let mut table = stats.table;
+	let mut status = stats.status.take();
+	let mut open = true;
+	ui.window(im_str!("Allocations"))
+		.size((420., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			plot(ui, "Allocations/frame", &stats.allocations);
+			plot(ui, "Frees/frame", &stats.frees);
+			plot(ui, "Bytes/frame", &stats.bytes);
+			if !stats.systems.is_empty() {
+				ui.separator();
+				let mut rows = stats.systems.iter().collect::<Vec<_>>();
+				table.sort(&mut rows, |a, b, column| match column {
+					0 => a.0.cmp(b.0),
+					1 => a.1.allocations.cmp(&b.1.allocations),
+					2 => a.1.frees.cmp(&b.1.frees),
+					_ => a.1.bytes.cmp(&b.1.bytes),
+				});
+				let headers = ["System", "Allocations", "Frees", "Bytes"];
+				let saved = export_buttons(ui, im_str!("system_allocations"), &headers, || {
+					rows.iter()
+						.map(|(name, count)| {
+							vec![
+								name.to_string(),
+								count.allocations.to_string(),
+								count.frees.to_string(),
+								count.bytes.to_string(),
+							]
+						})
+						.collect()
+				});
+				if saved.is_some() {
+					status = saved;
+				}
+				if let Some(ref status) = status {
+					ui.text_wrapped(&im_str!("{}", status));
+				}
+				table.begin(ui, im_str!("systems"), &headers);
+				for (name, count) in rows {
+					ui.text(name);
+					ui.next_column();
+					ui.text(count.allocations.to_string());
+					ui.next_column();
+					ui.text(count.frees.to_string());
+					ui.next_column();
+					ui.text(count.bytes.to_string());
+					ui.next_column();
+				}
+				table.end(ui);
+			}
+		});
+	stats.table = table;
+	stats.status = status;
+	stats.open = open;
+}
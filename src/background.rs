@@ -0,0 +1,99 @@
+use imgui::{sys, ImGuiCol, ImGuiCond, ImGuiWindowFlags, ImTexture, ImVec2, StyleVar, Ui};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BackgroundFit {
+	/// Fill the screen, ignoring the aspect ratio.
+	Stretch,
+	/// Fill the screen keeping the aspect ratio, cropping what doesn't fit.
+	Cover,
+	/// Repeat the image at `scale` times its size; the texture's sampler has to use a tiling wrap mode.
+	Tile { scale: f32 },
+}
+
+/// Draws `texture` (of `size` pixels) behind every other imgui window, covering the whole screen.
+///
+/// Call it before building any other window in the frame.
+pub fn background_image(ui: &Ui, texture: ImTexture, size: (f32, f32), fit: BackgroundFit) {
+	let (width, height) = ui.frame_size().logical_size;
+	let (width, height) = (width as f32, height as f32);
+	if width <= 0. || height <= 0. || size.0 <= 0. || size.1 <= 0. {
+		return;
+	}
+	let (uv0, uv1) = match fit {
+		BackgroundFit::Stretch => ((0., 0.), (1., 1.)),
+		BackgroundFit::Cover => {
+			let scale = (width / size.0).max(height / size.1);
+			let (u, v) = (width / (size.0 * scale), height / (size.1 * scale));
+			(((1. - u) / 2., (1. - v) / 2.), ((1. + u) / 2., (1. + v) / 2.))
+		},
+		BackgroundFit::Tile { scale } => ((0., 0.), (width / (size.0 * scale), height / (size.1 * scale))),
+	};
+
+	ui.with_style_vars(&[StyleVar::WindowPadding(ImVec2::new(0., 0.)), StyleVar::WindowBorderSize(0.)], || {
+		ui.window(im_str!("##background"))
+			.position((0., 0.), ImGuiCond::Always)
+			.size((width, height), ImGuiCond::Always)
+			.flags(
+				ImGuiWindowFlags::NoTitleBar
+					| ImGuiWindowFlags::NoResize
+					| ImGuiWindowFlags::NoMove
+					| ImGuiWindowFlags::NoScrollbar
+					| ImGuiWindowFlags::NoScrollWithMouse
+					| ImGuiWindowFlags::NoInputs
+					| ImGuiWindowFlags::NoSavedSettings
+					| ImGuiWindowFlags::NoFocusOnAppearing
+					| ImGuiWindowFlags::NoBringToFrontOnFocus,
+			)
+			.build(|| {
+				ui.image(texture, (width, height)).uv0(uv0).uv1(uv1).build();
+			});
+	});
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DimWhen {
+	Always,
+	/// While an imgui window has focus, i.e. keyboard input goes to the UI.
+	WindowFocused,
+	/// While imgui wants the mouse or keyboard.
+	UiActive,
+}
+
+/// Darkens the game behind every imgui window with `color`, to keep the UI readable over bright scenes.
+///
+/// Call it before building any other window in the frame, after `background_image` if you use both.
+pub fn dim_game(ui: &Ui, color: [f32; 4], when: DimWhen) {
+	let active = match when {
+		DimWhen::Always => true,
+		DimWhen::WindowFocused => unsafe { sys::igIsWindowFocused(sys::ImGuiFocusedFlags::AnyWindow) },
+		DimWhen::UiActive => unsafe {
+			let io = &*sys::igGetIO();
+			io.want_capture_mouse || io.want_capture_keyboard
+		},
+	};
+	if !active || color[3] <= 0. {
+		return;
+	}
+	let (width, height) = ui.frame_size().logical_size;
+	let (width, height) = (width as f32, height as f32);
+
+	ui.with_style_vars(&[StyleVar::WindowPadding(ImVec2::new(0., 0.)), StyleVar::WindowBorderSize(0.)], || {
+		ui.with_color_var(ImGuiCol::WindowBg, (color[0], color[1], color[2], color[3]), || {
+			ui.window(im_str!("##dim"))
+				.position((0., 0.), ImGuiCond::Always)
+				.size((width, height), ImGuiCond::Always)
+				.flags(
+					ImGuiWindowFlags::NoTitleBar
+						| ImGuiWindowFlags::NoResize
+						| ImGuiWindowFlags::NoMove
+						| ImGuiWindowFlags::NoScrollbar
+						| ImGuiWindowFlags::NoScrollWithMouse
+						| ImGuiWindowFlags::NoInputs
+						| ImGuiWindowFlags::NoSavedSettings
+						| ImGuiWindowFlags::NoFocusOnAppearing
+						| ImGuiWindowFlags::NoBringToFrontOnFocus,
+				)
+				.build(|| {});
+		});
+	});
+}
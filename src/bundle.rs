@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use amethyst::{
+	core::bundle::{Result, SystemBundle},
+	ecs::{
+		prelude::*,
+		shred::{Resource, ResourceId, SystemData},
+	},
+};
+use imgui::{sys, ImGuiCond, ImString, ImVec2, Ui};
+
+type AddSystem<'a, 'b> = Box<dyn FnOnce(&mut DispatcherBuilder<'a, 'b>) + 'a>;
+
+#[derive(Clone, Debug)]
+pub struct SystemNode {
+	pub name: String,
+	pub dependencies: Vec<String>,
+	pub reads: Vec<ResourceId>,
+	pub writes: Vec<ResourceId>,
+	/// Longest chain of dependencies in front of this system.
+	pub stage: usize,
+}
+
+/// Systems added through `ImguiBundle` with their dependencies and resource access, drawn by `system_graph_window`.
+#[derive(Clone, Default)]
+pub struct SystemGraph {
+	pub open: bool,
+	pub systems: Vec<SystemNode>,
+	names: HashMap<ResourceId, String>,
+}
+
+impl SystemGraph {
+	/// Gives a resource a readable name; others show up by id.
+	pub fn name_resource<T: Resource>(&mut self, name: &str) {
+		self.names.insert(ResourceId::new::<T>(), name.to_owned());
+	}
+
+	pub fn resource_name(&self, id: &ResourceId) -> String {
+		self.names.get(id).cloned().unwrap_or_else(|| format!("{:?}", id))
+	}
+
+	/// Whether `a` and `b` can't run at the same time because one writes what the other uses.
+	pub fn conflicts(a: &SystemNode, b: &SystemNode) -> bool {
+		let writes = |a: &SystemNode, b: &SystemNode| {
+			a.writes.iter().any(|x| b.reads.contains(x) || b.writes.contains(x))
+		};
+		writes(a, b) || writes(b, a)
+	}
+
+	fn add(&mut self, name: &str, dependencies: &[&str], reads: Vec<ResourceId>, writes: Vec<ResourceId>) {
+		let stage = self
+			.systems
+			.iter()
+			.filter(|x| dependencies.contains(&x.name.as_str()))
+			.map(|x| x.stage + 1)
+			.max()
+			.unwrap_or(0);
+		self.systems.push(SystemNode {
+			name: name.to_owned(),
+			dependencies: dependencies.iter().map(|x| (*x).to_owned()).collect(),
+			reads,
+			writes,
+			stage,
+		});
+	}
+}
+
+/// Inserts the recorded `SystemGraph` when the dispatcher is set up, since bundles can't reach the world.
+struct InsertGraph(Option<SystemGraph>);
+
+impl<'s> System<'s> for InsertGraph {
+	type SystemData = ();
+
+	fn run(&mut self, _: ()) {}
+
+	fn setup(&mut self, res: &mut Resources) {
+		if let Some(graph) = self.0.take() {
+			res.insert(graph);
+		}
+	}
+}
+
+/// Adds systems to the dispatcher like `GameDataBuilder::with`, recording them into a `SystemGraph`
+/// to visualize ordering and resource conflicts.
+///
+/// ```rust,ignore
+/// let game_data = GameDataBuilder::default().with_bundle(
+///     ImguiBundle::new()
+///         .with(ImguiInputSystem::default(), "imgui_input", &[])
+///         .with(MovementSystem, "movement", &["imgui_input"]),
+/// )?;
+/// ```
+pub struct ImguiBundle<'a, 'b> {
+	systems: Vec<AddSystem<'a, 'b>>,
+	graph: SystemGraph,
+}
+
+impl<'a, 'b> ImguiBundle<'a, 'b> {
+	pub fn new() -> Self {
+		Self {
+			systems: Vec::new(),
+			graph: SystemGraph::default(),
+		}
+	}
+
+	pub fn with<S>(mut self, system: S, name: &str, dependencies: &[&str]) -> Self
+	where
+		S: for<'c> System<'c> + Send + 'a,
+	{
+		let reads = <<S as System<'static>>::SystemData as SystemData<'static>>::reads();
+		let writes = <<S as System<'static>>::SystemData as SystemData<'static>>::writes();
+		self.graph.add(name, dependencies, reads, writes);
+		let (name, dependencies) = (name.to_owned(), dependencies.iter().map(|x| (*x).to_owned()).collect::<Vec<_>>());
+		self.systems.push(Box::new(move |builder| {
+			let dependencies = dependencies.iter().map(String::as_str).collect::<Vec<_>>();
+			builder.add(system, &name, &dependencies);
+		}));
+		self
+	}
+
+	pub fn name_resource<T: Resource>(mut self, name: &str) -> Self {
+		self.graph.name_resource::<T>(name);
+		self
+	}
+}
+
+impl<'a, 'b> SystemBundle<'a, 'b> for ImguiBundle<'a, 'b> {
+	fn build(self, builder: &mut DispatcherBuilder<'a, 'b>) -> Result<()> {
+		for add in self.systems {
+			add(builder);
+		}
+		builder.add(InsertGraph(Some(self.graph)), "imgui_system_graph", &[]);
+		Ok(())
+	}
+}
+
+const NODE_SIZE: (f32, f32) = (150., 24.);
+const SPACING: (f32, f32) = (60., 12.);
+
+pub fn system_graph_window(ui: &Ui, world: &World) {
+	let mut graph = match world.res.try_fetch_mut::<SystemGraph>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !graph.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Systems"))
+		.size((640., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.text_disabled("Columns are dependency stages; red links are resource conflicts within a stage.");
+			let (x, y) = ui.get_cursor_screen_pos();
+			let mut rows = Vec::<usize>::new();
+			let positions = graph
+				.systems
+				.iter()
+				.map(|system| {
+					if rows.len() <= system.stage {
+						rows.resize(system.stage + 1, 0);
+					}
+					let row = rows[system.stage];
+					rows[system.stage] += 1;
+					(
+						x + system.stage as f32 * (NODE_SIZE.0 + SPACING.0),
+						y + row as f32 * (NODE_SIZE.1 + SPACING.1),
+					)
+				})
+				.collect::<Vec<_>>();
+			let index = |name: &str| graph.systems.iter().position(|x| x.name == name);
+			let mouse = ui.imgui().mouse_pos();
+			let mut hovered = None;
+			unsafe {
+				let list = sys::igGetWindowDrawList();
+				for (i, system) in graph.systems.iter().enumerate() {
+					let (left, top) = positions[i];
+					for dependency in system.dependencies.iter().filter_map(|x| index(x)) {
+						let (from_x, from_y) = positions[dependency];
+						let from = ImVec2::new(from_x + NODE_SIZE.0, from_y + NODE_SIZE.1 / 2.);
+						let to = ImVec2::new(left, top + NODE_SIZE.1 / 2.);
+						sys::ImDrawList_AddLine(list, from, to, 0xff_a0_a0_a0, 1.5);
+					}
+					for (j, other) in graph.systems.iter().enumerate().skip(i + 1) {
+						if other.stage == system.stage && SystemGraph::conflicts(system, other) {
+							let (other_left, other_top) = positions[j];
+							let from = ImVec2::new(left + 4., top + NODE_SIZE.1 / 2.);
+							let to = ImVec2::new(other_left + 4., other_top + NODE_SIZE.1 / 2.);
+							sys::ImDrawList_AddLine(list, from, to, 0xff_40_40_ff, 1.5);
+						}
+					}
+				}
+				for (i, system) in graph.systems.iter().enumerate() {
+					let (left, top) = positions[i];
+					let (right, bottom) = (left + NODE_SIZE.0, top + NODE_SIZE.1);
+					let over = mouse.0 >= left && mouse.0 <= right && mouse.1 >= top && mouse.1 <= bottom;
+					if over {
+						hovered = Some(i);
+					}
+					let color = if over { 0xff_70_50_30 } else { 0xff_50_40_30 };
+					let (min, max) = (ImVec2::new(left, top), ImVec2::new(right, bottom));
+					sys::ImDrawList_AddRectFilledMultiColor(list, min, max, color, color, color, color);
+					let label = ImString::new(system.name.as_str());
+					let text = label.as_ptr();
+					let end = text.add(system.name.len());
+					sys::ImDrawList_AddText(list, ImVec2::new(left + 6., top + 5.), 0xff_ff_ff_ff, text, end);
+				}
+			}
+			let width = (rows.len() as f32 * (NODE_SIZE.0 + SPACING.0)).max(1.);
+			let height = (rows.iter().cloned().max().unwrap_or(0) as f32 * (NODE_SIZE.1 + SPACING.1)).max(1.);
+			ui.invisible_button(im_str!("##graph"), (width, height));
+
+			if let Some(system) = hovered.map(|x| &graph.systems[x]) {
+				ui.tooltip(|| {
+					ui.text(&system.name);
+					ui.separator();
+					for id in &system.writes {
+						ui.text(format!("writes {}", graph.resource_name(id)));
+					}
+					for id in &system.reads {
+						ui.text(format!("reads  {}", graph.resource_name(id)));
+					}
+				});
+			}
+		});
+	graph.open = open;
+}
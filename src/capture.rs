@@ -0,0 +1,171 @@
+use std::{
+	fs,
+	path::PathBuf,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use amethyst::{core::timing::Time, ecs::prelude::*};
+use imgui::{sys, ImGuiCond, ImGuiWindowFlags, Ui};
+
+use crate::ImguiState;
+
+struct Recording {
+	dir: PathBuf,
+	frame: usize,
+	remaining: f32,
+}
+
+/// Screenshot and recording controls drawn by `capture_window`.
+///
+/// Captures go through `ImguiState::capture_next_frame`, so they contain the overlay's render target.
+/// Bind `screenshot` and `record` to keys for hotkeys.
+pub struct CapturePanel {
+	pub open: bool,
+	/// Where captures are written, created when needed.
+	pub dir: PathBuf,
+	pub record_seconds: f32,
+	pub toast_seconds: f64,
+	screenshot: bool,
+	record: bool,
+	recording: Option<Recording>,
+	toasts: Vec<(f64, String)>,
+}
+
+impl Default for CapturePanel {
+	fn default() -> Self {
+		Self {
+			open: false,
+			dir: PathBuf::from("screenshots"),
+			record_seconds: 3.,
+			toast_seconds: 3.,
+			screenshot: false,
+			record: false,
+			recording: None,
+			toasts: Vec::new(),
+		}
+	}
+}
+
+impl CapturePanel {
+	/// Saves the next frame as a PNG.
+	pub fn screenshot(&mut self) { self.screenshot = true; }
+
+	/// Saves the next `record_seconds` of frames as a numbered PNG sequence.
+	pub fn record(&mut self) { self.record = true; }
+
+	pub fn is_recording(&self) -> bool { self.recording.is_some() }
+
+	fn toast(&mut self, message: String) { self.toasts.push((unsafe { sys::igGetTime() } as f64, message)); }
+}
+
+fn timestamp() -> String {
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+	format!("{}-{:03}", now.as_secs(), now.subsec_millis())
+}
+
+pub fn capture_window(ui: &Ui, world: &World) {
+	let mut panel = match world.res.try_fetch_mut::<CapturePanel>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let panel = &mut *panel;
+	let mut imgui_state = match world.res.try_fetch_mut::<Option<ImguiState>>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let imgui_state = match *imgui_state {
+		Some(ref mut x) => x,
+		_ => return,
+	};
+	let delta = world.res.try_fetch::<Time>().map_or(0., |x| x.delta_real_seconds());
+
+	if panel.screenshot {
+		panel.screenshot = false;
+		let path = panel.dir.join(format!("screenshot-{}.png", timestamp()));
+		match fs::create_dir_all(&panel.dir) {
+			Ok(()) => {
+				imgui_state.capture_next_frame(path.clone());
+				panel.toast(format!("Saving {}", path.display()));
+			},
+			Err(e) => panel.toast(format!("Can't create {}: {}", panel.dir.display(), e)),
+		}
+	}
+	if panel.record && panel.recording.is_none() {
+		let dir = panel.dir.join(format!("recording-{}", timestamp()));
+		match fs::create_dir_all(&dir) {
+			Ok(()) => {
+				panel.recording = Some(Recording {
+					dir,
+					frame: 0,
+					remaining: panel.record_seconds,
+				})
+			},
+			Err(e) => panel.toast(format!("Can't create {}: {}", dir.display(), e)),
+		}
+	}
+	panel.record = false;
+	let finished = match panel.recording {
+		Some(ref mut recording) => {
+			imgui_state.capture_next_frame(recording.dir.join(format!("frame_{:05}.png", recording.frame)));
+			recording.frame += 1;
+			recording.remaining -= delta;
+			recording.remaining <= 0.
+		},
+		None => false,
+	};
+	if finished {
+		if let Some(recording) = panel.recording.take() {
+			panel.toast(format!("Saved {} frames to {}", recording.frame, recording.dir.display()));
+		}
+	}
+
+	let now = unsafe { sys::igGetTime() } as f64;
+	let toast_seconds = panel.toast_seconds;
+	panel.toasts.retain(|x| now - x.0 < toast_seconds);
+	if !panel.toasts.is_empty() {
+		let (width, height) = ui.frame_size().logical_size;
+		ui.window(im_str!("##capture_toasts"))
+			.position((width as f32 - 410., height as f32 - 60.), ImGuiCond::Always)
+			.size((400., 0.), ImGuiCond::Always)
+			.flags(
+				ImGuiWindowFlags::NoTitleBar
+					| ImGuiWindowFlags::NoResize
+					| ImGuiWindowFlags::NoMove
+					| ImGuiWindowFlags::NoSavedSettings
+					| ImGuiWindowFlags::NoFocusOnAppearing
+					| ImGuiWindowFlags::AlwaysAutoResize,
+			)
+			.build(|| {
+				for (_, message) in &panel.toasts {
+					ui.text_wrapped(&im_str!("{}", message));
+				}
+			});
+	}
+
+	if !panel.open {
+		return;
+	}
+	let mut open = true;
+	ui.window(im_str!("Capture"))
+		.size((320., 160.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.text(format!("Output: {}", panel.dir.display()));
+			if ui.button(im_str!("Screenshot"), (0., 0.)) {
+				panel.screenshot();
+			}
+			ui.separator();
+			ui.slider_float(im_str!("Seconds"), &mut panel.record_seconds, 0.5, 30.).build();
+			match panel.recording {
+				Some(ref recording) => {
+					ui.text(format!("Recording... frame {}, {:.1}s left", recording.frame, recording.remaining.max(0.)))
+				},
+				None => {
+					if ui.button(im_str!("Record"), (0., 0.)) {
+						panel.record();
+					}
+				},
+			}
+		});
+	panel.open = open;
+}
@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use amethyst::{ecs::prelude::*, shrev::EventChannel};
+use imgui::{ImGuiCond, ImGuiSelectableFlags, ImString, Ui};
+
+use crate::debug_menus::MenuCommand;
+
+type Action = Arc<dyn Fn(&World) + Send + Sync>;
+type Get<T> = Arc<dyn Fn(&World) -> T + Send + Sync>;
+type Set<T> = Arc<dyn Fn(&World, T) + Send + Sync>;
+
+#[derive(Clone)]
+enum Cheat {
+	Category { label: ImString, items: Vec<Cheat> },
+	Toggle { label: ImString, get: Get<bool>, set: Set<bool> },
+	Slider { label: ImString, range: (f32, f32), step: f32, get: Get<f32>, set: Set<f32> },
+	Button { label: ImString, action: Action },
+}
+
+impl Cheat {
+	fn label(&self) -> &ImString {
+		match self {
+			Cheat::Category { label, .. }
+			| Cheat::Toggle { label, .. }
+			| Cheat::Slider { label, .. }
+			| Cheat::Button { label, .. } => label,
+		}
+	}
+
+	/// What `CheatNav::Activate` does on this item, other than entering a category.
+	fn activate(&self) -> Option<Action> {
+		match self {
+			Cheat::Toggle { get, set, .. } => {
+				let (get, set) = (get.clone(), set.clone());
+				Some(Arc::new(move |world| set(world, !get(world))))
+			},
+			Cheat::Button { action, .. } => Some(action.clone()),
+			_ => None,
+		}
+	}
+
+	/// What `CheatNav::Left` (-1) and `CheatNav::Right` (1) do on this item.
+	fn adjust(&self, direction: f32) -> Option<Action> {
+		match self {
+			Cheat::Toggle { set, .. } => {
+				let set = set.clone();
+				Some(Arc::new(move |world| set(world, direction > 0.)))
+			},
+			Cheat::Slider { range, step, get, set, .. } => {
+				let (range, step, get, set) = (*range, *step, get.clone(), set.clone());
+				Some(Arc::new(move |world| {
+					set(world, (get(world) + step * direction).max(range.0).min(range.1))
+				}))
+			},
+			_ => None,
+		}
+	}
+}
+
+/// Directional input for the cheat menu, for builds without mouse and keyboard.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CheatNav {
+	Up,
+	Down,
+	/// Decreases sliders and turns toggles off.
+	Left,
+	/// Increases sliders and turns toggles on.
+	Right,
+	/// Enters categories, flips toggles and presses buttons.
+	Activate,
+	/// Leaves the current category.
+	Back,
+	/// Shows or hides the menu.
+	Menu,
+}
+
+/// A hierarchical dev menu of toggles, sliders and buttons bound to closures or `MenuCommand`s, drawn by
+/// `cheat_menu`.
+///
+/// Categories are `/` separated paths created on first use. amethyst 0.10 doesn't read gamepads, so map
+/// your controller's buttons (e.g. from gilrs) to `navigate` calls; the menu works with the mouse too.
+///
+/// ```rust,ignore
+/// world.write_resource::<CheatMenu>()
+///     .toggle("Player", "God mode", |w| w.read_resource::<Cheats>().god, |w, x| w.write_resource::<Cheats>().god = x)
+///     .slider("Player/Movement", "Speed", (0., 20.), 0.5, |w| w.read_resource::<Speed>().0, |w, x| {
+///         w.write_resource::<Speed>().0 = x
+///     })
+///     .command("World", "Skip level", "skip_level");
+/// ```
+#[derive(Default)]
+pub struct CheatMenu {
+	pub open: bool,
+	root: Vec<Cheat>,
+	/// Indices of the entered categories.
+	path: Vec<usize>,
+	cursor: usize,
+	input: Vec<CheatNav>,
+}
+
+impl CheatMenu {
+	fn category(&mut self, path: &str) -> &mut Vec<Cheat> {
+		let mut items = &mut self.root;
+		for name in path.split('/').filter(|x| !x.is_empty()) {
+			let index = match items.iter().position(|x| match x {
+				Cheat::Category { label, .. } => label.to_str() == name,
+				_ => false,
+			}) {
+				Some(x) => x,
+				None => {
+					items.push(Cheat::Category {
+						label: ImString::new(name),
+						items: Vec::new(),
+					});
+					items.len() - 1
+				},
+			};
+			items = match items[index] {
+				Cheat::Category { ref mut items, .. } => items,
+				_ => unreachable!(),
+			};
+		}
+		items
+	}
+
+	fn add(&mut self, path: &str, cheat: Cheat) -> &mut Self {
+		let items = self.category(path);
+		items.retain(|x| x.label() != cheat.label());
+		items.push(cheat);
+		self
+	}
+
+	pub fn toggle<G, S>(&mut self, path: &str, label: &str, get: G, set: S) -> &mut Self
+	where
+		G: Fn(&World) -> bool + Send + Sync + 'static,
+		S: Fn(&World, bool) + Send + Sync + 'static,
+	{
+		self.add(path, Cheat::Toggle {
+			label: ImString::new(label),
+			get: Arc::new(get),
+			set: Arc::new(set),
+		})
+	}
+
+	/// A slider moving by `step` for each `CheatNav::Left` or `Right`.
+	pub fn slider<G, S>(&mut self, path: &str, label: &str, range: (f32, f32), step: f32, get: G, set: S) -> &mut Self
+	where
+		G: Fn(&World) -> f32 + Send + Sync + 'static,
+		S: Fn(&World, f32) + Send + Sync + 'static,
+	{
+		self.add(path, Cheat::Slider {
+			label: ImString::new(label),
+			range,
+			step,
+			get: Arc::new(get),
+			set: Arc::new(set),
+		})
+	}
+
+	pub fn button<F: Fn(&World) + Send + Sync + 'static>(&mut self, path: &str, label: &str, action: F) -> &mut Self {
+		self.add(path, Cheat::Button {
+			label: ImString::new(label),
+			action: Arc::new(action),
+		})
+	}
+
+	/// A button sending `command` as a `MenuCommand`, like the `debug_menus` buttons.
+	pub fn command(&mut self, path: &str, label: &str, command: &str) -> &mut Self {
+		let command = command.to_owned();
+		self.button(path, label, move |world| {
+			if let Some(mut channel) = world.res.try_fetch_mut::<EventChannel<MenuCommand>>() {
+				channel.single_write(MenuCommand(command.clone()));
+			}
+		})
+	}
+
+	/// Removes an item, or a whole category when `label` is `None`.
+	pub fn remove(&mut self, path: &str, label: Option<&str>) {
+		match label {
+			Some(label) => self.category(path).retain(|x| x.label().to_str() != label),
+			None => {
+				let (parent, name) = match path.rfind('/') {
+					Some(x) => (&path[..x], &path[x + 1..]),
+					None => ("", path),
+				};
+				self.category(parent).retain(|x| x.label().to_str() != name);
+			},
+		}
+		self.path.clear();
+		self.cursor = 0;
+	}
+
+	/// Queues input for the next `cheat_menu`.
+	pub fn navigate(&mut self, input: CheatNav) { self.input.push(input); }
+
+	fn items(&self) -> &[Cheat] {
+		let mut items = &self.root[..];
+		for &index in &self.path {
+			items = match items.get(index) {
+				Some(Cheat::Category { items, .. }) => items,
+				_ => return &[],
+			};
+		}
+		items
+	}
+
+	/// Applies queued input, returning the actions to run.
+	fn apply_input(&mut self) -> Vec<Action> {
+		let mut actions = Vec::new();
+		for input in self.input.drain(..).collect::<Vec<_>>() {
+			let len = self.items().len();
+			match input {
+				CheatNav::Menu => self.open = !self.open,
+				_ if !self.open => {},
+				CheatNav::Up if len > 0 => self.cursor = (self.cursor + len - 1) % len,
+				CheatNav::Down if len > 0 => self.cursor = (self.cursor + 1) % len,
+				CheatNav::Back => self.cursor = self.path.pop().unwrap_or(0),
+				CheatNav::Activate => {
+					let action = match self.items().get(self.cursor) {
+						Some(Cheat::Category { .. }) => None,
+						Some(item) => Some(item.activate()),
+						None => continue,
+					};
+					match action {
+						Some(action) => actions.extend(action),
+						None => {
+							self.path.push(self.cursor);
+							self.cursor = 0;
+						},
+					}
+				},
+				CheatNav::Left | CheatNav::Right => {
+					let direction = if input == CheatNav::Left { -1. } else { 1. };
+					actions.extend(self.items().get(self.cursor).and_then(|x| x.adjust(direction)));
+				},
+				_ => {},
+			}
+		}
+		actions
+	}
+}
+
+pub fn cheat_menu(ui: &Ui, world: &World) {
+	// Snapshot the current category so the cheats can use the resource themselves
+	let (actions, items, title, cursor, nested) = {
+		let mut menu = match world.res.try_fetch_mut::<CheatMenu>() {
+			Some(x) => x,
+			_ => return,
+		};
+		let actions = menu.apply_input();
+		if !menu.open {
+			(actions, Vec::new(), None, 0, false)
+		} else {
+			let mut title = "Cheats".to_owned();
+			let mut items = &menu.root[..];
+			for &index in &menu.path {
+				if let Some(Cheat::Category { label, items: children }) = items.get(index) {
+					title.push_str(" / ");
+					title.push_str(label.to_str());
+					items = children;
+				}
+			}
+			menu.cursor = menu.cursor.min(items.len().saturating_sub(1));
+			(actions, items.to_vec(), Some(title), menu.cursor, !menu.path.is_empty())
+		}
+	};
+	for action in actions {
+		action(world);
+	}
+	let title = match title {
+		Some(x) => x,
+		_ => return,
+	};
+
+	let mut open = true;
+	let mut cursor = cursor;
+	let mut enter = None;
+	let mut back = false;
+	ui.window(im_str!("Cheats###cheats"))
+		.size((320., 400.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.text(title.as_str());
+			if nested {
+				ui.same_line(0.);
+				back = ui.small_button(im_str!("Back"));
+			}
+			ui.separator();
+			for (i, item) in items.iter().enumerate() {
+				ui.text(if i == cursor { ">" } else { " " });
+				ui.same_line(0.);
+				let id = im_str!("{}##cheat{}", item.label().to_str(), i);
+				match item {
+					Cheat::Category { label, .. } => {
+						let label = im_str!("{} >##cheat{}", label.to_str(), i);
+						if ui.selectable(&label, false, ImGuiSelectableFlags::empty(), (0., 0.)) {
+							enter = Some(i);
+						}
+					},
+					Cheat::Toggle { get, set, .. } => {
+						let mut value = get(world);
+						if ui.checkbox(&id, &mut value) {
+							set(world, value);
+							cursor = i;
+						}
+					},
+					Cheat::Slider { range, get, set, .. } => {
+						let mut value = get(world);
+						if ui.slider_float(&id, &mut value, range.0, range.1).build() {
+							set(world, value);
+							cursor = i;
+						}
+					},
+					Cheat::Button { action, .. } => {
+						if ui.button(&id, (0., 0.)) {
+							action(world);
+							cursor = i;
+						}
+					},
+				}
+			}
+		});
+
+	if let Some(mut menu) = world.res.try_fetch_mut::<CheatMenu>() {
+		menu.open = open;
+		menu.cursor = cursor;
+		if let Some(index) = enter {
+			menu.path.push(index);
+			menu.cursor = 0;
+		} else if back {
+			menu.cursor = menu.path.pop().unwrap_or(0);
+		}
+	}
+}
@@ -0,0 +1,75 @@
+use std::{
+	ffi::{CStr, CString},
+	os::raw::{c_char, c_void},
+	ptr,
+	sync::Mutex,
+};
+
+use imgui::sys;
+#[cfg(feature = "os-clipboard")]
+use os_clipboard::ClipboardProvider;
+
+/// Where imgui's copy and paste go, e.g. a platform API on consoles or a mock in tests.
+pub trait ClipboardBackend: Send {
+	fn get(&mut self) -> Option<String>;
+	fn set(&mut self, text: &str);
+}
+
+/// The system clipboard, used by default with the `os-clipboard` feature.
+#[cfg(feature = "os-clipboard")]
+pub struct OsClipboard(::os_clipboard::ClipboardContext);
+
+#[cfg(feature = "os-clipboard")]
+impl OsClipboard {
+	pub fn new() -> Option<Self> { ClipboardProvider::new().ok().map(OsClipboard) }
+}
+
+#[cfg(feature = "os-clipboard")]
+impl ClipboardBackend for OsClipboard {
+	fn get(&mut self) -> Option<String> { self.0.get_contents().ok() }
+
+	fn set(&mut self, text: &str) { let _ = self.0.set_contents(text.to_owned()); }
+}
+
+pub(crate) struct ClipboardContext {
+	backend: Mutex<Box<dyn ClipboardBackend>>,
+	/// imgui expects the pasted text to stay alive until the next paste.
+	last: CString,
+}
+
+impl ClipboardContext {
+	pub(crate) fn new<B: ClipboardBackend + 'static>(backend: B) -> Self {
+		Self {
+			backend: Mutex::new(Box::new(backend)),
+			last: CString::default(),
+		}
+	}
+}
+
+extern "C" fn get_clipboard(user_data: *mut c_void) -> *const c_char {
+	let context = unsafe { &mut *(user_data as *mut ClipboardContext) };
+	let text = context.backend.lock().ok().and_then(|mut x| x.get()).and_then(|x| CString::new(x).ok());
+	match text {
+		Some(text) => {
+			context.last = text;
+			context.last.as_ptr()
+		},
+		None => ptr::null(),
+	}
+}
+
+extern "C" fn set_clipboard(user_data: *mut c_void, text: *const c_char) {
+	let context = unsafe { &mut *(user_data as *mut ClipboardContext) };
+	let text = unsafe { CStr::from_ptr(text) }.to_string_lossy();
+	if let Ok(mut backend) = context.backend.lock() {
+		backend.set(&text);
+	}
+}
+
+/// Points the current imgui context at `context`, which must not move or be dropped while installed.
+pub(crate) unsafe fn install(context: &mut ClipboardContext) {
+	let io = &mut *sys::igGetIO();
+	io.get_clipboard_text_fn = Some(get_clipboard);
+	io.set_clipboard_text_fn = Some(set_clipboard);
+	io.clipboard_user_data = context as *mut ClipboardContext as *mut c_void;
+}
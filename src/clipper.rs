@@ -0,0 +1,25 @@
+use std::{mem, os::raw::c_int};
+
+use imgui::sys;
+
+/// Calls `row` only for the rows of `count` scrolled into the current window, and keeps the scroll range
+/// as if all of them were drawn, so long lists take the same time to draw whatever their length.
+///
+/// Rows have to be `row_height` tall; with `None`, the first row is measured, e.g. for text lines.
+///
+/// ```rust,ignore
+/// ui.child_frame(im_str!("##log"), (0., 0.)).build(|| {
+///     clipped_rows(log.len(), None, |i| ui.text(&log[i]));
+/// });
+/// ```
+pub fn clipped_rows<F: FnMut(usize)>(count: usize, row_height: Option<f32>, mut row: F) {
+	let count = count.min(c_int::max_value() as usize) as c_int;
+	// Stays on the stack: imgui only holds on to it between Begin and the last Step
+	let mut clipper: sys::ImGuiListClipper = unsafe { mem::zeroed() };
+	unsafe { sys::ImGuiListClipper_Begin(&mut clipper, count, row_height.unwrap_or(-1.)) };
+	while unsafe { sys::ImGuiListClipper_Step(&mut clipper) } {
+		for i in clipper.display_start.max(0)..clipper.display_end.min(count) {
+			row(i as usize);
+		}
+	}
+}
@@ -0,0 +1,266 @@
+use std::{cell::RefCell, collections::HashSet, path::Path};
+
+use imgui::{sys, ImStr, ImString, Ui};
+
+use crate::clipper::clipped_rows;
+
+thread_local! {
+	/// Ids of `code_editor`s switched to editing.
+	static EDITING: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+const GLSL_KEYWORDS: &[&str] = &[
+	"attribute", "break", "case", "centroid", "const", "continue", "default", "discard", "do", "else", "false", "flat",
+	"for", "highp", "if", "in", "inout", "layout", "lowp", "mediump", "noperspective", "out", "precision", "return",
+	"smooth", "struct", "switch", "true", "uniform", "varying", "while",
+];
+const GLSL_TYPES: &[&str] = &[
+	"bool", "bvec2", "bvec3", "bvec4", "float", "int", "ivec2", "ivec3", "ivec4", "mat2", "mat3", "mat4", "sampler2D",
+	"sampler2DArray", "sampler2DShadow", "sampler3D", "samplerCube", "uint", "uvec2", "uvec3", "uvec4", "vec2", "vec3",
+	"vec4", "void",
+];
+const RHAI_KEYWORDS: &[&str] = &[
+	"as", "break", "const", "continue", "do", "else", "export", "false", "fn", "for", "if", "import", "in", "let",
+	"loop", "private", "return", "switch", "this", "throw", "true", "until", "while",
+];
+const RON_KEYWORDS: &[&str] = &["false", "None", "Some", "true"];
+
+/// Syntax to highlight in `CodeView`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Language {
+	Plain,
+	Ron,
+	Glsl,
+	Rhai,
+}
+
+impl Language {
+	/// Guesses from the file extension, falling back to `Plain`.
+	pub fn from_path(path: &Path) -> Self {
+		match path.extension().and_then(|x| x.to_str()) {
+			Some("ron") => Language::Ron,
+			Some("glsl") | Some("vert") | Some("frag") | Some("geom") | Some("comp") | Some("vs") | Some("fs") => {
+				Language::Glsl
+			},
+			Some("rhai") => Language::Rhai,
+			_ => Language::Plain,
+		}
+	}
+
+	fn keywords(self) -> &'static [&'static str] {
+		match self {
+			Language::Plain => &[],
+			Language::Ron => RON_KEYWORDS,
+			Language::Glsl => GLSL_KEYWORDS,
+			Language::Rhai => RHAI_KEYWORDS,
+		}
+	}
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Token {
+	Text,
+	Keyword,
+	Type,
+	Number,
+	String,
+	Comment,
+	Directive,
+}
+
+impl Token {
+	fn color(self) -> [f32; 4] {
+		match self {
+			Token::Text => [0.86, 0.86, 0.86, 1.],
+			Token::Keyword => [0.8, 0.47, 0.87, 1.],
+			Token::Type => [0.35, 0.75, 0.9, 1.],
+			Token::Number => [0.82, 0.6, 0.4, 1.],
+			Token::String => [0.6, 0.8, 0.45, 1.],
+			Token::Comment => [0.5, 0.55, 0.5, 1.],
+			Token::Directive => [0.9, 0.75, 0.35, 1.],
+		}
+	}
+}
+
+fn push(out: &mut Vec<(Token, usize, usize)>, token: Token, start: usize, end: usize) {
+	if let Some(last) = out.last_mut() {
+		if last.0 == Token::Text && token == Token::Text && last.2 == start {
+			last.2 = end;
+			return;
+		}
+	}
+	if start < end {
+		out.push((token, start, end));
+	}
+}
+
+/// Splits `line` into highlighted byte ranges; `in_comment` carries block comments over to the next line.
+fn tokenize(line: &str, language: Language, in_comment: &mut bool, out: &mut Vec<(Token, usize, usize)>) {
+	out.clear();
+	if language == Language::Plain {
+		push(out, Token::Text, 0, line.len());
+		return;
+	}
+	let bytes = line.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		let rest = &line[i..];
+		let c = bytes[i];
+		let (token, len) = if *in_comment {
+			match rest.find("*/") {
+				Some(x) => {
+					*in_comment = false;
+					(Token::Comment, x + 2)
+				},
+				None => (Token::Comment, rest.len()),
+			}
+		} else if rest.starts_with("//") {
+			(Token::Comment, rest.len())
+		} else if rest.starts_with("/*") {
+			*in_comment = true;
+			(Token::Comment, 2)
+		} else if c == b'#' && language != Language::Rhai {
+			(Token::Directive, rest.len())
+		} else if (c == b'"' || c == b'\'') && language != Language::Glsl {
+			let mut len = rest.len();
+			let mut escaped = false;
+			for (j, x) in rest.bytes().enumerate().skip(1) {
+				match x {
+					_ if escaped => escaped = false,
+					b'\\' => escaped = true,
+					_ if x == c => {
+						len = j + 1;
+						break;
+					},
+					_ => {},
+				}
+			}
+			(Token::String, len)
+		} else if c.is_ascii_digit() || (c == b'.' && bytes.get(i + 1).map_or(false, u8::is_ascii_digit)) {
+			let len = rest.find(|x: char| !(x.is_ascii_alphanumeric() || x == '.' || x == '_')).unwrap_or(rest.len());
+			(Token::Number, len)
+		} else if c.is_ascii_alphabetic() || c == b'_' {
+			let len = rest.find(|x: char| !(x.is_ascii_alphanumeric() || x == '_')).unwrap_or(rest.len());
+			let word = &rest[..len];
+			let token = if language.keywords().contains(&word) {
+				Token::Keyword
+			} else if (language == Language::Glsl && GLSL_TYPES.contains(&word))
+				|| (language == Language::Ron && c.is_ascii_uppercase())
+			{
+				Token::Type
+			} else {
+				Token::Text
+			};
+			(token, len)
+		} else {
+			(Token::Text, rest.chars().next().map_or(1, char::len_utf8))
+		};
+		push(out, token, i, i + len);
+		i += len;
+	}
+}
+
+/// A read-only source listing with line numbers and highlighting, drawn by `build`.
+///
+/// ```rust,ignore
+/// CodeView::new(&source, Language::Glsl).error_line(Some(12)).build(ui, im_str!("##shader"));
+/// ```
+pub struct CodeView<'a> {
+	source: &'a str,
+	language: Language,
+	size: (f32, f32),
+	error_line: Option<usize>,
+}
+
+impl<'a> CodeView<'a> {
+	pub fn new(source: &'a str, language: Language) -> Self {
+		Self {
+			source,
+			language,
+			size: (0., 0.),
+			error_line: None,
+		}
+	}
+
+	/// Size of the scrolling frame, as for `child_frame`; zero fills the window.
+	pub fn size(mut self, size: (f32, f32)) -> Self {
+		self.size = size;
+		self
+	}
+
+	/// 1-based line to mark and scroll to, e.g. where a compiler reported an error.
+	pub fn error_line(mut self, line: Option<usize>) -> Self {
+		self.error_line = line;
+		self
+	}
+
+	pub fn build(self, ui: &Ui, id: &ImStr) {
+		let lines = self.source.lines().map(|x| x.replace('\t', "    ")).collect::<Vec<_>>();
+		// Block comments can start above the visible rows, so find where each line starts
+		let mut comments = Vec::with_capacity(lines.len());
+		let mut in_comment = false;
+		let mut tokens = Vec::new();
+		for line in &lines {
+			comments.push(in_comment);
+			if line.contains("/*") || in_comment {
+				tokenize(line, self.language, &mut in_comment, &mut tokens);
+			}
+		}
+
+		let width = lines.len().to_string().len();
+		let error_line = self.error_line;
+		// A new child for every error line, so it scrolls to each new one when it appears
+		let id = im_str!("{}_{:?}", id.to_str(), error_line);
+		ui.child_frame(&id, self.size).show_borders(true).build(|| {
+			if let Some(line) = error_line {
+				if unsafe { sys::igIsWindowAppearing() } {
+					let height = unsafe { sys::igGetTextLineHeightWithSpacing() };
+					let y = (line.saturating_sub(1) as f32 * height - unsafe { sys::igGetWindowHeight() } / 2.).max(0.);
+					unsafe { sys::igSetScrollY(y) };
+				}
+			}
+			clipped_rows(lines.len(), None, |i| {
+				let number = im_str!("{:>width$} ", i + 1, width = width);
+				match error_line {
+					Some(line) if line == i + 1 => ui.text_colored([1., 0.35, 0.35, 1.], number.to_str()),
+					_ => ui.text_disabled(&number),
+				}
+				let mut in_comment = comments[i];
+				tokenize(&lines[i], self.language, &mut in_comment, &mut tokens);
+				for &(token, start, end) in &tokens {
+					unsafe { sys::igSameLine(0., 0.) };
+					ui.text_colored(token.color(), &lines[i][start..end]);
+				}
+			});
+		});
+	}
+}
+
+/// `CodeView` of `source`, with an "Edit" toggle that swaps it for a plain multiline input;
+/// returns whether the text changed.
+pub fn code_editor(
+	ui: &Ui,
+	id: &ImStr,
+	source: &mut ImString,
+	language: Language,
+	size: (f32, f32),
+	error_line: Option<usize>,
+) -> bool {
+	let key = id.to_str().to_owned();
+	let mut editing = EDITING.with(|x| x.borrow().contains(&key));
+	if ui.checkbox(&im_str!("Edit##{}", key), &mut editing) {
+		EDITING.with(|x| match editing {
+			true => x.borrow_mut().insert(key.clone()),
+			false => x.borrow_mut().remove(&key),
+		});
+	}
+	if !editing {
+		CodeView::new(source.to_str(), language).size(size).error_line(error_line).build(ui, id);
+		return false;
+	}
+	// imgui edits in place, so leave room to type
+	if source.capacity() < source.to_str().len() + 1024 {
+		source.reserve(4096);
+	}
+	ui.input_text_multiline(id, source, size).build()
+}
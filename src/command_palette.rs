@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use amethyst::{
+	core::Named,
+	ecs::{prelude::*, storage::MaskedStorage},
+	shrev::EventChannel,
+};
+use imgui::{sys, ImGuiCond, ImGuiSelectableFlags, ImGuiWindowFlags, ImString, ImVec2, Ui};
+
+use crate::{
+	clipper::clipped_rows,
+	debug_menus::{DebugMenus, MenuCommand, MenuItem},
+	selection::SelectedEntity,
+	tool_windows::ToolWindows,
+	KEY_P,
+};
+
+type Action = Arc<dyn Fn(&World) + Send + Sync>;
+
+/// Keyboard-driven launcher over everything registered with the crate, drawn by `command_palette`.
+///
+/// Ctrl+P opens it; typing fuzzy-searches its own commands, `ToolWindows`, `DebugMenus` windows and buttons,
+/// and entities with a `Named` component, and Enter runs, opens or selects the highlighted one.
+///
+/// ```rust,ignore
+/// palette.register("Reload level", |world| world.write_resource::<Level>().reload = true);
+/// ```
+pub struct CommandPalette {
+	pub open: bool,
+	/// List `Named` entities, selecting them into `SelectedEntity`; only once that resource is inserted.
+	pub entities: bool,
+	/// Open on Ctrl+P; turn it off to call `toggle` from the game's own bindings.
+	pub hotkey: bool,
+	commands: Vec<(String, Action)>,
+	query: ImString,
+	selected: usize,
+}
+
+impl Default for CommandPalette {
+	fn default() -> Self {
+		Self {
+			open: false,
+			entities: true,
+			hotkey: true,
+			commands: Vec::new(),
+			query: ImString::with_capacity(128),
+			selected: 0,
+		}
+	}
+}
+
+impl CommandPalette {
+	/// Adds a command, or replaces the one with the same name.
+	pub fn register<S: Into<String>, F: Fn(&World) + Send + Sync + 'static>(&mut self, name: S, action: F) {
+		let name = name.into();
+		self.unregister(&name);
+		self.commands.push((name, Arc::new(action)));
+	}
+
+	pub fn unregister(&mut self, name: &str) { self.commands.retain(|x| x.0 != name); }
+
+	pub fn toggle(&mut self) {
+		self.open = !self.open;
+		self.query.clear();
+		self.selected = 0;
+	}
+}
+
+/// How well `query` matches `name` as a case-insensitive subsequence, favoring runs of consecutive
+/// characters and matches at the start of words; `None` if it doesn't.
+fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+	let mut score = 0;
+	let mut previous_end = None;
+	let mut chars = name.char_indices();
+	for q in query.chars().flat_map(char::to_lowercase).filter(|x| !x.is_whitespace()) {
+		loop {
+			let (i, c) = chars.next()?;
+			if c.to_lowercase().any(|x| x == q) {
+				score += 1;
+				if previous_end == Some(i) {
+					score += 4;
+				}
+				if i == 0 || name[..i].ends_with(|x: char| !x.is_alphanumeric()) {
+					score += 3;
+				}
+				previous_end = Some(i + c.len_utf8());
+				break;
+			}
+		}
+	}
+	// Shorter names first among equal matches
+	Some(score * 100 - name.len().min(99) as i32)
+}
+
+fn menu_commands(items: &[MenuItem], out: &mut Vec<(String, Action)>) {
+	for item in items {
+		match item {
+			MenuItem::Button { label, command } => {
+				let command = command.clone();
+				out.push((
+					format!("Command: {}", label),
+					Arc::new(move |world: &World| {
+						if let Some(mut channel) = world.res.try_fetch_mut::<EventChannel<MenuCommand>>() {
+							channel.single_write(MenuCommand(command.clone()));
+						}
+					}),
+				));
+			},
+			MenuItem::Group { items, .. } => menu_commands(items, out),
+			_ => {},
+		}
+	}
+}
+
+/// Everything the palette can run, gathered while it's open.
+fn entries(world: &World, palette: &CommandPalette) -> Vec<(String, Action)> {
+	let mut out = palette.commands.clone();
+	if let Some(windows) = world.res.try_fetch::<ToolWindows>() {
+		for name in windows.names() {
+			let name = name.to_owned();
+			out.push((
+				format!("Window: {}", name),
+				Arc::new(move |world: &World| {
+					if let Some(mut windows) = world.res.try_fetch_mut::<ToolWindows>() {
+						windows.set_open(&name, true);
+					}
+				}),
+			));
+		}
+	}
+	if let Some(menus) = world.res.try_fetch::<DebugMenus>() {
+		for (i, window) in menus.windows.iter().enumerate() {
+			out.push((
+				format!("Menu: {}", window.title),
+				Arc::new(move |world: &World| {
+					if let Some(mut menus) = world.res.try_fetch_mut::<DebugMenus>() {
+						if let Some(window) = menus.windows.get_mut(i) {
+							window.open = true;
+						}
+					}
+				}),
+			));
+			menu_commands(&window.items, &mut out);
+		}
+	}
+	// Only games that name entities have the storage
+	if palette.entities && world.res.has_value::<SelectedEntity>() && world.res.has_value::<MaskedStorage<Named>>() {
+		let names = world.read_storage::<Named>();
+		for (entity, named) in (&world.entities(), &names).join() {
+			out.push((
+				format!("Entity: {} ({})", named.name, entity.id()),
+				Arc::new(move |world: &World| {
+					if let Some(mut selected) = world.res.try_fetch_mut::<SelectedEntity>() {
+						selected.0 = Some(entity);
+					}
+				}),
+			));
+		}
+	}
+	out
+}
+
+pub fn command_palette(ui: &Ui, world: &World) {
+	let action = {
+		let mut palette = match world.res.try_fetch_mut::<CommandPalette>() {
+			Some(x) => x,
+			_ => return,
+		};
+		let palette = &mut *palette;
+		let io = unsafe { &*sys::igGetIO() };
+		if palette.hotkey && io.key_ctrl && unsafe { sys::igIsKeyPressed(i32::from(KEY_P), false) } {
+			palette.toggle();
+		}
+		if !palette.open {
+			return;
+		}
+
+		let query = palette.query.to_str().to_owned();
+		let mut matches = entries(world, palette)
+			.into_iter()
+			.filter_map(|(name, action)| fuzzy_score(&query, &name).map(|score| (score, name, action)))
+			.collect::<Vec<_>>();
+		matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+		palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+
+		let key = |key| unsafe { sys::igIsKeyPressed(sys::igGetKeyIndex(key), true) };
+		if key(sys::ImGuiKey::DownArrow) {
+			palette.selected = (palette.selected + 1).min(matches.len().saturating_sub(1));
+		}
+		if key(sys::ImGuiKey::UpArrow) {
+			palette.selected = palette.selected.saturating_sub(1);
+		}
+		let mut run = match key(sys::ImGuiKey::Enter) {
+			true => Some(palette.selected),
+			false => None,
+		};
+		let mut open = !key(sys::ImGuiKey::Escape);
+
+		let (width, height) = ui.frame_size().logical_size;
+		unsafe {
+			let position = ImVec2::new(width as f32 / 2., height as f32 * 0.2);
+			sys::igSetNextWindowPos(position, ImGuiCond::Always, ImVec2::new(0.5, 0.));
+		}
+		// Zero height fits the list
+		ui.window(im_str!("##command_palette"))
+			.size((520., 0.), ImGuiCond::Always)
+			.flags(
+				ImGuiWindowFlags::NoTitleBar
+					| ImGuiWindowFlags::NoResize
+					| ImGuiWindowFlags::NoMove
+					| ImGuiWindowFlags::NoSavedSettings,
+			)
+			.build(|| {
+				if unsafe { sys::igIsWindowAppearing() } {
+					unsafe { sys::igSetKeyboardFocusHere(0) };
+				}
+				ui.push_item_width(-1.);
+				if ui.input_text(im_str!("##query"), &mut palette.query).build() {
+					palette.selected = 0;
+				}
+				ui.pop_item_width();
+				let rows = matches.len().min(12).max(1) as f32;
+				let row_height = unsafe { sys::igGetTextLineHeightWithSpacing() };
+				let selected = palette.selected;
+				ui.child_frame(im_str!("##matches"), (0., rows * row_height + 8.)).build(|| {
+					clipped_rows(matches.len(), Some(row_height), |i| {
+						let label = im_str!("{}##{}", matches[i].1, i);
+						if ui.selectable(&label, i == selected, ImGuiSelectableFlags::empty(), (0., 0.)) {
+							run = Some(i);
+						}
+						if i == selected && (key(sys::ImGuiKey::DownArrow) || key(sys::ImGuiKey::UpArrow)) {
+							unsafe { sys::igSetScrollHere(0.5) };
+						}
+					});
+				});
+				// Clicking elsewhere dismisses it, like a popup
+				if !unsafe { sys::igIsWindowFocused(sys::ImGuiFocusedFlags::RootAndChildWindows) } {
+					open = false;
+				}
+			});
+
+		let action = run.and_then(|i| matches.get(i)).map(|x| x.2.clone());
+		if action.is_some() || !open {
+			palette.toggle();
+		}
+		action
+	};
+
+	// Run without holding the palette, so actions can use it too
+	if let Some(action) = action {
+		action(world);
+	}
+}
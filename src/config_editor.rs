@@ -0,0 +1,211 @@
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImGuiSelectableFlags, ImString, Ui};
+use ron;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{self, Value};
+
+use crate::{
+	code_view::{CodeView, Language},
+	value_editor::edit_value,
+};
+
+type Load = Box<dyn Fn(&Path) -> Result<Value, String> + Send + Sync>;
+type Source = Box<dyn Fn(&Value) -> Result<String, String> + Send + Sync>;
+type Apply = Arc<dyn Fn(&World, &Value) -> Result<(), String> + Send + Sync>;
+
+struct ConfigFile {
+	name: ImString,
+	path: PathBuf,
+	load: Load,
+	source: Source,
+	apply: Option<Apply>,
+	/// Apply every edit right away instead of on request.
+	live: bool,
+	/// Show the RON that saving would write instead of the value editor.
+	show_source: bool,
+	value: Option<Value>,
+	/// Read from disk yet; failures stay in `status` until "Reload".
+	loaded: bool,
+	dirty: bool,
+	status: Option<String>,
+}
+
+/// Editor for RON config files like `DisplayConfig`, input bindings or the game's own, drawn by
+/// `config_editor_window`.
+///
+/// Files are edited with `edit_value` and only written when they still deserialize into their type.
+///
+/// ```rust,ignore
+/// configs.register::<DisplayConfig>("Display", "resources/display_config.ron");
+/// configs.register_with_apply::<Tuning, _>("Tuning", "resources/tuning.ron", |world, tuning| {
+///     *world.write_resource::<Tuning>() = tuning;
+/// });
+/// ```
+#[derive(Default)]
+pub struct ConfigEditor {
+	pub open: bool,
+	configs: Vec<ConfigFile>,
+	selected: usize,
+}
+
+impl ConfigEditor {
+	pub fn register<T>(&mut self, name: &str, path: &str)
+	where
+		T: Serialize + DeserializeOwned + 'static,
+	{
+		self.add::<T>(name, path, None);
+	}
+
+	/// Like `register`, with a way to apply changes to the running game; amethyst 0.10 can't reconfigure
+	/// everything live (the window's vsync for one), so what it does is up to the game.
+	pub fn register_with_apply<T, F>(&mut self, name: &str, path: &str, apply: F)
+	where
+		T: Serialize + DeserializeOwned + 'static,
+		F: Fn(&World, T) + Send + Sync + 'static,
+	{
+		let apply: Apply = Arc::new(move |world, value| {
+			let config = serde_json::from_value::<T>(value.clone()).map_err(|e| e.to_string())?;
+			apply(world, config);
+			Ok(())
+		});
+		self.add::<T>(name, path, Some(apply));
+	}
+
+	fn add<T>(&mut self, name: &str, path: &str, apply: Option<Apply>)
+	where
+		T: Serialize + DeserializeOwned + 'static,
+	{
+		self.configs.retain(|x| x.name.to_str() != name);
+		self.configs.push(ConfigFile {
+			name: ImString::new(name),
+			path: PathBuf::from(path),
+			load: Box::new(|path| {
+				let source = fs::read_to_string(path).map_err(|e| e.to_string())?;
+				let config = ron::de::from_str::<T>(&source).map_err(|e| e.to_string())?;
+				serde_json::to_value(&config).map_err(|e| e.to_string())
+			}),
+			source: Box::new(|value| {
+				let config = serde_json::from_value::<T>(value.clone()).map_err(|e| e.to_string())?;
+				ron::ser::to_string_pretty(&config, Default::default()).map_err(|e| e.to_string())
+			}),
+			apply,
+			live: false,
+			show_source: false,
+			value: None,
+			loaded: false,
+			dirty: false,
+			status: None,
+		});
+	}
+}
+
+pub fn config_editor_window(ui: &Ui, world: &World) {
+	let pending = {
+		let mut editor = match world.res.try_fetch_mut::<ConfigEditor>() {
+			Some(x) => x,
+			_ => return,
+		};
+		let editor = &mut *editor;
+		if !editor.open {
+			return;
+		}
+
+		let mut pending = None;
+		let mut open = true;
+		let configs = &mut editor.configs;
+		let selected = &mut editor.selected;
+		ui.window(im_str!("Configs"))
+			.size((620., 480.), ImGuiCond::FirstUseEver)
+			.opened(&mut open)
+			.build(|| {
+				ui.child_frame(im_str!("##files"), (160., 0.)).show_borders(true).build(|| {
+					for (i, config) in configs.iter().enumerate() {
+						let label = match config.dirty {
+							true => im_str!("{}*", config.name.to_str()),
+							false => config.name.clone(),
+						};
+						if ui.selectable(&label, *selected == i, ImGuiSelectableFlags::empty(), (0., 0.)) {
+							*selected = i;
+						}
+					}
+				});
+				ui.same_line(0.);
+				let config = match configs.get_mut(*selected) {
+					Some(x) => x,
+					_ => return,
+				};
+				ui.child_frame(im_str!("##config"), (0., 0.)).build(|| {
+					ui.text(format!("{}", config.path.display()));
+					if ui.small_button(im_str!("Reload")) || !config.loaded {
+						config.loaded = true;
+						let loaded = (config.load)(&config.path);
+						config.status = loaded.as_ref().err().map(|e| format!("Can't load: {}", e));
+						config.value = loaded.ok();
+						config.dirty = false;
+					}
+					if config.value.is_some() {
+						ui.same_line(0.);
+						if ui.small_button(im_str!("Save")) {
+							let source = (config.source)(config.value.as_ref().unwrap());
+							match source.and_then(|x| fs::write(&config.path, x).map_err(|e| e.to_string())) {
+								Ok(()) => {
+									config.status = Some("Saved".to_owned());
+									config.dirty = false;
+								},
+								Err(e) => config.status = Some(format!("Not saved, invalid: {}", e)),
+							}
+						}
+					}
+					let mut apply_now = false;
+					if config.apply.is_some() {
+						ui.same_line(0.);
+						apply_now = ui.small_button(im_str!("Apply"));
+						ui.same_line(0.);
+						ui.checkbox(im_str!("Live"), &mut config.live);
+					}
+					ui.same_line(0.);
+					ui.checkbox(im_str!("Source"), &mut config.show_source);
+					if let Some(ref status) = config.status {
+						ui.text_wrapped(&im_str!("{}", status));
+					}
+					ui.separator();
+					let mut changed = false;
+					if config.show_source {
+						if let Some(ref value) = config.value {
+							match (config.source)(value) {
+								Ok(source) => CodeView::new(&source, Language::Ron).build(ui, im_str!("##source")),
+								Err(e) => ui.text_colored([1., 0.35, 0.35, 1.], e),
+							}
+						}
+					} else if let Some(ref mut value) = config.value {
+						changed = edit_value(ui, config.name.to_str(), value);
+						config.dirty |= changed;
+					}
+					if apply_now || (changed && config.live) {
+						if let (Some(apply), Some(value)) = (config.apply.clone(), config.value.clone()) {
+							pending = Some((*selected, apply, value));
+						}
+					}
+				});
+			});
+		editor.open = open;
+		pending
+	};
+
+	// Apply without holding the editor, so the game's code can use any resource
+	if let Some((index, apply, value)) = pending {
+		if let Err(e) = apply(world, &value) {
+			if let Some(mut editor) = world.res.try_fetch_mut::<ConfigEditor>() {
+				if let Some(config) = editor.configs.get_mut(index) {
+					config.status = Some(format!("Can't apply: {}", e));
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,141 @@
+use std::{path::Path, sync::Arc};
+
+use amethyst::ecs::{prelude::*, storage::MaskedStorage};
+use imgui::{sys, ImString, Ui};
+
+type EntityPredicate = Box<dyn Fn(&World, Entity) -> bool + Send + Sync>;
+type EntityAction = Arc<dyn Fn(&World, Entity) + Send + Sync>;
+type AssetAction = Arc<dyn Fn(&World, &Path) + Send + Sync>;
+
+/// What a context menu item does, returned to the panel that drew it to run once it let go of its own
+/// resources.
+pub type ContextAction = Box<dyn FnOnce(&World)>;
+
+struct EntityItem {
+	label: String,
+	predicate: EntityPredicate,
+	action: EntityAction,
+}
+
+struct AssetItem {
+	label: String,
+	/// Lowercase extensions it applies to, or empty for every asset.
+	extensions: Vec<String>,
+	action: AssetAction,
+}
+
+/// Right-click menu items tools add to the entity rows and asset entries of other panels, so they can be
+/// extended without forking them. Panels show them with `entity_context_menu` and `asset_context_menu`.
+///
+/// ```rust,ignore
+/// menus
+///     .component_item::<Transform, _>("Reset transform", |world, entity| {
+///         world.write_storage::<Transform>().insert(entity, Transform::default()).unwrap();
+///     })
+///     .asset_item("Open in editor", &["png"], |_, path| open_in_editor(path));
+/// ```
+#[derive(Default)]
+pub struct ContextMenus {
+	entities: Vec<EntityItem>,
+	assets: Vec<AssetItem>,
+}
+
+impl ContextMenus {
+	/// An item for entities `predicate` accepts.
+	pub fn entity_item<P, F>(&mut self, label: &str, predicate: P, action: F) -> &mut Self
+	where
+		P: Fn(&World, Entity) -> bool + Send + Sync + 'static,
+		F: Fn(&World, Entity) + Send + Sync + 'static,
+	{
+		self.entities.push(EntityItem {
+			label: label.to_owned(),
+			predicate: Box::new(predicate),
+			action: Arc::new(action),
+		});
+		self
+	}
+
+	/// An item for entities with a `T` component.
+	pub fn component_item<T, F>(&mut self, label: &str, action: F) -> &mut Self
+	where
+		T: Component,
+		F: Fn(&World, Entity) + Send + Sync + 'static,
+	{
+		let has = |world: &World, entity| {
+			world.res.has_value::<MaskedStorage<T>>() && world.read_storage::<T>().contains(entity)
+		};
+		self.entity_item(label, has, action)
+	}
+
+	/// An item for asset files with one of `extensions`, or for every asset if it's empty.
+	pub fn asset_item<F>(&mut self, label: &str, extensions: &[&str], action: F) -> &mut Self
+	where
+		F: Fn(&World, &Path) + Send + Sync + 'static,
+	{
+		self.assets.push(AssetItem {
+			label: label.to_owned(),
+			extensions: extensions.iter().map(|x| x.to_lowercase()).collect(),
+			action: Arc::new(action),
+		});
+		self
+	}
+
+	/// Removes every entity and asset item called `label`.
+	pub fn remove(&mut self, label: &str) {
+		self.entities.retain(|x| x.label != label);
+		self.assets.retain(|x| x.label != label);
+	}
+}
+
+/// Draws a context menu for the last item with `items`, returning the chosen one's action.
+fn context_menu<A: Clone, F: FnOnce() -> Vec<(String, A)>>(ui: &Ui, id: &str, items: F) -> Option<A> {
+	let id = ImString::new(id);
+	if !unsafe { sys::igBeginPopupContextItem(id.as_ptr(), 1) } {
+		return None;
+	}
+	let items = items();
+	let mut chosen = None;
+	for (label, action) in &items {
+		if ui.menu_item(&ImString::new(label.as_str())).build() {
+			chosen = Some(action.clone());
+		}
+	}
+	if items.is_empty() {
+		ui.text_disabled("Nothing to do");
+	}
+	unsafe { sys::igEndPopup() };
+	chosen
+}
+
+/// The registered entity menu, opened by right-clicking the item drawn last, e.g. an entity's row.
+/// Run the returned action once the panel's own resources are released.
+pub fn entity_context_menu(ui: &Ui, world: &World, entity: Entity) -> Option<ContextAction> {
+	let action = context_menu(ui, &format!("##entity_menu{}", entity.id()), || {
+		world.res.try_fetch::<ContextMenus>().map_or_else(Vec::new, |menus| {
+			menus
+				.entities
+				.iter()
+				.filter(|x| (x.predicate)(world, entity))
+				.map(|x| (x.label.clone(), x.action.clone()))
+				.collect()
+		})
+	})?;
+	Some(Box::new(move |world: &World| action(world, entity)))
+}
+
+/// The registered asset menu for `path`, like `entity_context_menu`.
+pub fn asset_context_menu(ui: &Ui, world: &World, path: &Path) -> Option<ContextAction> {
+	let extension = path.extension().and_then(|x| x.to_str()).unwrap_or("").to_lowercase();
+	let action = context_menu(ui, &format!("##asset_menu{}", path.display()), || {
+		world.res.try_fetch::<ContextMenus>().map_or_else(Vec::new, |menus| {
+			menus
+				.assets
+				.iter()
+				.filter(|x| x.extensions.is_empty() || x.extensions.contains(&extension))
+				.map(|x| (x.label.clone(), x.action.clone()))
+				.collect()
+		})
+	})?;
+	let path = path.to_owned();
+	Some(Box::new(move |world: &World| action(world, &path)))
+}
@@ -0,0 +1,234 @@
+use std::{
+	ffi::CString,
+	fs,
+	panic::{self, AssertUnwindSafe},
+	path::PathBuf,
+	sync::Mutex,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use amethyst::{ecs::prelude::World, renderer::Event, GameData, State, StateData, StateEvent, Trans};
+use backtrace::Backtrace;
+use imgui::{ImString, Ui};
+
+#[derive(Clone, Debug)]
+pub struct PanicReport {
+	pub message: String,
+	pub location: Option<String>,
+	pub thread: Option<String>,
+	pub backtrace: String,
+}
+
+impl PanicReport {
+	pub fn to_text(&self) -> String {
+		format!(
+			"thread '{}' panicked at '{}', {}\n\n{}",
+			self.thread.as_ref().map_or("<unnamed>", String::as_str),
+			self.message,
+			self.location.as_ref().map_or("<unknown location>", String::as_str),
+			self.backtrace
+		)
+	}
+}
+
+/// Events a `CrashGuard` can forward to imgui once the wrapped state is gone.
+pub trait WindowEventSource {
+	fn window_event(&self) -> Option<&Event>;
+}
+
+impl WindowEventSource for Event {
+	fn window_event(&self) -> Option<&Event> { Some(self) }
+}
+
+impl WindowEventSource for StateEvent {
+	fn window_event(&self) -> Option<&Event> {
+		match self {
+			StateEvent::Window(x) => Some(x),
+			_ => None,
+		}
+	}
+}
+
+/// Game data a `CrashGuard` keeps dispatching after a crash, so the `DrawUi` pass still renders the report.
+pub trait CrashData {
+	/// Runs the dispatcher holding `DrawUi`, as the wrapped states do in their `update`.
+	fn dispatch(&mut self, world: &World);
+}
+
+impl<'a, 'b> CrashData for GameData<'a, 'b> {
+	fn dispatch(&mut self, world: &World) { self.update(world); }
+}
+
+/// Global rather than thread-local, as systems panic on the dispatcher's worker threads.
+static LAST_PANIC: Mutex<Option<PanicReport>> = Mutex::new(None);
+
+fn last_panic() -> std::sync::MutexGuard<'static, Option<PanicReport>> {
+	// A panic while the hook held the lock doesn't leave the report half-written
+	LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Records panics so `CrashGuard` can show them; the previous hook still runs afterwards.
+pub fn install_panic_hook() {
+	let previous = panic::take_hook();
+	panic::set_hook(Box::new(move |info| {
+		let message = match info.payload().downcast_ref::<&str>() {
+			Some(x) => x.to_string(),
+			None => match info.payload().downcast_ref::<String>() {
+				Some(x) => x.clone(),
+				None => "Box<Any>".into(),
+			},
+		};
+		let report = PanicReport {
+			message,
+			location: info.location().map(|x| format!("{}:{}:{}", x.file(), x.line(), x.column())),
+			thread: std::thread::current().name().map(String::from),
+			backtrace: format!("{:?}", Backtrace::new()),
+		};
+		*last_panic() = Some(report);
+		previous(info);
+	}));
+}
+
+fn take_report() -> PanicReport {
+	last_panic().take().unwrap_or_else(|| PanicReport {
+		message: "panicked (install_panic_hook wasn't called, no details available)".into(),
+		location: None,
+		thread: None,
+		backtrace: String::new(),
+	})
+}
+
+/// Wraps the root state: panics from it (and everything it pushes) are caught and shown in a
+/// final imgui modal instead of tearing the application down.
+///
+/// After a crash the guard dispatches the game data itself each frame to render the modal, so `T` has to be
+/// `CrashData`, like amethyst's `GameData`. Every system keeps running; frames where one panics again are
+/// dropped.
+pub struct CrashGuard<T, E> {
+	inner: Box<dyn State<T, E>>,
+	report: Option<PanicReport>,
+	saved: Option<Result<PathBuf, String>>,
+}
+
+impl<T: CrashData, E: Send + Sync + 'static> CrashGuard<T, E> {
+	pub fn new<S: State<T, E> + 'static>(state: S) -> Self {
+		Self {
+			inner: Box::new(state),
+			report: None,
+			saved: None,
+		}
+	}
+
+	fn guard<R, F: FnOnce(&mut dyn State<T, E>) -> R>(&mut self, f: F) -> Option<R> {
+		if self.report.is_some() {
+			return None;
+		}
+		let inner = &mut *self.inner;
+		match panic::catch_unwind(AssertUnwindSafe(|| f(inner))) {
+			Ok(x) => Some(x),
+			Err(_) => {
+				// End the frame the panicking code may have left open; a no-op if it was rendered
+				unsafe { imgui::sys::igEndFrame() };
+				self.report = Some(take_report());
+				None
+			},
+		}
+	}
+
+	fn report_window(&mut self, ui: &Ui) -> bool {
+		let report = match self.report {
+			Some(ref x) => x,
+			_ => return false,
+		};
+		let mut quit = false;
+		let mut save = false;
+		ui.open_popup(im_str!("Crash"));
+		ui.popup_modal(im_str!("Crash")).always_auto_resize(true).build(|| {
+			ui.text_colored([1., 0.35, 0.35, 1.], "The game panicked and cannot continue.");
+			ui.text_wrapped(&ImString::new(report.message.as_str()));
+			if let Some(ref location) = report.location {
+				ui.text_disabled(location.as_str());
+			}
+			ui.separator();
+			ui.child_frame(im_str!("##backtrace"), (700., 300.)).show_borders(true).build(|| {
+				ui.text(report.backtrace.as_str());
+			});
+			if ui.button(im_str!("Copy to clipboard"), (0., 0.)) {
+				if let Ok(text) = CString::new(report.to_text()) {
+					unsafe { imgui::sys::igSetClipboardText(text.as_ptr()) };
+				}
+			}
+			ui.same_line(0.);
+			save = ui.button(im_str!("Save report"), (0., 0.));
+			ui.same_line(0.);
+			quit = ui.button(im_str!("Quit"), (0., 0.));
+			match self.saved {
+				Some(Ok(ref path)) => ui.text(format!("Saved to {}", path.display())),
+				Some(Err(ref e)) => ui.text_colored([1., 0.35, 0.35, 1.], format!("Failed to save: {}", e)),
+				None => {},
+			}
+		});
+
+		if save {
+			let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_secs()).unwrap_or(0);
+			let path = PathBuf::from(format!("crash-{}.txt", seconds));
+			self.saved = Some(fs::write(&path, report.to_text()).map(|_| path).map_err(|e| e.to_string()));
+		}
+		quit
+	}
+
+	fn crashed(&mut self, data: StateData<'_, T>) -> Trans<T, E> {
+		let quit = match crate::open_frame(data.world) {
+			Some(ui) => {
+				let quit = self.report_window(ui);
+				crate::close_frame(ui);
+				quit
+			},
+			None => false,
+		};
+		let (world, game_data) = (data.world, data.data);
+		if panic::catch_unwind(AssertUnwindSafe(|| game_data.dispatch(world))).is_err() {
+			// `DrawUi` may not have run, so end the frame here for the next `open_frame`
+			unsafe { imgui::sys::igEndFrame() };
+		}
+		if quit { Trans::Quit } else { Trans::None }
+	}
+}
+
+impl<T: CrashData, E: WindowEventSource + Send + Sync + 'static> State<T, E> for CrashGuard<T, E> {
+	fn on_start(&mut self, data: StateData<'_, T>) { self.guard(|x| x.on_start(data)); }
+
+	fn on_stop(&mut self, data: StateData<'_, T>) { self.guard(|x| x.on_stop(data)); }
+
+	fn on_pause(&mut self, data: StateData<'_, T>) { self.guard(|x| x.on_pause(data)); }
+
+	fn on_resume(&mut self, data: StateData<'_, T>) { self.guard(|x| x.on_resume(data)); }
+
+	fn handle_event(&mut self, data: StateData<'_, T>, event: E) -> Trans<T, E> {
+		if self.report.is_some() {
+			if let Some(event) = event.window_event() {
+				crate::handle_imgui_events(data.world, event);
+			}
+		}
+		self.guard(|x| x.handle_event(data, event)).unwrap_or(Trans::None)
+	}
+
+	fn fixed_update(&mut self, data: StateData<'_, T>) -> Trans<T, E> {
+		self.guard(|x| x.fixed_update(data)).unwrap_or(Trans::None)
+	}
+
+	fn update(&mut self, data: StateData<'_, T>) -> Trans<T, E> {
+		if self.report.is_some() {
+			return self.crashed(data);
+		}
+		let trans = self.guard(|x| x.update(StateData::new(data.world, data.data)));
+		match trans {
+			Some(x) => x,
+			None => self.crashed(data),
+		}
+	}
+
+	fn shadow_fixed_update(&mut self, data: StateData<'_, T>) { self.guard(|x| x.shadow_fixed_update(data)); }
+
+	fn shadow_update(&mut self, data: StateData<'_, T>) { self.guard(|x| x.shadow_update(data)); }
+}
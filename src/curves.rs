@@ -0,0 +1,270 @@
+use std::cell::RefCell;
+
+use imgui::{sys, ImMouseButton, ImStr, ImVec2, Ui};
+
+/// A 1D spline through `keys`, e.g. a particle's size over its lifetime or an easing curve.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Curve {
+	/// `(t, value)` pairs sorted by `t`, which runs from 0 to 1.
+	pub keys: Vec<(f32, f32)>,
+}
+
+impl Default for Curve {
+	fn default() -> Self { Self { keys: vec![(0., 0.), (1., 1.)] } }
+}
+
+impl Curve {
+	/// Catmull-Rom interpolation between the keys, flat before the first and after the last.
+	pub fn evaluate(&self, t: f32) -> f32 {
+		let keys = &self.keys;
+		match keys.len() {
+			0 => return 0.,
+			1 => return keys[0].1,
+			_ => {},
+		}
+		if t <= keys[0].0 {
+			return keys[0].1;
+		}
+		let i = match keys.iter().position(|x| x.0 > t) {
+			Some(x) => x - 1,
+			None => return keys[keys.len() - 1].1,
+		};
+		let (p1, p2) = (keys[i], keys[i + 1]);
+		let p0 = if i > 0 { keys[i - 1] } else { p1 };
+		let p3 = if i + 2 < keys.len() { keys[i + 2] } else { p2 };
+		let span = p2.0 - p1.0;
+		if span <= 0. {
+			return p2.1;
+		}
+		let s = (t - p1.0) / span;
+		// Tangents scaled to the segment so uneven key spacing doesn't overshoot
+		let m1 = if p2.0 > p0.0 { (p2.1 - p0.1) / (p2.0 - p0.0) * span } else { 0. };
+		let m2 = if p3.0 > p1.0 { (p3.1 - p1.1) / (p3.0 - p1.0) * span } else { 0. };
+		let (s2, s3) = (s * s, s * s * s);
+		(2. * s3 - 3. * s2 + 1.) * p1.1 + (s3 - 2. * s2 + s) * m1 + (-2. * s3 + 3. * s2) * p2.1 + (s3 - s2) * m2
+	}
+
+	fn sort(&mut self) { self.keys.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)); }
+}
+
+/// A color ramp, e.g. a particle's color over its lifetime or a day/night sky.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Gradient {
+	/// `(t, rgba)` pairs sorted by `t`, which runs from 0 to 1.
+	pub keys: Vec<(f32, [f32; 4])>,
+}
+
+impl Default for Gradient {
+	fn default() -> Self { Self { keys: vec![(0., [0., 0., 0., 1.]), (1., [1., 1., 1., 1.])] } }
+}
+
+impl Gradient {
+	/// Linear interpolation between the keys.
+	pub fn evaluate(&self, t: f32) -> [f32; 4] {
+		let keys = &self.keys;
+		if keys.is_empty() {
+			return [0., 0., 0., 0.];
+		}
+		if t <= keys[0].0 {
+			return keys[0].1;
+		}
+		let i = match keys.iter().position(|x| x.0 > t) {
+			Some(x) => x - 1,
+			None => return keys[keys.len() - 1].1,
+		};
+		let ((t1, a), (t2, b)) = (keys[i], keys[i + 1]);
+		let s = if t2 > t1 { (t - t1) / (t2 - t1) } else { 1. };
+		let mut out = [0.; 4];
+		for c in 0..4 {
+			out[c] = a[c] + (b[c] - a[c]) * s;
+		}
+		out
+	}
+
+	fn sort(&mut self) { self.keys.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)); }
+}
+
+thread_local! {
+	/// The widget and key being dragged, by label.
+	static DRAGGING: RefCell<Option<(String, usize)>> = RefCell::new(None);
+	/// The gradient key whose color is being edited, by label.
+	static SELECTED: RefCell<Option<(String, usize)>> = RefCell::new(None);
+}
+
+const KEY_RADIUS: f32 = 5.;
+
+fn pack([r, g, b, a]: [f32; 4]) -> u32 {
+	let channel = |x: f32| (x.max(0.).min(1.) * 255. + 0.5) as u32;
+	channel(r) | channel(g) << 8 | channel(b) << 16 | channel(a) << 24
+}
+
+/// Index of the key closest to `mouse` if it's within grabbing distance.
+fn key_under(points: &[ImVec2], mouse: (f32, f32)) -> Option<usize> {
+	points
+		.iter()
+		.map(|p| (p.x - mouse.0).powi(2) + (p.y - mouse.1).powi(2))
+		.enumerate()
+		.filter(|x| x.1 <= (KEY_RADIUS * 2.).powi(2))
+		.min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+		.map(|x| x.0)
+}
+
+/// Tracks dragging of key `index` for widget `label`; returns the dragged key while the mouse is down.
+fn drag(ui: &Ui, label: &ImStr, hovered_key: Option<usize>) -> Option<usize> {
+	let label = label.to_str().to_owned();
+	DRAGGING.with(|dragging| {
+		let mut dragging = dragging.borrow_mut();
+		if ui.is_item_hovered() && ui.imgui().is_mouse_clicked(ImMouseButton::Left) {
+			*dragging = hovered_key.map(|x| (label.clone(), x));
+		}
+		if !ui.imgui().is_mouse_down(ImMouseButton::Left) {
+			*dragging = None;
+		}
+		match *dragging {
+			Some((ref x, index)) if *x == label => Some(index),
+			_ => None,
+		}
+	})
+}
+
+/// Whether a key of the widget called `label` is being dragged.
+pub(crate) fn is_dragging(label: &ImStr) -> bool {
+	DRAGGING.with(|dragging| dragging.borrow().as_ref().map_or(false, |x| x.0 == label.to_str()))
+}
+
+/// Edits `curve` with values between `range`: drag keys to move them, double-click to add one and right-click
+/// to remove it.
+///
+/// Returns whether the curve changed.
+pub fn curve_editor(ui: &Ui, label: &ImStr, curve: &mut Curve, range: (f32, f32), size: (f32, f32)) -> bool {
+	let (x, y) = ui.get_cursor_screen_pos();
+	ui.invisible_button(label, size);
+	let span = if range.1 != range.0 { range.1 - range.0 } else { 1. };
+	let to_screen = |(t, v): (f32, f32)| ImVec2::new(x + t * size.0, y + (1. - (v - range.0) / span) * size.1);
+	let from_screen = |(px, py): (f32, f32)| {
+		let t = ((px - x) / size.0).max(0.).min(1.);
+		let v = range.0 + (1. - (py - y) / size.1) * span;
+		(t, v.max(range.0.min(range.1)).min(range.0.max(range.1)))
+	};
+
+	let mouse = ui.imgui().mouse_pos();
+	let points = curve.keys.iter().map(|&x| to_screen(x)).collect::<Vec<_>>();
+	let hovered_key = if ui.is_item_hovered() { key_under(&points, mouse) } else { None };
+	let mut changed = false;
+	if let Some(index) = drag(ui, label, hovered_key) {
+		if index < curve.keys.len() {
+			curve.keys[index] = from_screen(mouse);
+			// Keep the dragged key from passing its neighbors, so its index stays valid
+			let min = if index > 0 { curve.keys[index - 1].0 } else { 0. };
+			let max = if index + 1 < curve.keys.len() { curve.keys[index + 1].0 } else { 1. };
+			curve.keys[index].0 = curve.keys[index].0.max(min).min(max);
+			changed = true;
+		}
+	} else if ui.is_item_hovered() {
+		if ui.imgui().is_mouse_double_clicked(ImMouseButton::Left) && hovered_key.is_none() {
+			curve.keys.push(from_screen(mouse));
+			curve.sort();
+			changed = true;
+		} else if let (true, Some(index)) = (ui.imgui().is_mouse_clicked(ImMouseButton::Right), hovered_key) {
+			if curve.keys.len() > 1 {
+				curve.keys.remove(index);
+				changed = true;
+			}
+		}
+	}
+
+	unsafe {
+		let list = sys::igGetWindowDrawList();
+		let (min, max) = (ImVec2::new(x, y), ImVec2::new(x + size.0, y + size.1));
+		let background = 0xff_30_30_30;
+		sys::ImDrawList_AddRectFilledMultiColor(list, min, max, background, background, background, background);
+		let steps = size.0.max(2.) as usize / 2;
+		let mut previous = None;
+		for i in 0..=steps {
+			let t = i as f32 / steps as f32;
+			let point = to_screen((t, curve.evaluate(t)));
+			if let Some(previous) = previous {
+				sys::ImDrawList_AddLine(list, previous, point, 0xff_ff_c0_40, 1.5);
+			}
+			previous = Some(point);
+		}
+		for (i, &key) in curve.keys.iter().enumerate() {
+			let color = if hovered_key == Some(i) { 0xff_ff_ff_ff } else { 0xff_a0_a0_a0 };
+			sys::ImDrawList_AddCircleFilled(list, to_screen(key), KEY_RADIUS, color, 8);
+		}
+	}
+	changed
+}
+
+/// Edits `gradient`: drag the markers under the ramp, double-click to add one, click one to edit its color
+/// and right-click to remove it.
+///
+/// Returns whether the gradient changed.
+pub fn gradient_editor(ui: &Ui, label: &ImStr, gradient: &mut Gradient, width: f32) -> bool {
+	let (x, y) = ui.get_cursor_screen_pos();
+	let (bar, markers) = (20., 12.);
+	ui.invisible_button(label, (width, bar + markers));
+	let t_at = |px: f32| ((px - x) / width).max(0.).min(1.);
+
+	let mouse = ui.imgui().mouse_pos();
+	let points = gradient.keys.iter().map(|k| ImVec2::new(x + k.0 * width, y + bar + markers / 2.)).collect::<Vec<_>>();
+	let hovered_key = if ui.is_item_hovered() { key_under(&points, mouse) } else { None };
+	let name = label.to_str().to_owned();
+	let mut changed = false;
+	if let Some(index) = drag(ui, label, hovered_key) {
+		if index < gradient.keys.len() {
+			let min = if index > 0 { gradient.keys[index - 1].0 } else { 0. };
+			let max = if index + 1 < gradient.keys.len() { gradient.keys[index + 1].0 } else { 1. };
+			gradient.keys[index].0 = t_at(mouse.0).max(min).min(max);
+			SELECTED.with(|x| *x.borrow_mut() = Some((name.clone(), index)));
+			changed = true;
+		}
+	} else if ui.is_item_hovered() {
+		if ui.imgui().is_mouse_double_clicked(ImMouseButton::Left) && hovered_key.is_none() {
+			let t = t_at(mouse.0);
+			gradient.keys.push((t, gradient.evaluate(t)));
+			gradient.sort();
+			changed = true;
+		} else if let (true, Some(index)) = (ui.imgui().is_mouse_clicked(ImMouseButton::Right), hovered_key) {
+			if gradient.keys.len() > 1 {
+				gradient.keys.remove(index);
+				SELECTED.with(|x| *x.borrow_mut() = None);
+				changed = true;
+			}
+		}
+	}
+
+	unsafe {
+		let list = sys::igGetWindowDrawList();
+		let segments = width.max(2.) as usize / 4;
+		for i in 0..segments {
+			let (t0, t1) = (i as f32 / segments as f32, (i + 1) as f32 / segments as f32);
+			let (left, right) = (pack(gradient.evaluate(t0)), pack(gradient.evaluate(t1)));
+			sys::ImDrawList_AddRectFilledMultiColor(
+				list,
+				ImVec2::new(x + t0 * width, y),
+				ImVec2::new(x + t1 * width, y + bar),
+				left,
+				right,
+				right,
+				left,
+			);
+		}
+		for (i, point) in points.iter().enumerate() {
+			let color = if hovered_key == Some(i) { 0xff_ff_ff_ff } else { 0xff_a0_a0_a0 };
+			sys::ImDrawList_AddCircleFilled(list, *point, KEY_RADIUS, color, 8);
+			sys::ImDrawList_AddCircleFilled(list, *point, KEY_RADIUS - 2., pack(gradient.keys[i].1), 8);
+		}
+	}
+
+	let selected = SELECTED.with(|x| match *x.borrow() {
+		Some((ref label, index)) if *label == name => Some(index),
+		_ => None,
+	});
+	if let Some(index) = selected.filter(|x| *x < gradient.keys.len()) {
+		if ui.color_edit(&im_str!("Key {}##{}", index, name), &mut gradient.keys[index].1).build() {
+			changed = true;
+		}
+	}
+	changed
+}
@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use amethyst::{ecs::prelude::*, shrev::EventChannel};
+use imgui::{ImGuiCond, ImString, Ui};
+use ron;
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum MenuItem {
+	Text(String),
+	Separator,
+	SameLine,
+	Slider { label: String, value: String, min: f32, max: f32 },
+	SliderInt { label: String, value: String, min: i32, max: i32 },
+	Drag { label: String, value: String, speed: f32 },
+	Checkbox { label: String, value: String },
+	Input { label: String, value: String },
+	Button { label: String, command: String },
+	Group { label: String, items: Vec<MenuItem> },
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MenuWindow {
+	pub title: String,
+	#[serde(default)]
+	pub open: bool,
+	pub items: Vec<MenuItem>,
+}
+
+/// Debug menus described in RON, e.g.
+///
+/// ```ron
+/// (windows: [(title: "Player", open: true, items: [
+///     Slider(label: "Speed", value: "player.speed", min: 0.0, max: 10.0),
+///     Button(label: "Respawn", command: "respawn"),
+/// ])])
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct DebugMenus {
+	pub windows: Vec<MenuWindow>,
+}
+
+impl DebugMenus {
+	pub fn from_ron(source: &str) -> Result<Self, ron::de::Error> { ron::de::from_str(source) }
+
+	pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+		let source = fs::read_to_string(path.as_ref()).map_err(|e| format!("{}: {}", path.as_ref().display(), e))?;
+		Self::from_ron(&source).map_err(|e| format!("{}: {}", path.as_ref().display(), e))
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MenuValue {
+	Float(f32),
+	Int(i32),
+	Bool(bool),
+	Text(String),
+}
+
+/// Named values the debug menus read and write; games read them back by name.
+#[derive(Clone, Default)]
+pub struct MenuValues {
+	values: BTreeMap<String, MenuValue>,
+}
+
+impl MenuValues {
+	pub fn set<S: Into<String>>(&mut self, name: S, value: MenuValue) { self.values.insert(name.into(), value); }
+
+	pub fn get(&self, name: &str) -> Option<&MenuValue> { self.values.get(name) }
+
+	pub fn float(&self, name: &str) -> Option<f32> {
+		match self.values.get(name) {
+			Some(MenuValue::Float(x)) => Some(*x),
+			Some(MenuValue::Int(x)) => Some(*x as f32),
+			_ => None,
+		}
+	}
+
+	pub fn int(&self, name: &str) -> Option<i32> {
+		match self.values.get(name) {
+			Some(MenuValue::Int(x)) => Some(*x),
+			_ => None,
+		}
+	}
+
+	pub fn bool(&self, name: &str) -> Option<bool> {
+		match self.values.get(name) {
+			Some(MenuValue::Bool(x)) => Some(*x),
+			_ => None,
+		}
+	}
+
+	pub fn text(&self, name: &str) -> Option<&str> {
+		match self.values.get(name) {
+			Some(MenuValue::Text(x)) => Some(x),
+			_ => None,
+		}
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&String, &MenuValue)> { self.values.iter() }
+}
+
+/// Emitted when a `Button` menu item is pressed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuCommand(pub String);
+
+fn draw_items(ui: &Ui, items: &[MenuItem], values: &mut MenuValues, commands: &mut Vec<MenuCommand>) {
+	for item in items {
+		match item {
+			MenuItem::Text(text) => ui.text(text.as_str()),
+			MenuItem::Separator => ui.separator(),
+			MenuItem::SameLine => ui.same_line(0.),
+			MenuItem::Slider { label, value, min, max } => {
+				let mut x = values.float(value).unwrap_or(*min);
+				if ui.slider_float(&ImString::new(label.as_str()), &mut x, *min, *max).build() {
+					values.set(value.as_str(), MenuValue::Float(x));
+				}
+			},
+			MenuItem::SliderInt { label, value, min, max } => {
+				let mut x = values.int(value).unwrap_or(*min);
+				if ui.slider_int(&ImString::new(label.as_str()), &mut x, *min, *max).build() {
+					values.set(value.as_str(), MenuValue::Int(x));
+				}
+			},
+			MenuItem::Drag { label, value, speed } => {
+				let mut x = values.float(value).unwrap_or(0.);
+				if ui.drag_float(&ImString::new(label.as_str()), &mut x).speed(*speed).build() {
+					values.set(value.as_str(), MenuValue::Float(x));
+				}
+			},
+			MenuItem::Checkbox { label, value } => {
+				let mut x = values.bool(value).unwrap_or(false);
+				if ui.checkbox(&ImString::new(label.as_str()), &mut x) {
+					values.set(value.as_str(), MenuValue::Bool(x));
+				}
+			},
+			MenuItem::Input { label, value } => {
+				let mut buffer = ImString::with_capacity(256);
+				buffer.push_str(values.text(value).unwrap_or(""));
+				if ui.input_text(&ImString::new(label.as_str()), &mut buffer).build() {
+					values.set(value.as_str(), MenuValue::Text(buffer.to_str().to_owned()));
+				}
+			},
+			MenuItem::Button { label, command } => {
+				if ui.button(&ImString::new(label.as_str()), (0., 0.)) {
+					commands.push(MenuCommand(command.clone()));
+				}
+			},
+			MenuItem::Group { label, items } => {
+				ui.tree_node(&ImString::new(label.as_str())).build(|| draw_items(ui, items, values, commands));
+			},
+		}
+	}
+}
+
+/// Draws every open `DebugMenus` window, writing into `MenuValues` and `EventChannel<MenuCommand>`.
+pub fn debug_menus(ui: &Ui, world: &World) {
+	let mut menus = match world.res.try_fetch_mut::<DebugMenus>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let mut values = match world.res.try_fetch_mut::<MenuValues>() {
+		Some(x) => x,
+		_ => return,
+	};
+
+	let mut commands = Vec::new();
+	for window in menus.windows.iter_mut().filter(|x| x.open) {
+		let mut open = true;
+		let items = &window.items;
+		ui.window(&ImString::new(window.title.as_str()))
+			.size((300., 0.), ImGuiCond::FirstUseEver)
+			.always_auto_resize(true)
+			.opened(&mut open)
+			.build(|| draw_items(ui, items, &mut values, &mut commands));
+		window.open = open;
+	}
+
+	if !commands.is_empty() {
+		if let Some(mut channel) = world.res.try_fetch_mut::<EventChannel<MenuCommand>>() {
+			channel.iter_write(commands);
+		}
+	}
+}
@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImString, Ui};
+
+type Callback = Box<dyn FnOnce(&World) + Send + Sync>;
+
+enum DialogKind {
+	Confirm {
+		message: String,
+		on_yes: Option<Callback>,
+		on_no: Option<Callback>,
+	},
+	Progress {
+		fraction: f32,
+		status: Option<String>,
+	},
+}
+
+struct Dialog {
+	title: ImString,
+	kind: DialogKind,
+	shown: bool,
+	finished: bool,
+}
+
+/// Queue of modal dialogs, shown one at a time by `dialogs`.
+///
+/// ```rust,ignore
+/// world.write_resource::<Dialogs>().confirm("Delete entity?", move |world| world.delete_entity(entity).unwrap());
+/// ```
+#[derive(Default)]
+pub struct Dialogs {
+	queue: VecDeque<Dialog>,
+}
+
+impl Dialogs {
+	pub fn confirm<S, F>(&mut self, message: S, on_yes: F)
+	where
+		S: Into<String>,
+		F: FnOnce(&World) + Send + Sync + 'static,
+	{
+		self.confirm_with("Confirm", message, Some(on_yes), None::<fn(&World)>);
+	}
+
+	pub fn confirm_with<S, M, Y, N>(&mut self, title: S, message: M, on_yes: Option<Y>, on_no: Option<N>)
+	where
+		S: Into<String>,
+		M: Into<String>,
+		Y: FnOnce(&World) + Send + Sync + 'static,
+		N: FnOnce(&World) + Send + Sync + 'static,
+	{
+		self.queue.push_back(Dialog {
+			title: ImString::new(title.into()),
+			kind: DialogKind::Confirm {
+				message: message.into(),
+				on_yes: on_yes.map(|x| Box::new(x) as Callback),
+				on_no: on_no.map(|x| Box::new(x) as Callback),
+			},
+			shown: false,
+			finished: false,
+		});
+	}
+
+	/// Opens a progress dialog with the given title, or updates it if it is already queued.
+	pub fn progress<S: Into<String>>(&mut self, title: S, fraction: f32) {
+		let title = ImString::new(title.into());
+		let fraction = fraction.max(0.).min(1.);
+		for dialog in &mut self.queue {
+			if dialog.title == title {
+				if let DialogKind::Progress { fraction: ref mut x, .. } = dialog.kind {
+					*x = fraction;
+					return;
+				}
+			}
+		}
+		self.queue.push_back(Dialog {
+			title,
+			kind: DialogKind::Progress { fraction, status: None },
+			shown: false,
+			finished: false,
+		});
+	}
+
+	pub fn progress_status<S: Into<String>, T: Into<String>>(&mut self, title: S, status: T) {
+		let title = ImString::new(title.into());
+		for dialog in &mut self.queue {
+			if dialog.title == title {
+				if let DialogKind::Progress { status: ref mut x, .. } = dialog.kind {
+					*x = Some(status.into());
+				}
+			}
+		}
+	}
+
+	pub fn end_progress<S: Into<String>>(&mut self, title: S) {
+		let title = ImString::new(title.into());
+		for dialog in &mut self.queue {
+			if dialog.title == title {
+				if let DialogKind::Progress { .. } = dialog.kind {
+					dialog.finished = true;
+				}
+			}
+		}
+	}
+
+	pub fn is_open(&self) -> bool { !self.queue.is_empty() }
+}
+
+/// Draws the front dialog and runs its callback once it's dismissed.
+pub fn dialogs(ui: &Ui, world: &World) {
+	let callback = {
+		let mut dialogs = match world.res.try_fetch_mut::<Dialogs>() {
+			Some(x) => x,
+			_ => return,
+		};
+		let mut callback = None;
+		let mut dismissed = false;
+		if let Some(dialog) = dialogs.queue.front_mut() {
+			if !dialog.shown {
+				ui.open_popup(&dialog.title);
+				dialog.shown = true;
+			}
+
+			let finished = dialog.finished;
+			let kind = &mut dialog.kind;
+			ui.popup_modal(&dialog.title).always_auto_resize(true).build(|| {
+				match kind {
+					DialogKind::Confirm { message, on_yes, on_no } => {
+						ui.text(message.as_str());
+						ui.separator();
+						if ui.button(im_str!("Yes"), (120., 0.)) {
+							callback = on_yes.take();
+							dismissed = true;
+						}
+						ui.same_line(0.);
+						if ui.button(im_str!("No"), (120., 0.)) {
+							callback = on_no.take();
+							dismissed = true;
+						}
+					},
+					DialogKind::Progress { fraction, status } => {
+						if let Some(status) = status {
+							ui.text(status.as_str());
+						}
+						ui.progress_bar(*fraction)
+							.size((300., 0.))
+							.overlay_text(&im_str!("{:.0}%", *fraction * 100.))
+							.build();
+						if finished {
+							dismissed = true;
+						}
+					},
+				}
+				if dismissed {
+					ui.close_current_popup();
+				}
+			});
+		}
+		if dismissed {
+			dialogs.queue.pop_front();
+		}
+		callback
+	};
+
+	if let Some(callback) = callback {
+		callback(world);
+	}
+}
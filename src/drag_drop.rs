@@ -0,0 +1,55 @@
+use std::{
+	any::{Any, TypeId},
+	cell::RefCell,
+	collections::hash_map::DefaultHasher,
+	ffi::CString,
+	hash::{Hash, Hasher},
+};
+
+use imgui::{sys, ImGuiCond, Ui};
+
+thread_local! {
+	/// The value being dragged; imgui only carries its type tag, so any `Clone` type can be dropped.
+	static PAYLOAD: RefCell<Option<Box<dyn Any>>> = RefCell::new(None);
+}
+
+fn payload_type<T: 'static>() -> CString {
+	let mut hasher = DefaultHasher::new();
+	TypeId::of::<T>().hash(&mut hasher);
+	CString::new(format!("rs{:016x}", hasher.finish())).unwrap()
+}
+
+/// Makes the last item draggable, carrying a copy of `value` and showing `preview` next to the cursor.
+///
+/// Returns whether it's being dragged.
+pub fn drag_source<T: Clone + 'static>(ui: &Ui, value: &T, preview: &str) -> bool {
+	unsafe {
+		if !sys::igBeginDragDropSource(sys::ImGuiDragDropFlags::empty()) {
+			return false;
+		}
+		PAYLOAD.with(|x| *x.borrow_mut() = Some(Box::new(value.clone())));
+		let kind = payload_type::<T>();
+		sys::igSetDragDropPayload(kind.as_ptr(), std::ptr::null(), 0, ImGuiCond::Always);
+		ui.text(preview);
+		sys::igEndDragDropSource();
+	}
+	true
+}
+
+/// Makes the last item accept values dragged by `drag_source::<T>`, returning one when it's dropped.
+pub fn drop_target<T: Clone + 'static>(_ui: &Ui) -> Option<T> {
+	unsafe {
+		if !sys::igBeginDragDropTarget() {
+			return None;
+		}
+		let kind = payload_type::<T>();
+		let payload = sys::igAcceptDragDropPayload(kind.as_ptr(), sys::ImGuiDragDropFlags::empty());
+		let value = if payload.is_null() {
+			None
+		} else {
+			PAYLOAD.with(|x| x.borrow_mut().take()).and_then(|x| x.downcast::<T>().ok()).map(|x| *x)
+		};
+		sys::igEndDragDropTarget();
+		value
+	}
+}
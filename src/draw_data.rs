@@ -0,0 +1,102 @@
+use imgui::sys;
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CapturedVertex {
+	pub pos: [f32; 2],
+	pub uv: [f32; 2],
+	/// Packed RGBA, as imgui emits it.
+	pub col: u32,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CapturedCommand {
+	pub elem_count: u32,
+	/// `[x1, y1, x2, y2]` in framebuffer pixels.
+	pub clip_rect: [f32; 4],
+	pub texture_id: usize,
+	/// Whether imgui asked for a user callback instead of a draw.
+	pub callback: bool,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CapturedDrawList {
+	pub vertices: Vec<CapturedVertex>,
+	pub indices: Vec<u16>,
+	pub commands: Vec<CapturedCommand>,
+}
+
+/// Owned copy of the draw data imgui generated for a frame.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CapturedDrawData {
+	pub display_size: [f32; 2],
+	pub lists: Vec<CapturedDrawList>,
+}
+
+impl CapturedDrawData {
+	pub fn total_vertices(&self) -> usize { self.lists.iter().map(|x| x.vertices.len()).sum() }
+
+	pub fn total_indices(&self) -> usize { self.lists.iter().map(|x| x.indices.len()).sum() }
+
+	pub fn total_commands(&self) -> usize { self.lists.iter().map(|x| x.commands.len()).sum() }
+}
+
+/// Resource the `DrawUi` pass fills with the rendered draw data while `enabled` is set.
+#[derive(Default)]
+pub struct DrawDataCapture {
+	pub enabled: bool,
+	pub data: Option<CapturedDrawData>,
+}
+
+unsafe fn slice<'a, T>(vector: &'a sys::ImVector<T>) -> &'a [T] {
+	if vector.data.is_null() || vector.size <= 0 {
+		&[]
+	} else {
+		std::slice::from_raw_parts(vector.data, vector.size as usize)
+	}
+}
+
+/// Copies the draw data of the last rendered frame.
+///
+/// Unsafe because it reads imgui's global draw data, which is only valid between
+/// rendering a frame and starting the next one.
+pub unsafe fn capture() -> Option<CapturedDrawData> {
+	let draw_data = sys::igGetDrawData();
+	if draw_data.is_null() || !(*draw_data).valid {
+		return None;
+	}
+	let draw_data = &*draw_data;
+	let lists = if draw_data.cmd_lists.is_null() {
+		&[][..]
+	} else {
+		std::slice::from_raw_parts(draw_data.cmd_lists, draw_data.cmd_lists_count.max(0) as usize)
+	};
+
+	let mut captured = CapturedDrawData {
+		display_size: [draw_data.display_size.x, draw_data.display_size.y],
+		lists: Vec::with_capacity(lists.len()),
+	};
+	for &list in lists {
+		let list = &*list;
+		captured.lists.push(CapturedDrawList {
+			vertices: slice(&list.vtx_buffer)
+				.iter()
+				.map(|x| CapturedVertex {
+					pos: [x.pos.x, x.pos.y],
+					uv: [x.uv.x, x.uv.y],
+					col: x.col,
+				})
+				.collect(),
+			indices: slice(&list.idx_buffer).to_vec(),
+			commands: slice(&list.cmd_buffer)
+				.iter()
+				.map(|x| CapturedCommand {
+					elem_count: x.elem_count,
+					clip_rect: [x.clip_rect.x, x.clip_rect.y, x.clip_rect.z, x.clip_rect.w],
+					texture_id: x.texture_id as usize,
+					callback: x.user_callback.is_some(),
+				})
+				.collect(),
+		});
+	}
+	Some(captured)
+}
@@ -0,0 +1,60 @@
+use std::{error::Error, fmt};
+
+use imgui_gfx_renderer::RendererError;
+
+#[derive(Debug)]
+pub enum ImguiRenderError {
+	/// Creating the texture the overlay is rendered into failed.
+	RenderTarget(gfx::CombinedError),
+	/// Viewing the overlay texture as a render target failed.
+	TargetView(gfx::TargetViewError),
+	/// `imgui-gfx-renderer` failed to create its pipeline or to render the draw lists.
+	Renderer(RendererError),
+	/// Creating the quad the overlay is composited with failed.
+	Mesh(amethyst::renderer::error::Error),
+	/// Copying the overlay back from the GPU for a screenshot failed.
+	Readback(String),
+}
+
+impl fmt::Display for ImguiRenderError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ImguiRenderError::RenderTarget(e) => write!(f, "failed to create the overlay's render target: {:?}", e),
+			ImguiRenderError::TargetView(e) => write!(f, "failed to view the overlay as a render target: {:?}", e),
+			ImguiRenderError::Renderer(e) => write!(f, "imgui renderer failed: {:?}", e),
+			ImguiRenderError::Mesh(e) => write!(f, "failed to create the overlay's mesh: {}", e),
+			ImguiRenderError::Readback(e) => write!(f, "failed to read the overlay back: {}", e),
+		}
+	}
+}
+
+impl Error for ImguiRenderError {}
+
+impl From<gfx::CombinedError> for ImguiRenderError {
+	fn from(e: gfx::CombinedError) -> Self { ImguiRenderError::RenderTarget(e) }
+}
+
+impl From<gfx::TargetViewError> for ImguiRenderError {
+	fn from(e: gfx::TargetViewError) -> Self { ImguiRenderError::TargetView(e) }
+}
+
+impl From<RendererError> for ImguiRenderError {
+	fn from(e: RendererError) -> Self { ImguiRenderError::Renderer(e) }
+}
+
+/// For `Pass::compile`, which can only fail with amethyst's error: the closest variant, or the message as a
+/// `NoSuchTarget` where none fits.
+impl From<ImguiRenderError> for amethyst::renderer::error::Error {
+	fn from(e: ImguiRenderError) -> Self {
+		use amethyst::renderer::error::Error;
+		match e {
+			ImguiRenderError::RenderTarget(e) => Error::TargetCreation(e),
+			ImguiRenderError::TargetView(e) => Error::TargetCreation(gfx::CombinedError::Target(e)),
+			ImguiRenderError::Renderer(RendererError::Buffer(e)) => Error::BufferCreation(e),
+			ImguiRenderError::Renderer(RendererError::Pipeline(e)) => Error::PipelineCreation(e),
+			ImguiRenderError::Renderer(RendererError::Combined(e)) => Error::TargetCreation(e),
+			ImguiRenderError::Mesh(e) => e,
+			e => Error::NoSuchTarget(e.to_string()),
+		}
+	}
+}
@@ -0,0 +1,149 @@
+use std::{collections::VecDeque, fmt::Debug};
+
+use amethyst::{
+	core::timing::Time,
+	ecs::prelude::*,
+	shrev::{Event, EventChannel},
+};
+use imgui::{sys, ImGuiCond, ImString, Ui};
+
+use crate::{clipper::clipped_rows, history::History};
+
+const MAX_LOG: usize = 100_000;
+
+type Reader = Box<dyn FnMut(&World, &mut Vec<String>) + Send + Sync>;
+
+struct Channel {
+	name: ImString,
+	read: Reader,
+	/// Show this channel's events in the log.
+	logged: bool,
+	total: u64,
+	window: u64,
+	rate: History,
+}
+
+/// Monitors registered event channels: events per second and a log of their `Debug` output.
+/// Drawn by `event_monitor_window`.
+///
+/// ```rust,ignore
+/// let mut monitor = EventMonitor::default();
+/// monitor.register::<MenuCommand>(&mut world, "menu commands");
+/// world.add_resource(monitor);
+/// ```
+pub struct EventMonitor {
+	pub open: bool,
+	pub paused: bool,
+	pub log: VecDeque<(f64, usize, String)>,
+	channels: Vec<Channel>,
+	elapsed: f32,
+	time: f64,
+}
+
+impl Default for EventMonitor {
+	fn default() -> Self {
+		Self {
+			open: false,
+			paused: false,
+			log: VecDeque::new(),
+			channels: Vec::new(),
+			elapsed: 0.,
+			time: 0.,
+		}
+	}
+}
+
+impl EventMonitor {
+	/// Starts reading `EventChannel<T>`, adding the channel if it doesn't exist yet.
+	pub fn register<T: Event + Debug>(&mut self, world: &mut World, name: &str) {
+		let mut reader = {
+			let mut channel = world.res.entry::<EventChannel<T>>().or_insert_with(EventChannel::new);
+			channel.register_reader()
+		};
+		self.channels.push(Channel {
+			name: ImString::new(name),
+			read: Box::new(move |world, out| {
+				if let Some(channel) = world.res.try_fetch::<EventChannel<T>>() {
+					out.extend(channel.read(&mut reader).map(|x| format!("{:?}", x)));
+				}
+			}),
+			logged: true,
+			total: 0,
+			window: 0,
+			rate: History::new(120),
+		});
+	}
+}
+
+pub fn event_monitor_window(ui: &Ui, world: &World) {
+	let mut monitor = match world.res.try_fetch_mut::<EventMonitor>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let monitor = &mut *monitor;
+	let delta = world.res.try_fetch::<Time>().map_or(0., |x| x.delta_real_seconds());
+	monitor.time += f64::from(delta);
+	monitor.elapsed += delta;
+	let roll = monitor.elapsed >= 1.;
+
+	let mut events = Vec::new();
+	for (index, channel) in monitor.channels.iter_mut().enumerate() {
+		(channel.read)(world, &mut events);
+		channel.total += events.len() as u64;
+		channel.window += events.len() as u64;
+		if roll {
+			channel.rate.push(channel.window as f32 / monitor.elapsed);
+			channel.window = 0;
+		}
+		if !monitor.paused && channel.logged {
+			for event in events.drain(..) {
+				monitor.log.push_back((monitor.time, index, event));
+			}
+		}
+		events.clear();
+	}
+	if roll {
+		monitor.elapsed = 0.;
+	}
+	while monitor.log.len() > MAX_LOG {
+		monitor.log.pop_front();
+	}
+
+	if !monitor.open {
+		return;
+	}
+	let mut open = true;
+	ui.window(im_str!("Events"))
+		.size((520., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			for channel in &mut monitor.channels {
+				ui.checkbox(&im_str!("##log_{}", channel.name), &mut channel.logged);
+				ui.same_line(0.);
+				let values = channel.rate.to_vec();
+				ui.plot_lines(&channel.name, &values)
+					.overlay_text(&im_str!("{:.0}/s, {} total", channel.rate.last().unwrap_or(0.), channel.total))
+					.scale_min(0.)
+					.graph_size((300., 30.))
+					.build();
+			}
+			ui.separator();
+			ui.checkbox(im_str!("Pause log"), &mut monitor.paused);
+			ui.same_line(0.);
+			if ui.small_button(im_str!("Clear")) {
+				monitor.log.clear();
+			}
+			let channels = &monitor.channels;
+			ui.child_frame(im_str!("##log"), (0., 0.)).build(|| {
+				let log = &monitor.log;
+				clipped_rows(log.len(), None, |i| {
+					let (time, index, ref event) = log[i];
+					ui.text(format!("{:>9.3}s  [{}] {}", time, channels[index].name.to_str(), event));
+				});
+				if !monitor.paused {
+					unsafe { sys::igSetScrollHere(1.) };
+				}
+			});
+		});
+	monitor.open = open;
+}
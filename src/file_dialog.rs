@@ -0,0 +1,455 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	fs,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex, MutexGuard},
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImGuiSelectableFlags, ImMouseButton, ImStr, ImString, Ui};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FileDialogMode {
+	Open,
+	Save,
+	SelectFolder,
+}
+
+#[derive(Clone, Debug)]
+pub struct FileFilter {
+	pub name: String,
+	/// Extensions without the leading dot; empty matches everything.
+	pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+	pub fn new<S: Into<String>>(name: S, extensions: &[&str]) -> Self {
+		Self {
+			name: name.into(),
+			extensions: extensions.iter().map(|x| x.to_lowercase()).collect(),
+		}
+	}
+
+	pub fn matches(&self, path: &Path) -> bool {
+		if self.extensions.is_empty() {
+			return true;
+		}
+		path.extension()
+			.and_then(|x| x.to_str())
+			.map_or(false, |x| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(x)))
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct FileDialogOptions {
+	pub title: String,
+	pub mode: FileDialogMode,
+	pub directory: PathBuf,
+	pub file_name: String,
+	pub filters: Vec<FileFilter>,
+}
+
+impl FileDialogOptions {
+	pub fn open() -> Self { Self::new("Open file", FileDialogMode::Open) }
+
+	pub fn save() -> Self { Self::new("Save file", FileDialogMode::Save) }
+
+	pub fn select_folder() -> Self { Self::new("Select folder", FileDialogMode::SelectFolder) }
+
+	fn new(title: &str, mode: FileDialogMode) -> Self {
+		Self {
+			title: title.into(),
+			mode,
+			directory: std::env::current_dir().unwrap_or_default(),
+			file_name: String::new(),
+			filters: Vec::new(),
+		}
+	}
+
+	pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+		self.title = title.into();
+		self
+	}
+
+	pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+		self.directory = directory.into();
+		self
+	}
+
+	pub fn with_file_name<S: Into<String>>(mut self, file_name: S) -> Self {
+		self.file_name = file_name.into();
+		self
+	}
+
+	pub fn with_filter(mut self, filter: FileFilter) -> Self {
+		self.filters.push(filter);
+		self
+	}
+}
+
+type Outcome = Arc<Mutex<Option<Option<PathBuf>>>>;
+
+/// Locks `mutex` even if a thread panicked while holding it; the values behind it stay valid.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<T> { mutex.lock().unwrap_or_else(|e| e.into_inner()) }
+
+/// Handle to a pending dialog; poll it from a system or state until it resolves.
+#[derive(Clone, Debug)]
+pub struct FileDialogHandle {
+	outcome: Outcome,
+}
+
+impl FileDialogHandle {
+	/// `None` while the dialog is open, `Some(None)` if it was canceled.
+	pub fn poll(&self) -> Option<Option<PathBuf>> { lock(&self.outcome).clone() }
+
+	pub fn is_done(&self) -> bool { lock(&self.outcome).is_some() }
+}
+
+struct ActiveDialog {
+	options: FileDialogOptions,
+	outcome: Outcome,
+	directory: PathBuf,
+	file_name: ImString,
+	new_folder: ImString,
+	filter: i32,
+	error: Option<String>,
+	/// Subdirectories and files of `directory`, read again after navigating or "Refresh".
+	listing: Option<(Vec<PathBuf>, Vec<PathBuf>)>,
+	/// Subdirectories of the folders expanded in the tree, cleared with `listing`.
+	tree: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FileDialogBackend {
+	Imgui,
+	/// OS dialog through `nfd`, shown from its own thread; only available with the `nfd` feature.
+	Native,
+}
+
+impl Default for FileDialogBackend {
+	#[cfg(feature = "nfd")]
+	fn default() -> Self { FileDialogBackend::Native }
+
+	#[cfg(not(feature = "nfd"))]
+	fn default() -> Self { FileDialogBackend::Imgui }
+}
+
+/// File dialog queue; dialogs are shown one at a time by `file_dialog`.
+#[derive(Default)]
+pub struct FileDialogs {
+	pub bookmarks: Vec<PathBuf>,
+	pub backend: FileDialogBackend,
+	queue: VecDeque<ActiveDialog>,
+	/// Native dialogs that failed to open, queued as imgui dialogs by `file_dialog`.
+	fallback: Arc<Mutex<Vec<(FileDialogOptions, Outcome)>>>,
+}
+
+#[cfg(feature = "nfd")]
+fn show_native(options: &FileDialogOptions) -> Result<Option<PathBuf>, nfd::error::NFDError> {
+	use nfd::Response;
+
+	let filters = options
+		.filters
+		.iter()
+		.filter(|x| !x.extensions.is_empty())
+		.map(|x| x.extensions.join(","))
+		.collect::<Vec<_>>()
+		.join(";");
+	let filters = if filters.is_empty() { None } else { Some(filters.as_str()) };
+	let directory = options.directory.to_str();
+
+	let response = match options.mode {
+		FileDialogMode::Open => nfd::open_file_dialog(filters, directory)?,
+		FileDialogMode::Save => nfd::open_save_dialog(filters, directory)?,
+		FileDialogMode::SelectFolder => nfd::open_pick_folder(directory)?,
+	};
+	Ok(match response {
+		Response::Okay(path) => Some(path.into()),
+		Response::OkayMultiple(paths) => paths.into_iter().next().map(PathBuf::from),
+		Response::Cancel => None,
+	})
+}
+
+impl FileDialogs {
+	pub fn show(&mut self, options: FileDialogOptions) -> FileDialogHandle {
+		let outcome = Outcome::default();
+		#[cfg(feature = "nfd")]
+		{
+			if self.backend == FileDialogBackend::Native {
+				// The OS dialog blocks until it's closed, so it can't run on the game loop. When no native
+				// implementation is available it's handed back to be shown with imgui
+				let fallback = self.fallback.clone();
+				let result = outcome.clone();
+				std::thread::spawn(move || match show_native(&options) {
+					Ok(path) => *lock(&result) = Some(path),
+					Err(_) => lock(&fallback).push((options, result)),
+				});
+				return FileDialogHandle { outcome };
+			}
+		}
+		self.push(options, outcome.clone());
+		FileDialogHandle { outcome }
+	}
+
+	fn push(&mut self, options: FileDialogOptions, outcome: Outcome) {
+		let mut file_name = ImString::with_capacity(256);
+		file_name.push_str(&options.file_name);
+		self.queue.push_back(ActiveDialog {
+			directory: options.directory.clone(),
+			options,
+			outcome,
+			file_name,
+			new_folder: ImString::with_capacity(128),
+			filter: 0,
+			error: None,
+			listing: None,
+			tree: HashMap::new(),
+		});
+	}
+
+	pub fn add_bookmark<P: Into<PathBuf>>(&mut self, path: P) {
+		let path = path.into();
+		if !self.bookmarks.contains(&path) {
+			self.bookmarks.push(path);
+		}
+	}
+
+	pub fn is_open(&self) -> bool { !self.queue.is_empty() }
+}
+
+fn read_dir(directory: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+	let mut dirs = Vec::new();
+	let mut files = Vec::new();
+	if let Ok(entries) = fs::read_dir(directory) {
+		for path in entries.filter_map(|x| x.ok()).map(|x| x.path()) {
+			if path.is_dir() {
+				dirs.push(path);
+			} else {
+				files.push(path);
+			}
+		}
+	}
+	dirs.sort();
+	files.sort();
+	(dirs, files)
+}
+
+fn file_name(path: &Path) -> String {
+	path.file_name()
+		.map(|x| x.to_string_lossy().into_owned())
+		.unwrap_or_else(|| path.display().to_string())
+}
+
+fn directory_tree(
+	ui: &Ui,
+	directory: &Path,
+	tree: &mut HashMap<PathBuf, Vec<PathBuf>>,
+	selected: &mut Option<PathBuf>,
+) {
+	let children = tree.entry(directory.to_path_buf()).or_insert_with(|| read_dir(directory).0).clone();
+	for child in children {
+		let label = ImString::new(format!("{}##{}", file_name(&child), child.display()));
+		ui.tree_node(&label).build(|| {
+			if ui.small_button(im_str!("Go")) {
+				*selected = Some(child.clone());
+			}
+			directory_tree(ui, &child, tree, selected);
+		});
+	}
+}
+
+enum Action {
+	Navigate(PathBuf),
+	Accept(PathBuf),
+	Cancel,
+}
+
+fn dialog_contents(ui: &Ui, dialog: &mut ActiveDialog, bookmarks: &mut Vec<PathBuf>) -> Option<Action> {
+	let mut action = None;
+	let directory = &dialog.directory;
+	let (dirs, files) = dialog.listing.get_or_insert_with(|| read_dir(directory)).clone();
+	let filter = dialog.options.filters.get(dialog.filter as usize).cloned();
+
+	if ui.small_button(im_str!("Up")) {
+		if let Some(parent) = dialog.directory.parent() {
+			action = Some(Action::Navigate(parent.to_path_buf()));
+		}
+	}
+	ui.same_line(0.);
+	if ui.small_button(im_str!("Refresh")) {
+		dialog.listing = None;
+		dialog.tree.clear();
+	}
+	ui.same_line(0.);
+	if ui.small_button(im_str!("Bookmark")) && !bookmarks.contains(&dialog.directory) {
+		bookmarks.push(dialog.directory.clone());
+	}
+	ui.same_line(0.);
+	ui.text(dialog.directory.display().to_string());
+	ui.separator();
+
+	ui.child_frame(im_str!("##places"), (200., -60.)).show_borders(true).build(|| {
+		let mut remove = None;
+		for (index, bookmark) in bookmarks.iter().enumerate() {
+			let label = ImString::new(format!("{}##bookmark{}", file_name(bookmark), index));
+			if ui.selectable(&label, *bookmark == dialog.directory, ImGuiSelectableFlags::empty(), (0., 0.)) {
+				action = Some(Action::Navigate(bookmark.clone()));
+			}
+			if ui.is_item_hovered() && ui.imgui().is_mouse_clicked(ImMouseButton::Right) {
+				remove = Some(index);
+			}
+		}
+		if let Some(index) = remove {
+			bookmarks.remove(index);
+		}
+		if !bookmarks.is_empty() {
+			ui.separator();
+		}
+
+		let mut selected = None;
+		if let Some(root) = dialog.directory.ancestors().last().map(Path::to_path_buf) {
+			directory_tree(ui, &root, &mut dialog.tree, &mut selected);
+		}
+		if let Some(selected) = selected {
+			action = Some(Action::Navigate(selected));
+		}
+	});
+	ui.same_line(0.);
+	ui.child_frame(im_str!("##entries"), (0., -60.)).show_borders(true).build(|| {
+		for dir in &dirs {
+			let label = ImString::new(format!("[{}]", file_name(dir)));
+			if ui.selectable(&label, false, ImGuiSelectableFlags::AllowDoubleClick, (0., 0.))
+				&& ui.imgui().is_mouse_double_clicked(ImMouseButton::Left)
+			{
+				action = Some(Action::Navigate(dir.clone()));
+			}
+		}
+		if dialog.options.mode == FileDialogMode::SelectFolder {
+			return;
+		}
+		for file in files.iter().filter(|x| filter.as_ref().map_or(true, |f| f.matches(x))) {
+			let name = file_name(file);
+			let label = ImString::new(name.clone());
+			let selected = dialog.file_name.to_str() == name;
+			if ui.selectable(&label, selected, ImGuiSelectableFlags::AllowDoubleClick, (0., 0.)) {
+				dialog.file_name.clear();
+				dialog.file_name.push_str(&name);
+				if ui.imgui().is_mouse_double_clicked(ImMouseButton::Left) {
+					action = Some(Action::Accept(file.clone()));
+				}
+			}
+		}
+	});
+
+	ui.push_item_width(200.);
+	ui.input_text(im_str!("##new_folder"), &mut dialog.new_folder).build();
+	ui.pop_item_width();
+	ui.same_line(0.);
+	if ui.button(im_str!("New folder"), (0., 0.)) && !dialog.new_folder.to_str().is_empty() {
+		let path = dialog.directory.join(dialog.new_folder.to_str());
+		match fs::create_dir(&path) {
+			Ok(()) => {
+				dialog.new_folder.clear();
+				action = Some(Action::Navigate(path));
+			},
+			Err(e) => dialog.error = Some(format!("Failed to create {}: {}", path.display(), e)),
+		}
+	}
+
+	if dialog.options.mode != FileDialogMode::SelectFolder {
+		ui.push_item_width(-160.);
+		ui.input_text(im_str!("##file_name"), &mut dialog.file_name).build();
+		ui.pop_item_width();
+		if !dialog.options.filters.is_empty() {
+			let names = dialog.options.filters.iter().map(|x| ImString::new(x.name.clone())).collect::<Vec<_>>();
+			let names = names.iter().map(|x| x.as_ref()).collect::<Vec<&ImStr>>();
+			ui.same_line(0.);
+			ui.push_item_width(150.);
+			ui.combo(im_str!("##filter"), &mut dialog.filter, &names, 8);
+			ui.pop_item_width();
+		}
+	}
+
+	let accept_label = match dialog.options.mode {
+		FileDialogMode::Open => im_str!("Open"),
+		FileDialogMode::Save => im_str!("Save"),
+		FileDialogMode::SelectFolder => im_str!("Select"),
+	};
+	if ui.button(accept_label, (80., 0.)) {
+		match dialog.options.mode {
+			FileDialogMode::SelectFolder => action = Some(Action::Accept(dialog.directory.clone())),
+			_ if dialog.file_name.to_str().is_empty() => dialog.error = Some("No file name given".into()),
+			FileDialogMode::Open => {
+				let path = dialog.directory.join(dialog.file_name.to_str());
+				if path.is_file() {
+					action = Some(Action::Accept(path));
+				} else {
+					dialog.error = Some(format!("{} does not exist", path.display()));
+				}
+			},
+			FileDialogMode::Save => {
+				let mut path = dialog.directory.join(dialog.file_name.to_str());
+				if path.extension().is_none() {
+					if let Some(extension) = filter.as_ref().and_then(|x| x.extensions.first()) {
+						path.set_extension(extension);
+					}
+				}
+				action = Some(Action::Accept(path));
+			},
+		}
+	}
+	ui.same_line(0.);
+	if ui.button(im_str!("Cancel"), (80., 0.)) {
+		action = Some(Action::Cancel);
+	}
+	if let Some(ref error) = dialog.error {
+		ui.same_line(0.);
+		ui.text_colored([1., 0.35, 0.35, 1.], error.as_str());
+	}
+
+	action
+}
+
+pub fn file_dialog(ui: &Ui, world: &World) {
+	let mut dialogs = match world.res.try_fetch_mut::<FileDialogs>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let dialogs = &mut *dialogs;
+	let fallback = std::mem::replace(&mut *lock(&dialogs.fallback), Vec::new());
+	for (options, outcome) in fallback {
+		dialogs.push(options, outcome);
+	}
+	let dialog = match dialogs.queue.front_mut() {
+		Some(x) => x,
+		_ => return,
+	};
+	let bookmarks = &mut dialogs.bookmarks;
+
+	let mut open = true;
+	let mut action = None;
+	let title = ImString::new(format!("{}##file_dialog", dialog.options.title));
+	ui.window(&title)
+		.size((640., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.collapsible(false)
+		.build(|| action = dialog_contents(ui, dialog, bookmarks));
+	if !open {
+		action = Some(Action::Cancel);
+	}
+
+	let result = match action {
+		Some(Action::Navigate(directory)) => {
+			dialog.directory = directory;
+			dialog.error = None;
+			dialog.listing = None;
+			dialog.tree.clear();
+			return;
+		},
+		Some(Action::Accept(path)) => Some(path),
+		Some(Action::Cancel) => None,
+		None => return,
+	};
+	*lock(&dialog.outcome) = Some(result);
+	dialogs.queue.pop_front();
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use amethyst::{ecs::prelude::*, shrev::EventChannel};
+use imgui::Ui;
+
+use crate::ImguiState;
+
+/// A file dragged from the desktop and dropped on the window.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImguiFileDrop {
+	pub path: PathBuf,
+	/// Cursor position when it was dropped, in window pixels.
+	pub position: (i32, i32),
+	/// Whether it was dropped on an imgui window rather than the game.
+	pub over_ui: bool,
+}
+
+/// Moves files dropped since the last call to the channel, if there is one.
+pub(crate) fn publish(imgui_state: &mut ImguiState, channel: Option<&mut EventChannel<ImguiFileDrop>>) {
+	if imgui_state.dropped_files.is_empty() {
+		return;
+	}
+	let position = imgui_state.mouse_state.pos;
+	let over_ui = imgui_state.imgui.want_capture_mouse();
+	let files = imgui_state.dropped_files.drain(..);
+	if let Some(channel) = channel {
+		channel.iter_write(files.map(|path| ImguiFileDrop { path, position, over_ui }));
+	}
+}
+
+impl ImguiState {
+	/// Files currently dragged over the window.
+	pub fn hovered_files(&self) -> &[PathBuf] { &self.hovered_files }
+}
+
+/// Shows which files are dragged over the window next to the cursor.
+pub fn hover_feedback(ui: &Ui, world: &World) {
+	let imgui_state = match world.res.try_fetch::<Option<ImguiState>>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let files = match *imgui_state {
+		Some(ref x) if !x.hovered_files.is_empty() => &x.hovered_files,
+		_ => return,
+	};
+	ui.tooltip(|| {
+		ui.text(format!("Drop {} file{}", files.len(), if files.len() == 1 { "" } else { "s" }));
+		for file in files.iter().take(8) {
+			let name = match file.file_name() {
+				Some(x) => x.to_string_lossy().into_owned(),
+				None => file.display().to_string(),
+			};
+			ui.text_disabled(name);
+		}
+		if files.len() > 8 {
+			ui.text_disabled("...");
+		}
+	});
+}
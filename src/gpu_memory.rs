@@ -0,0 +1,117 @@
+use amethyst::ecs::prelude::*;
+use imgui::{sys, ImGuiCond, Ui};
+
+use crate::textures::ImguiTextures;
+
+/// imgui-gfx-renderer's vertex and index buffers start this big and grow to the largest draw list.
+const INITIAL_BUFFER_LEN: usize = 256;
+
+/// GPU memory held by the overlay itself, in bytes, updated by the `DrawUi` pass. Drawn by `gpu_memory_window`.
+///
+/// Registered textures are only referenced, their memory belongs to amethyst's asset storage.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ImguiGpuMemory {
+	pub open: bool,
+	/// Warn when the total goes over this many bytes.
+	pub budget: Option<usize>,
+	pub render_target: usize,
+	pub font_atlas: usize,
+	/// The checkerboard shown for textures that are still loading.
+	pub placeholder: usize,
+	pub vertex_buffer: usize,
+	pub index_buffer: usize,
+	/// Download buffer of a pending screenshot.
+	pub readback: usize,
+	pub registered_textures: usize,
+	largest_list: (usize, usize),
+}
+
+impl ImguiGpuMemory {
+	pub fn total(&self) -> usize {
+		self.render_target + self.font_atlas + self.placeholder + self.vertex_buffer + self.index_buffer + self.readback
+	}
+
+	/// Same safety rules as `draw_data::capture`.
+	pub(crate) unsafe fn update(&mut self, target: (u16, u16), textures: &ImguiTextures, readback: bool) {
+		let rgba = |width: usize, height: usize| width * height * 4;
+		self.render_target = rgba(usize::from(target.0), usize::from(target.1));
+		self.readback = if readback { self.render_target } else { 0 };
+		self.font_atlas = textures.font_atlas().map_or(0, |(_, size)| rgba(size.0 as usize, size.1 as usize));
+		self.placeholder = if textures.has_placeholder() { rgba(8, 8) } else { 0 };
+		self.registered_textures = textures.registered_len();
+
+		let draw_data = sys::igGetDrawData();
+		if !draw_data.is_null() && (*draw_data).valid && !(*draw_data).cmd_lists.is_null() {
+			let draw_data = &*draw_data;
+			let count = draw_data.cmd_lists_count.max(0) as usize;
+			for &list in std::slice::from_raw_parts(draw_data.cmd_lists, count) {
+				let list = &*list;
+				self.largest_list.0 = self.largest_list.0.max(list.vtx_buffer.size.max(0) as usize);
+				self.largest_list.1 = self.largest_list.1.max(list.idx_buffer.size.max(0) as usize);
+			}
+		}
+		self.vertex_buffer = self.largest_list.0.max(INITIAL_BUFFER_LEN) * std::mem::size_of::<sys::ImDrawVert>();
+		self.index_buffer = self.largest_list.1.max(INITIAL_BUFFER_LEN) * std::mem::size_of::<sys::ImDrawIdx>();
+	}
+
+	/// The pass re-created its renderer, so buffers start small again.
+	pub(crate) fn reset(&mut self) { self.largest_list = (0, 0); }
+}
+
+fn format_bytes(bytes: usize) -> String {
+	match bytes {
+		x if x >= 1 << 20 => format!("{:.2} MiB", x as f32 / (1 << 20) as f32),
+		x if x >= 1 << 10 => format!("{:.1} KiB", x as f32 / (1 << 10) as f32),
+		x => format!("{} B", x),
+	}
+}
+
+pub fn gpu_memory_window(ui: &Ui, world: &World) {
+	let mut memory = match world.res.try_fetch_mut::<ImguiGpuMemory>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !memory.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Overlay GPU memory"))
+		.size((320., 260.), ImGuiCond::FirstUseEver)
+		.always_auto_resize(true)
+		.opened(&mut open)
+		.build(|| {
+			let rows = [
+				("Render target", memory.render_target),
+				("Font atlas", memory.font_atlas),
+				("Placeholder", memory.placeholder),
+				("Vertex buffer", memory.vertex_buffer),
+				("Index buffer", memory.index_buffer),
+				("Screenshot readback", memory.readback),
+			];
+			ui.columns(2, im_str!("gpu_memory"), false);
+			for (name, bytes) in rows.iter() {
+				ui.text(*name);
+				ui.next_column();
+				ui.text(format_bytes(*bytes));
+				ui.next_column();
+			}
+			ui.separator();
+			ui.text("Total");
+			ui.next_column();
+			ui.text(format_bytes(memory.total()));
+			ui.next_column();
+			ui.columns(1, im_str!("##gpu_memory_end"), false);
+			ui.text_disabled(&im_str!("{} registered textures, owned by amethyst", memory.registered_textures));
+
+			if let Some(budget) = memory.budget {
+				let fraction = memory.total() as f32 / budget.max(1) as f32;
+				let overlay = im_str!("{} of {}", format_bytes(memory.total()), format_bytes(budget));
+				ui.progress_bar(fraction.min(1.)).size((300., 0.)).overlay_text(&overlay).build();
+				if fraction > 1. {
+					ui.text_colored([1., 0.35, 0.35, 1.], "Over budget");
+				}
+			}
+		});
+	memory.open = open;
+}
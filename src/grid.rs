@@ -0,0 +1,155 @@
+use amethyst::{core::nalgebra::Point3, ecs::prelude::*};
+use imgui::{sys, ImGuiCol, ImGuiCond, ImGuiWindowFlags, ImVec2, StyleVar, Ui};
+
+use crate::picking::ScreenProjection;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GridPlane {
+	/// The ground of a 2D game, or a side-on view.
+	XY,
+	/// The ground of a Y-up 3D game.
+	XZ,
+}
+
+/// World grid and origin axes drawn under every imgui window by `grid`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Grid {
+	pub visible: bool,
+	pub plane: GridPlane,
+	/// World units between lines.
+	pub spacing: f32,
+	/// Lines drawn on each side of the point under the camera.
+	pub lines: u32,
+	/// Distance from the camera where lines have faded out completely, `None` to never fade.
+	pub fade_distance: Option<f32>,
+	/// Draw the X (red), Y (green) and Z (blue) axes through the origin.
+	pub axes: bool,
+	/// Line color, ABGR.
+	pub color: u32,
+}
+
+impl Default for Grid {
+	fn default() -> Self {
+		Self {
+			visible: true,
+			plane: GridPlane::XY,
+			spacing: 1.,
+			lines: 50,
+			fade_distance: Some(40.),
+			axes: true,
+			color: 0x80_80_80_80,
+		}
+	}
+}
+
+/// Segments per line, so each can fade and be culled behind the camera on its own.
+const SEGMENTS: u32 = 16;
+
+fn with_alpha(color: u32, alpha: f32) -> u32 {
+	let a = ((color >> 24) as f32 * alpha.max(0.).min(1.)) as u32;
+	(color & 0x00_ff_ff_ff) | (a << 24)
+}
+
+fn draw_line(
+	list: *mut sys::ImDrawList,
+	projection: &ScreenProjection,
+	grid: &Grid,
+	a: Point3<f32>,
+	b: Point3<f32>,
+	color: u32,
+) {
+	for i in 0..SEGMENTS {
+		let (t0, t1) = (i as f32 / SEGMENTS as f32, (i + 1) as f32 / SEGMENTS as f32);
+		let (start, end) = (a + (b - a) * t0, a + (b - a) * t1);
+		let alpha = match grid.fade_distance {
+			Some(fade) => 1. - ((start + (end - start) / 2.) - projection.camera_position).norm() / fade,
+			None => 1.,
+		};
+		if alpha <= 0. {
+			continue;
+		}
+		if let (Some(start), Some(end)) = (projection.project(&start), projection.project(&end)) {
+			unsafe { sys::ImDrawList_AddLine(list, start, end, with_alpha(color, alpha), 1.) };
+		}
+	}
+}
+
+fn draw(list: *mut sys::ImDrawList, projection: &ScreenProjection, grid: &Grid) {
+	let point = |u: f32, v: f32| match grid.plane {
+		GridPlane::XY => Point3::new(u, v, 0.),
+		GridPlane::XZ => Point3::new(u, 0., v),
+	};
+	let camera = projection.camera_position;
+	let (center_u, center_v) = match grid.plane {
+		GridPlane::XY => (camera.x, camera.y),
+		GridPlane::XZ => (camera.x, camera.z),
+	};
+	// Snap to the grid so lines don't swim as the camera moves
+	let snap = |x: f32| (x / grid.spacing).round() * grid.spacing;
+	let (center_u, center_v) = (snap(center_u), snap(center_v));
+	let extent = grid.lines as f32 * grid.spacing;
+	for i in -(grid.lines as i32)..=grid.lines as i32 {
+		let offset = i as f32 * grid.spacing;
+		let (u, v) = (center_u + offset, center_v + offset);
+		draw_line(list, projection, grid, point(u, center_v - extent), point(u, center_v + extent), grid.color);
+		draw_line(list, projection, grid, point(center_u - extent, v), point(center_u + extent, v), grid.color);
+	}
+	if grid.axes {
+		let axes = [
+			(Point3::new(extent, 0., 0.), 0xff_40_40_ff),
+			(Point3::new(0., extent, 0.), 0xff_40_ff_40),
+			(Point3::new(0., 0., extent), 0xff_ff_40_40),
+		];
+		let axes_grid = Grid { fade_distance: None, ..*grid };
+		for &(end, color) in &axes {
+			draw_line(list, projection, &axes_grid, Point3::origin(), end, color);
+		}
+	}
+}
+
+/// Draws the `Grid` behind every other imgui window, projected through the active camera.
+///
+/// Call it before building any other window in the frame. While the `Viewport` window shows the game,
+/// the grid goes on the overlay draw list clipped to the viewport image instead, as it would be hidden otherwise.
+pub fn grid(ui: &Ui, world: &World) {
+	let grid = match world.res.try_fetch::<Grid>() {
+		Some(ref x) if x.visible && x.spacing > 0. => **x,
+		_ => return,
+	};
+	let projection = match ScreenProjection::new(ui, world) {
+		Some(x) => x,
+		_ => return,
+	};
+	let ((left, top), (width, height)) = projection.rect;
+
+	if projection.in_viewport {
+		unsafe {
+			let list = sys::igGetOverlayDrawList();
+			sys::ImDrawList_PushClipRect(list, ImVec2::new(left, top), ImVec2::new(left + width, top + height), true);
+			draw(list, &projection, &grid);
+			sys::ImDrawList_PopClipRect(list);
+		}
+		return;
+	}
+	ui.with_color_var(ImGuiCol::WindowBg, (0., 0., 0., 0.), || {
+		ui.with_style_vars(&[StyleVar::WindowPadding(ImVec2::new(0., 0.)), StyleVar::WindowBorderSize(0.)], || {
+			ui.window(im_str!("##grid"))
+				.position((left, top), ImGuiCond::Always)
+				.size((width, height), ImGuiCond::Always)
+				.flags(
+					ImGuiWindowFlags::NoTitleBar
+						| ImGuiWindowFlags::NoResize
+						| ImGuiWindowFlags::NoMove
+						| ImGuiWindowFlags::NoScrollbar
+						| ImGuiWindowFlags::NoScrollWithMouse
+						| ImGuiWindowFlags::NoInputs
+						| ImGuiWindowFlags::NoSavedSettings
+						| ImGuiWindowFlags::NoFocusOnAppearing
+						| ImGuiWindowFlags::NoBringToFrontOnFocus,
+				)
+				.build(|| {
+					draw(unsafe { sys::igGetWindowDrawList() }, &projection, &grid);
+				});
+		});
+	});
+}
@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+
+/// Fixed-capacity rolling buffer of samples, oldest first.
+#[derive(Clone, Debug)]
+pub struct History {
+	values: VecDeque<f32>,
+	capacity: usize,
+}
+
+impl History {
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			values: VecDeque::with_capacity(capacity),
+			capacity: capacity.max(1),
+		}
+	}
+
+	pub fn push(&mut self, value: f32) {
+		while self.values.len() >= self.capacity {
+			self.values.pop_front();
+		}
+		self.values.push_back(value);
+	}
+
+	pub fn capacity(&self) -> usize { self.capacity }
+
+	pub fn set_capacity(&mut self, capacity: usize) {
+		self.capacity = capacity.max(1);
+		while self.values.len() > self.capacity {
+			self.values.pop_front();
+		}
+	}
+
+	pub fn len(&self) -> usize { self.values.len() }
+
+	pub fn is_empty(&self) -> bool { self.values.is_empty() }
+
+	pub fn clear(&mut self) { self.values.clear(); }
+
+	pub fn last(&self) -> Option<f32> { self.values.back().cloned() }
+
+	pub fn iter(&self) -> impl Iterator<Item = f32> + '_ { self.values.iter().cloned() }
+
+	pub fn min(&self) -> Option<f32> { self.iter().fold(None, |x, y| Some(x.map_or(y, |x: f32| x.min(y)))) }
+
+	pub fn max(&self) -> Option<f32> { self.iter().fold(None, |x, y| Some(x.map_or(y, |x: f32| x.max(y)))) }
+
+	pub fn average(&self) -> f32 {
+		if self.values.is_empty() {
+			return 0.;
+		}
+		self.iter().sum::<f32>() / self.values.len() as f32
+	}
+
+	/// Contiguous copy of the samples, as expected by `Ui::plot_lines`/`plot_histogram`.
+	pub fn to_vec(&self) -> Vec<f32> { self.iter().collect() }
+}
+
+impl Default for History {
+	fn default() -> Self { Self::new(120) }
+}
@@ -0,0 +1,80 @@
+use std::ffi::CStr;
+
+use imgui::sys;
+
+/// Opaque triangles of a window drawn with a shaped hit area, as of the last rendered frame.
+#[derive(Clone, Debug)]
+pub(crate) struct HitShape {
+	min: (f32, f32),
+	max: (f32, f32),
+	triangles: Vec<[(f32, f32); 3]>,
+}
+
+/// Vertices more transparent than this don't catch the mouse.
+const MIN_ALPHA: u32 = 8;
+
+fn owned_by(owner: &str, windows: &[String]) -> bool {
+	// Child windows are named "Parent/Child_1234"
+	windows.iter().any(|x| owner == x || (owner.starts_with(x.as_str()) && owner[x.len()..].starts_with('/')))
+}
+
+/// Reads the shapes of `windows` from the last draw data, which stays valid until the next frame starts.
+pub(crate) unsafe fn collect(windows: &[String]) -> Vec<HitShape> {
+	let draw_data = sys::igGetDrawData();
+	if windows.is_empty() || draw_data.is_null() || !(*draw_data).valid || (*draw_data).cmd_lists.is_null() {
+		return Vec::new();
+	}
+	let draw_data = &*draw_data;
+	let count = draw_data.cmd_lists_count.max(0) as usize;
+	let mut shapes = Vec::new();
+	for &list in std::slice::from_raw_parts(draw_data.cmd_lists, count) {
+		let list = &*list;
+		if list.owner_name.is_null() || !owned_by(&CStr::from_ptr(list.owner_name).to_string_lossy(), windows) {
+			continue;
+		}
+		if list.vtx_buffer.data.is_null() || list.idx_buffer.data.is_null() {
+			continue;
+		}
+		let vertices = std::slice::from_raw_parts(list.vtx_buffer.data, list.vtx_buffer.size.max(0) as usize);
+		let indices = std::slice::from_raw_parts(list.idx_buffer.data, list.idx_buffer.size.max(0) as usize);
+		let mut shape = HitShape {
+			min: (std::f32::MAX, std::f32::MAX),
+			max: (std::f32::MIN, std::f32::MIN),
+			triangles: Vec::new(),
+		};
+		for triangle in indices.chunks(3).filter(|x| x.len() == 3) {
+			let corners = [
+				vertices[triangle[0] as usize],
+				vertices[triangle[1] as usize],
+				vertices[triangle[2] as usize],
+			];
+			for corner in &corners {
+				shape.min = (shape.min.0.min(corner.pos.x), shape.min.1.min(corner.pos.y));
+				shape.max = (shape.max.0.max(corner.pos.x), shape.max.1.max(corner.pos.y));
+			}
+			if corners.iter().any(|x| x.col >> 24 >= MIN_ALPHA) {
+				let point = |i: usize| (corners[i].pos.x, corners[i].pos.y);
+				shape.triangles.push([point(0), point(1), point(2)]);
+			}
+		}
+		if shape.min.0 <= shape.max.0 {
+			shapes.push(shape);
+		}
+	}
+	shapes
+}
+
+fn in_triangle(p: (f32, f32), [a, b, c]: &[(f32, f32); 3]) -> bool {
+	let side = |a: (f32, f32), b: (f32, f32)| (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+	let (x, y, z) = (side(*a, *b), side(*b, *c), side(*c, *a));
+	(x >= 0. && y >= 0. && z >= 0.) || (x <= 0. && y <= 0. && z <= 0.)
+}
+
+/// Whether `pos` is inside a shaped window, but not on anything it drew.
+pub(crate) fn is_transparent(shapes: &[HitShape], pos: (f32, f32)) -> bool {
+	let inside = |shape: &&HitShape| {
+		pos.0 >= shape.min.0 && pos.1 >= shape.min.1 && pos.0 <= shape.max.0 && pos.1 <= shape.max.1
+	};
+	let mut candidates = shapes.iter().filter(inside).peekable();
+	candidates.peek().is_some() && !candidates.any(|x| x.triangles.iter().any(|t| in_triangle(pos, t)))
+}
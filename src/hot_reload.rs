@@ -0,0 +1,269 @@
+use std::{
+	collections::{HashMap, VecDeque},
+	fs,
+	path::{Path, PathBuf},
+	time::SystemTime,
+};
+
+use amethyst::{core::timing::Time, ecs::prelude::*};
+use imgui::{sys, ImGuiCond, ImGuiWindowFlags, Ui};
+
+use crate::{
+	code_view::{CodeView, Language},
+	context_menus::asset_context_menu,
+};
+
+const MAX_ENTRIES: usize = 128;
+
+#[derive(Clone, Debug)]
+pub struct ReloadEntry {
+	pub time: f64,
+	pub path: PathBuf,
+	pub error: Option<String>,
+}
+
+/// Feed of reloaded assets, filled by `HotReloadWatchSystem` and by `record_error` for failures.
+pub struct HotReloadFeed {
+	pub open: bool,
+	pub toasts: bool,
+	pub toast_seconds: f64,
+	pub entries: VecDeque<ReloadEntry>,
+	now: f64,
+	/// Asset directory of `HotReloadWatchSystem`, which entries are relative to.
+	root: Option<PathBuf>,
+	/// File shown by "Source", with the line its error points at.
+	viewing: Option<(PathBuf, Result<String, String>, Option<usize>)>,
+}
+
+impl Default for HotReloadFeed {
+	fn default() -> Self {
+		Self {
+			open: false,
+			toasts: true,
+			toast_seconds: 4.,
+			entries: VecDeque::new(),
+			now: 0.,
+			root: None,
+			viewing: None,
+		}
+	}
+}
+
+impl HotReloadFeed {
+	fn push(&mut self, path: PathBuf, error: Option<String>) {
+		if self.entries.len() >= MAX_ENTRIES {
+			self.entries.pop_front();
+		}
+		let time = self.now;
+		self.entries.push_back(ReloadEntry { time, path, error });
+	}
+
+	pub fn record_reload<P: Into<PathBuf>>(&mut self, path: P) { self.push(path.into(), None); }
+
+	pub fn record_error<P: Into<PathBuf>, S: Into<String>>(&mut self, path: P, error: S) {
+		self.push(path.into(), Some(error.into()));
+	}
+
+	/// Where an entry's file is on disk: relative paths are resolved against the watched asset directory.
+	pub fn resolve(&self, path: &Path) -> PathBuf {
+		match self.root {
+			Some(ref root) if path.is_relative() => root.join(path),
+			_ => path.to_path_buf(),
+		}
+	}
+}
+
+/// Polls modification times under an asset directory and reports changed files to `HotReloadFeed`.
+///
+/// Meant to run alongside amethyst's `HotReloadSystem`, with a matching interval.
+pub struct HotReloadWatchSystem {
+	root: PathBuf,
+	interval: f64,
+	last_scan: Option<f64>,
+	mtimes: HashMap<PathBuf, SystemTime>,
+}
+
+impl HotReloadWatchSystem {
+	pub fn new<P: Into<PathBuf>>(root: P, interval: f64) -> Self {
+		Self {
+			root: root.into(),
+			interval,
+			last_scan: None,
+			mtimes: HashMap::new(),
+		}
+	}
+}
+
+fn scan(dir: &Path, out: &mut Vec<(PathBuf, SystemTime)>) {
+	let entries = match fs::read_dir(dir) {
+		Ok(x) => x,
+		_ => return,
+	};
+	for entry in entries.filter_map(|x| x.ok()) {
+		let path = entry.path();
+		match entry.metadata() {
+			Ok(ref metadata) if metadata.is_dir() => scan(&path, out),
+			Ok(metadata) => {
+				if let Ok(modified) = metadata.modified() {
+					out.push((path, modified));
+				}
+			},
+			_ => {},
+		}
+	}
+}
+
+impl<'s> System<'s> for HotReloadWatchSystem {
+	type SystemData = (Read<'s, Time>, Write<'s, HotReloadFeed>);
+
+	fn run(&mut self, (time, mut feed): Self::SystemData) {
+		let now = time.absolute_real_time_seconds();
+		feed.now = now;
+		if feed.root.is_none() {
+			feed.root = Some(self.root.clone());
+		}
+		if self.last_scan.map_or(false, |x| now - x < self.interval) {
+			return;
+		}
+		let first_scan = self.last_scan.is_none();
+		self.last_scan = Some(now);
+
+		let mut files = Vec::new();
+		scan(&self.root, &mut files);
+		for (path, modified) in files {
+			let changed = self.mtimes.get(&path).map_or(false, |x| *x != modified);
+			if changed && !first_scan {
+				let relative = path.strip_prefix(&self.root).map(Path::to_path_buf).unwrap_or_else(|_| path.clone());
+				feed.record_reload(relative);
+			}
+			self.mtimes.insert(path, modified);
+		}
+	}
+}
+
+/// The line a shader compiler error points at, from the `0:12(5):`, `0(12) :` or `ERROR: 0:12:` forms
+/// drivers use.
+fn error_line(error: &str) -> Option<usize> {
+	for (i, _) in error.match_indices("0:").chain(error.match_indices("0(")) {
+		if i > 0 && error.as_bytes()[i - 1].is_ascii_digit() {
+			continue;
+		}
+		let digits = error[i + 2..].chars().take_while(char::is_ascii_digit).collect::<String>();
+		if let Ok(line) = digits.parse() {
+			return Some(line);
+		}
+	}
+	None
+}
+
+fn entry_line(ui: &Ui, entry: &ReloadEntry) {
+	match entry.error {
+		Some(ref error) => {
+			ui.text_colored([1., 0.35, 0.35, 1.], format!("{:>9.2}s  {}", entry.time, entry.path.display()));
+			ui.text_wrapped(&im_str!("    {}", error));
+		},
+		None => ui.text(format!("{:>9.2}s  {}", entry.time, entry.path.display())),
+	}
+}
+
+pub fn hot_reload_window(ui: &Ui, world: &World) {
+	let mut feed = match world.res.try_fetch_mut::<HotReloadFeed>() {
+		Some(x) => x,
+		_ => return,
+	};
+
+	if feed.toasts {
+		let now = feed.now;
+		let recent = feed
+			.entries
+			.iter()
+			.rev()
+			.take_while(|x| now - x.time < feed.toast_seconds)
+			.take(5)
+			.collect::<Vec<_>>();
+		if !recent.is_empty() {
+			let (width, _) = ui.frame_size().logical_size;
+			ui.window(im_str!("##hot_reload_toasts"))
+				.position((width as f32 - 330., 10.), ImGuiCond::Always)
+				.size((320., 0.), ImGuiCond::Always)
+				.flags(
+					ImGuiWindowFlags::NoTitleBar
+						| ImGuiWindowFlags::NoResize
+						| ImGuiWindowFlags::NoMove
+						| ImGuiWindowFlags::NoSavedSettings
+						| ImGuiWindowFlags::NoFocusOnAppearing
+						| ImGuiWindowFlags::AlwaysAutoResize,
+				)
+				.build(|| {
+					ui.text("Reloaded");
+					ui.separator();
+					for entry in recent {
+						entry_line(ui, entry);
+					}
+				});
+		}
+	}
+
+	if !feed.open {
+		return;
+	}
+	let mut open = true;
+	let mut clear = false;
+	let mut view = None;
+	let mut action = None;
+	ui.window(im_str!("Hot reload"))
+		.size((420., 300.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			let errors = feed.entries.iter().filter(|x| x.error.is_some()).count();
+			ui.text(format!("{} reloads, {} errors", feed.entries.len() - errors, errors));
+			ui.same_line(0.);
+			clear = ui.small_button(im_str!("Clear"));
+			ui.separator();
+			for (i, entry) in feed.entries.iter().enumerate().rev() {
+				entry_line(ui, entry);
+				// The same file can be listed more than once, so the menu's id comes from the row
+				unsafe { sys::igPushIDInt(i as i32) };
+				action = asset_context_menu(ui, world, &feed.resolve(&entry.path)).or(action);
+				unsafe { sys::igPopID() };
+				if let Some(ref error) = entry.error {
+					if ui.small_button(&im_str!("Source##{}", i)) {
+						view = Some((entry.path.clone(), error_line(error)));
+					}
+				}
+			}
+		});
+	feed.open = open;
+	if clear {
+		feed.entries.clear();
+	}
+	if let Some((path, line)) = view {
+		let source = fs::read_to_string(feed.resolve(&path)).map_err(|e| e.to_string());
+		feed.viewing = Some((path, source, line));
+	}
+
+	let mut close = false;
+	if let Some((ref path, ref source, line)) = feed.viewing {
+		let mut open = true;
+		ui.window(im_str!("Reload source"))
+			.size((560., 420.), ImGuiCond::FirstUseEver)
+			.opened(&mut open)
+			.build(|| {
+				ui.text(path.display().to_string());
+				match source {
+					Ok(source) => CodeView::new(source, Language::from_path(path))
+						.error_line(line)
+						.build(ui, &im_str!("##{}", path.display())),
+					Err(e) => ui.text_colored([1., 0.35, 0.35, 1.], format!("Can't read: {}", e)),
+				}
+			});
+		close = !open;
+	}
+	if close {
+		feed.viewing = None;
+	}
+	drop(feed);
+	if let Some(action) = action {
+		action(world);
+	}
+}
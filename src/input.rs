@@ -0,0 +1,47 @@
+use amethyst::{
+	ecs::prelude::*,
+	renderer::Event,
+	shrev::{EventChannel, ReaderId},
+};
+
+use crate::{
+	file_drop::{self, ImguiFileDrop},
+	process_event,
+	recording::InputRecorder,
+	ImguiState,
+};
+
+/// Feeds window events to imgui from the dispatcher, instead of calling `handle_imgui_events` in every state.
+///
+/// The reader lives in the system, so rebuilding the render pipeline doesn't lose or repeat events.
+/// Events arriving before the `DrawUi` pass created the imgui context are dropped.
+#[derive(Default)]
+pub struct ImguiInputSystem {
+	reader: Option<ReaderId<Event>>,
+}
+
+impl<'s> System<'s> for ImguiInputSystem {
+	type SystemData = (
+		Read<'s, EventChannel<Event>>,
+		Write<'s, Option<ImguiState>>,
+		Option<Write<'s, InputRecorder>>,
+		Option<Write<'s, EventChannel<ImguiFileDrop>>>,
+	);
+
+	fn run(&mut self, (events, mut imgui_state, mut recorder, mut file_drops): Self::SystemData) {
+		let reader = self.reader.as_mut().expect("ImguiInputSystem::setup wasn't called");
+		for event in events.read(reader) {
+			if let Some(ref mut imgui_state) = *imgui_state {
+				process_event(imgui_state, recorder.as_mut().map(|x| &mut **x), event);
+			}
+		}
+		if let Some(ref mut imgui_state) = *imgui_state {
+			file_drop::publish(imgui_state, file_drops.as_mut().map(|x| &mut **x));
+		}
+	}
+
+	fn setup(&mut self, res: &mut Resources) {
+		Self::SystemData::setup(res);
+		self.reader = Some(res.fetch_mut::<EventChannel<Event>>().register_reader());
+	}
+}
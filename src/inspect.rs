@@ -0,0 +1,88 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use imgui::{ImStr, Ui};
+
+use crate::curves::{self, curve_editor, gradient_editor, Curve, Gradient};
+
+thread_local! {
+	/// Value range each inspected curve was last shown with, by label, kept while one of its keys is dragged.
+	static CURVE_RANGES: RefCell<HashMap<String, (f32, f32)>> = RefCell::new(HashMap::new());
+}
+
+/// Values that can draw their own editor, so whole parameter structs can be edited by implementing it field by field.
+///
+/// ```rust,ignore
+/// impl ImguiInspect for EmitterParams {
+///     fn inspect(&mut self, ui: &Ui, _label: &ImStr) -> bool {
+///         self.rate.inspect(ui, im_str!("Rate")) | self.size.inspect(ui, im_str!("Size"))
+///     }
+/// }
+/// ```
+pub trait ImguiInspect {
+	/// Returns whether the value changed.
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool;
+}
+
+impl ImguiInspect for f32 {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool { ui.drag_float(label, self).speed(0.01).build() }
+}
+
+impl ImguiInspect for i32 {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool { ui.drag_int(label, self).build() }
+}
+
+impl ImguiInspect for u32 {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool {
+		let mut x = *self as i32;
+		let changed = ui.drag_int(label, &mut x).min(0).build();
+		*self = x.max(0) as u32;
+		changed
+	}
+}
+
+impl ImguiInspect for bool {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool { ui.checkbox(label, self) }
+}
+
+impl ImguiInspect for [f32; 2] {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool { ui.drag_float2(label, self).speed(0.01).build() }
+}
+
+impl ImguiInspect for [f32; 3] {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool { ui.drag_float3(label, self).speed(0.01).build() }
+}
+
+/// Edited as a color.
+impl ImguiInspect for [f32; 4] {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool { ui.color_edit(label, self).build() }
+}
+
+impl ImguiInspect for Curve {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool {
+		ui.text(label);
+		// Leave headroom so keys can be dragged past the current extremes. It's frozen while dragging, or a key
+		// held at the edge would grow the range every frame.
+		let dragging = curves::is_dragging(label);
+		let range = CURVE_RANGES.with(|ranges| {
+			let mut ranges = ranges.borrow_mut();
+			match ranges.get(label.to_str()) {
+				Some(&range) if dragging => range,
+				_ => {
+					let (min, max) = self.keys.iter().fold((0f32, 1f32), |(min, max), x| (min.min(x.1), max.max(x.1)));
+					let padding = (max - min) * 0.25;
+					let range = (if min < 0. { min - padding } else { 0. }, max + padding);
+					ranges.insert(label.to_str().to_owned(), range);
+					range
+				},
+			}
+		});
+		curve_editor(ui, label, self, range, (300., 100.))
+	}
+}
+
+impl ImguiInspect for Gradient {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool {
+		ui.text(label);
+		gradient_editor(ui, label, self, 300.)
+	}
+}
@@ -0,0 +1,209 @@
+use std::collections::VecDeque;
+
+use amethyst::ecs::prelude::*;
+use imgui::{sys, ImGuiCol, ImGuiCond, ImGuiWindowFlags, ImString, ImVec2, Ui};
+
+use crate::{click_through_flags, ImguiInput};
+
+/// Key indices registered in `create_imgui`.
+const KEY_BACKSPACE: u8 = 10;
+const KEY_ENTER: u8 = 11;
+
+const ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl-", "zxcvbnm,./"];
+const SHIFTED: [&str; 4] = ["!@#$%^&*()", "QWERTYUIOP", "ASDFGHJKL_", "ZXCVBNM<>?"];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Key {
+	Char(usize),
+	Shift,
+	Space,
+	Backspace,
+	Done,
+}
+
+/// The bottom row, below the character rows.
+const SPECIAL: [(Key, &str, f32); 4] =
+	[(Key::Shift, "Shift", 2.), (Key::Space, "Space", 4.), (Key::Backspace, "Del", 2.), (Key::Done, "Done", 2.)];
+
+/// Directional input for the on-screen keyboard, mapped from the game's controller buttons.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum KeyboardNav {
+	Up,
+	Down,
+	Left,
+	Right,
+	/// Types the highlighted key.
+	Press,
+	/// Deletes the character before the text cursor, wherever the highlight is.
+	Backspace,
+	/// Hides the keyboard; the text field keeps focus.
+	Close,
+}
+
+/// A keyboard drawn by `on_screen_keyboard` for typing into imgui text fields with a controller.
+///
+/// It opens when a text field gains focus while `gamepad` is set and closes when the field loses it.
+/// amethyst 0.10 doesn't read gamepads, so set `gamepad` while the player uses one and map its buttons (e.g.
+/// from gilrs) to `navigate` calls. Keys are typed through `open_frame` as if they came from a keyboard.
+#[derive(Default)]
+pub struct OnScreenKeyboard {
+	/// The player is using a controller, so focusing a text field opens the keyboard.
+	pub gamepad: bool,
+	pub open: bool,
+	shift: bool,
+	/// Row and column of the highlighted key; the row past the character rows is `SPECIAL`.
+	cursor: (usize, usize),
+	was_typing: bool,
+	input: Vec<KeyboardNav>,
+	typed: VecDeque<ImguiInput>,
+}
+
+impl OnScreenKeyboard {
+	pub fn navigate(&mut self, input: KeyboardNav) { self.input.push(input); }
+
+	fn row_len(row: usize) -> usize { ROWS.get(row).map_or(SPECIAL.len(), |x| x.chars().count()) }
+
+	fn key(&self) -> Key {
+		match ROWS.get(self.cursor.0) {
+			Some(_) => Key::Char(self.cursor.1),
+			None => SPECIAL[self.cursor.1].0,
+		}
+	}
+
+	fn press_key(&mut self, key: u8) {
+		self.typed.push_back(ImguiInput::Key(key, true));
+		self.typed.push_back(ImguiInput::Key(key, false));
+	}
+
+	fn press(&mut self) {
+		match self.key() {
+			Key::Char(i) => {
+				let rows = if self.shift { &SHIFTED } else { &ROWS };
+				if let Some(c) = rows[self.cursor.0].chars().nth(i) {
+					self.typed.push_back(ImguiInput::Character(c));
+				}
+				self.shift = false;
+			},
+			Key::Shift => self.shift = !self.shift,
+			Key::Space => self.typed.push_back(ImguiInput::Character(' ')),
+			Key::Backspace => self.press_key(KEY_BACKSPACE),
+			Key::Done => {
+				self.press_key(KEY_ENTER);
+				self.open = false;
+			},
+		}
+	}
+
+	/// Moves the highlight, keeping roughly the same horizontal position between rows of different widths.
+	fn move_cursor(&mut self, rows: isize, columns: isize) {
+		let count = ROWS.len() as isize + 1;
+		let (row, column) = (self.cursor.0 as isize, self.cursor.1 as isize);
+		let len = Self::row_len(self.cursor.0) as isize;
+		if rows != 0 {
+			let position = (column as f32 + 0.5) / len as f32;
+			let row = ((row + rows) % count + count) % count;
+			let len = Self::row_len(row as usize);
+			self.cursor = (row as usize, ((position * len as f32) as usize).min(len - 1));
+		} else {
+			self.cursor.1 = (((column + columns) % len + len) % len) as usize;
+		}
+	}
+
+	/// Inputs to feed imgui this frame. A key press ends the frame's batch so its release comes a frame later.
+	pub(crate) fn frame_input(&mut self) -> Vec<ImguiInput> {
+		let mut out = Vec::new();
+		while let Some(input) = self.typed.pop_front() {
+			out.push(input);
+			if let ImguiInput::Key(_, true) = input {
+				break;
+			}
+		}
+		out
+	}
+}
+
+pub fn on_screen_keyboard(ui: &Ui, world: &World) {
+	let mut keyboard = match world.res.try_fetch_mut::<OnScreenKeyboard>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let keyboard = &mut *keyboard;
+
+	let typing = unsafe { (*sys::igGetIO()).want_text_input };
+	if typing && !keyboard.was_typing && keyboard.gamepad {
+		keyboard.open = true;
+		keyboard.shift = false;
+	}
+	keyboard.was_typing = typing;
+	if !typing {
+		keyboard.open = false;
+	}
+	if !keyboard.open {
+		keyboard.input.clear();
+		return;
+	}
+
+	for input in std::mem::replace(&mut keyboard.input, Vec::new()) {
+		match input {
+			KeyboardNav::Up => keyboard.move_cursor(-1, 0),
+			KeyboardNav::Down => keyboard.move_cursor(1, 0),
+			KeyboardNav::Left => keyboard.move_cursor(0, -1),
+			KeyboardNav::Right => keyboard.move_cursor(0, 1),
+			KeyboardNav::Press => keyboard.press(),
+			KeyboardNav::Backspace => keyboard.press_key(KEY_BACKSPACE),
+			KeyboardNav::Close => keyboard.open = false,
+		}
+	}
+	if !keyboard.open {
+		return;
+	}
+
+	let (width, height) = ui.frame_size().logical_size;
+	unsafe {
+		let position = ImVec2::new(width as f32 / 2., height as f32 - 20.);
+		sys::igSetNextWindowPos(position, ImGuiCond::Always, ImVec2::new(0.5, 1.));
+	}
+	// Focusing the keyboard would take it away from the text field, so it only takes controller input
+	let flags = click_through_flags()
+		| ImGuiWindowFlags::NoTitleBar
+		| ImGuiWindowFlags::NoMove
+		| ImGuiWindowFlags::NoNav
+		| ImGuiWindowFlags::NoSavedSettings
+		| ImGuiWindowFlags::AlwaysAutoResize;
+	let size = 36.;
+	let highlight = unsafe { (*sys::igGetStyle()).colors[ImGuiCol::ButtonActive as usize] };
+	let cursor = keyboard.cursor;
+	ui.window(im_str!("##on_screen_keyboard")).flags(flags).build(|| {
+		let rows = if keyboard.shift { &SHIFTED } else { &ROWS };
+		let key = |label: &ImString, selected: bool, width: f32| {
+			if selected {
+				ui.with_color_var(ImGuiCol::Button, highlight, || ui.button(label, (width, size)));
+			} else {
+				ui.button(label, (width, size));
+			}
+		};
+		for (row, keys) in rows.iter().enumerate() {
+			for (column, c) in keys.chars().enumerate() {
+				if column > 0 {
+					ui.same_line(0.);
+				}
+				// Labels are just the character, so ids come from the position
+				unsafe { sys::igPushIDInt((row * 16 + column) as i32) };
+				key(&ImString::new(c.to_string()), cursor == (row, column), size);
+				unsafe { sys::igPopID() };
+			}
+		}
+		for (column, &(special, label, span)) in SPECIAL.iter().enumerate() {
+			if column > 0 {
+				ui.same_line(0.);
+			}
+			let label = match special {
+				Key::Shift if keyboard.shift => ImString::new("SHIFT"),
+				_ => ImString::new(label),
+			};
+			// Spans line up with the character keys, spacing included
+			let width = span * size + (span - 1.) * unsafe { (*sys::igGetStyle()).item_spacing.x };
+			key(&label, cursor == (ROWS.len(), column), width);
+		}
+	});
+}
@@ -0,0 +1,65 @@
+use imgui::{sys, ImGuiCond, ImVec2, Ui};
+
+use crate::safe_area::Corner;
+
+/// Where a window is pinned to the screen; re-applied every frame, so it follows resizes.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Anchor {
+	pub corner: Corner,
+	/// Distance from the edges the window sticks to, in pixels.
+	pub offset: (f32, f32),
+	/// Stretch windows on the top or bottom edge across the screen's width, and ones on the left or right
+	/// edge across its height, keeping `offset` on both ends.
+	pub stretch: bool,
+}
+
+impl Anchor {
+	pub fn new(corner: Corner, offset: (f32, f32)) -> Self {
+		Self {
+			corner,
+			offset,
+			stretch: false,
+		}
+	}
+
+	pub fn stretched(self) -> Self { Self { stretch: true, ..self } }
+
+	/// Positions (and when stretched, sizes) the next window inside `rect`, given as top left corner and size.
+	pub fn apply_in(&self, rect: ((f32, f32), (f32, f32))) {
+		let ((left, top), (width, height)) = rect;
+		let pivot = self.corner.pivot();
+		// Offsets point away from the edges the window sticks to
+		let direction = |pivot: f32| if pivot < 0.5 { 1. } else if pivot > 0.5 { -1. } else { 0. };
+		let pos = ImVec2::new(
+			left + width * pivot.0 + self.offset.0 * direction(pivot.0),
+			top + height * pivot.1 + self.offset.1 * direction(pivot.1),
+		);
+		unsafe { sys::igSetNextWindowPos(pos, ImGuiCond::Always, ImVec2::new(pivot.0, pivot.1)) };
+		if !self.stretch {
+			return;
+		}
+		let size = match self.corner {
+			Corner::Top | Corner::Bottom => Some(ImVec2::new((width - self.offset.0 * 2.).max(0.), 0.)),
+			Corner::Left | Corner::Right => Some(ImVec2::new(0., (height - self.offset.1 * 2.).max(0.))),
+			_ => None,
+		};
+		if let Some(size) = size {
+			// Zero keeps the other axis fitting the content
+			unsafe { sys::igSetNextWindowSize(size, ImGuiCond::Always) };
+		}
+	}
+
+	/// Positions the next window on the screen.
+	///
+	/// ```rust,ignore
+	/// Anchor::new(Corner::BottomRight, (10., 10.)).apply(ui);
+	/// ui.window(im_str!("FPS")).always_auto_resize(true).build(|| { /* ... */ });
+	/// ```
+	pub fn apply(&self, ui: &Ui) {
+		let (width, height) = ui.frame_size().logical_size;
+		self.apply_in(((0., 0.), (width as f32, height as f32)));
+	}
+}
+
+/// Pins the next window to `corner` of the screen, `offset` pixels away from its edges.
+pub fn anchor_next_window(ui: &Ui, corner: Corner, offset: (f32, f32)) { Anchor::new(corner, offset).apply(ui); }
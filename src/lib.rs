@@ -1,8 +1,33 @@
+#[macro_use]
 pub extern crate imgui;
 extern crate amethyst;
+extern crate backtrace;
 extern crate gfx;
 extern crate glsl_layout;
+extern crate image;
 extern crate imgui_gfx_renderer;
+#[cfg(feature = "memory-editor")]
+extern crate bincode;
+#[cfg(feature = "os-clipboard")]
+extern crate clipboard as os_clipboard;
+#[cfg(feature = "nfd")]
+extern crate nfd;
+#[cfg(feature = "scripting")]
+extern crate rhai;
+#[cfg(feature = "remote")]
+extern crate tungstenite;
+#[cfg(feature = "tracing")]
+extern crate tracing_core;
+#[cfg(feature = "tracing")]
+extern crate tracing_subscriber;
+extern crate ron;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+use std::time::Instant;
 
 use amethyst::{
 	ecs::shred::FetchMut,
@@ -24,12 +49,97 @@ use amethyst::{
 };
 use gfx::{memory::Typed, preset::blend, pso::buffer::ElemStride, state::ColorMask};
 use gfx::traits::Factory;
-use glsl_layout::{vec2, vec4, Uniform};
-use imgui::{FontGlyphRange, FrameSize, ImFontConfig, ImGui, ImVec4};
+use glsl_layout::{float, vec2, vec4, Uniform};
+use imgui::{FontGlyphRange, FrameSize, ImFontConfig, ImGui};
 use imgui_gfx_renderer::{Renderer as ImguiRenderer, Shaders};
 
+use crate::error::ImguiRenderError;
+
+#[cfg(feature = "alloc-tracker")]
+pub mod allocations;
+pub mod background;
+pub mod bundle;
+#[cfg(feature = "capture")]
+pub mod capture;
+pub mod cheats;
+pub mod clipboard;
+pub mod clipper;
+pub mod code_view;
+pub mod command_palette;
+pub mod config_editor;
+pub mod context_menus;
+pub mod crash;
+pub mod curves;
+pub mod debug_menus;
+pub mod dialogs;
+pub mod drag_drop;
+pub mod draw_data;
+pub mod error;
+pub mod events;
+pub mod file_dialog;
+pub mod file_drop;
+pub mod gpu_memory;
+pub mod grid;
+mod hit_shape;
+pub mod history;
+pub mod hot_reload;
+pub mod input;
+pub mod inspect;
+pub mod keyboard;
+pub mod layout;
+pub mod lights;
+pub mod loading;
+pub mod locale;
+pub mod main_menu;
+pub mod markdown;
+pub mod memory;
+#[cfg(feature = "memory-editor")]
+pub mod memory_editor;
+pub mod metrics;
+#[cfg(feature = "network")]
+pub mod network;
+pub mod particles;
+pub mod paths;
+#[cfg(feature = "physics")]
+pub mod physics;
+pub mod picking;
+pub mod plots;
+pub mod profiler;
+pub mod property_grid;
+pub mod recording;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod render_params;
+pub mod ruler;
+pub mod runtime;
+pub mod safe_area;
+pub mod save_inspector;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+mod screenshot;
+pub mod select;
+pub mod selection;
+pub mod snapping;
+pub mod sprite_preview;
+pub mod states;
+pub mod status_bar;
+pub mod tables;
+pub mod telemetry;
+pub mod testing;
+pub mod textures;
+pub mod theme;
+pub mod tool_windows;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+pub mod tuning;
+pub mod value_editor;
+pub mod viewport;
+pub mod watch;
+pub mod watchdog;
+
 const VERT_SRC: &[u8] = include_bytes!("shaders/vertex.glsl");
 const FRAG_SRC: &[u8] = include_bytes!("shaders/frag.glsl");
+const FRAG_SRGB_SRC: &[u8] = include_bytes!("shaders/frag_srgb.glsl");
 
 #[derive(Copy, Clone, Debug, Uniform)]
 #[allow(dead_code)] // This is used by the shaders
@@ -40,6 +150,53 @@ struct VertexArgs {
 	dimension: vec2,
 }
 
+/// Uniform block available to shaders set with `DrawUi::with_shaders`.
+#[derive(Copy, Clone, Debug, Uniform)]
+#[allow(dead_code)] // This is used by the shaders
+#[repr(C)]
+struct FrameArgs {
+	size: vec2,
+	time: float,
+	user: vec4,
+}
+
+/// Tint the built-in shaders multiply the overlay with.
+#[derive(Copy, Clone, Debug, Uniform)]
+#[allow(dead_code)] // This is used by the shaders
+#[repr(C)]
+struct OverlayArgs {
+	tint: vec4,
+}
+
+/// Compositing parameters read by the `DrawUi` passes every frame.
+#[derive(Copy, Clone, Debug)]
+pub struct OverlayUniforms {
+	/// Value passed as `user` in the `FrameArgs` block of custom overlay shaders.
+	pub user: [f32; 4],
+	/// Multiplies the whole overlay's alpha; only applied by the built-in shaders.
+	pub opacity: f32,
+}
+
+impl Default for OverlayUniforms {
+	fn default() -> Self {
+		Self {
+			user: [0.; 4],
+			opacity: 1.,
+		}
+	}
+}
+
+impl OverlayUniforms {
+	fn tint(&self, blend_mode: BlendMode) -> [f32; 4] {
+		let opacity = self.opacity.max(0.).min(1.);
+		match blend_mode {
+			// Color isn't scaled by alpha while blending, so scale it here
+			BlendMode::Premultiplied | BlendMode::Additive => [opacity; 4],
+			BlendMode::Alpha | BlendMode::None => [1., 1., 1., opacity],
+		}
+	}
+}
+
 struct RendererThing {
 	renderer: ImguiRenderer<Resources>,
 	texture: gfx::handle::Texture<Resources, gfx::format::R8_G8_B8_A8>,
@@ -47,141 +204,475 @@ struct RendererThing {
 	mesh: Mesh,
 }
 
+fn quad_mesh(factory: &mut amethyst::renderer::Factory) -> Result<Mesh> {
+	let data = vec![
+		PosTex {
+			position: Vector3::new(0., 1., 0.),
+			tex_coord: Vector2::new(0., 0.),
+		},
+		PosTex {
+			position: Vector3::new(1., 1., 0.),
+			tex_coord: Vector2::new(1., 0.),
+		},
+		PosTex {
+			position: Vector3::new(1., 0., 0.),
+			tex_coord: Vector2::new(1., 1.),
+		},
+		PosTex {
+			position: Vector3::new(0., 1., 0.),
+			tex_coord: Vector2::new(0., 0.),
+		},
+		PosTex {
+			position: Vector3::new(1., 0., 0.),
+			tex_coord: Vector2::new(1., 1.),
+		},
+		PosTex {
+			position: Vector3::new(0., 0., 0.),
+			tex_coord: Vector2::new(0., 1.),
+		},
+	];
+
+	Mesh::build(data).build(factory)
+}
+
+impl RendererThing {
+	fn new(
+		imgui: &mut ImGui,
+		factory: &mut amethyst::renderer::Factory,
+		size: (u16, u16),
+	) -> std::result::Result<Self, ImguiRenderError> {
+		let (texture, shader_resource_view, target) = factory.create_render_target::<FormattedT>(size.0, size.1)?;
+		let renderer = ImguiRenderer::init(imgui, factory, Shaders::GlSl130, target)?;
+		Ok(Self {
+			renderer,
+			texture,
+			shader_resource_view,
+			mesh: quad_mesh(factory).map_err(ImguiRenderError::Mesh)?,
+		})
+	}
+}
+
 #[derive(Default)]
 pub struct DrawUi {
 	imgui: Option<ImGui>,
 	renderer: Option<RendererThing>,
+	readback: Option<screenshot::Readback>,
+	mirror: Option<Option<Mesh>>,
+	shaders: Option<(&'static [u8], &'static [u8])>,
+	srgb_colors: bool,
+	blend_mode: BlendMode,
+	size: (f32, f32),
+}
+
+/// How the overlay is blended into the stage's target.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum BlendMode {
+	Alpha,
+	/// For targets holding premultiplied alpha.
+	Premultiplied,
+	Additive,
+	/// Overwrite the target, including the overlay's transparent areas.
+	None,
+}
+
+impl Default for BlendMode {
+	fn default() -> Self { BlendMode::Alpha }
+}
+
+impl BlendMode {
+	fn blend(self) -> gfx::state::Blend {
+		use gfx::state::{Blend, BlendChannel, BlendValue, Equation, Factor};
+		let channel = |source, destination| BlendChannel {
+			equation: Equation::Add,
+			source,
+			destination,
+		};
+		match self {
+			BlendMode::Alpha => blend::ALPHA,
+			BlendMode::Premultiplied => Blend {
+				color: channel(Factor::One, Factor::OneMinus(BlendValue::SourceAlpha)),
+				alpha: channel(Factor::One, Factor::OneMinus(BlendValue::SourceAlpha)),
+			},
+			BlendMode::Additive => blend::ADD,
+			BlendMode::None => Blend {
+				color: channel(Factor::One, Factor::Zero),
+				alpha: channel(Factor::One, Factor::Zero),
+			},
+		}
+	}
+}
+
+impl DrawUi {
+	/// A pass that composites the overlay rendered by the main `DrawUi` instead of owning a context,
+	/// for showing the same UI in another stage or render target.
+	///
+	/// It has to run after the main pass to show the current frame, otherwise it lags one frame behind.
+	pub fn mirror() -> Self {
+		Self {
+			mirror: Some(None),
+			..Self::default()
+		}
+	}
+
+	/// Composites the overlay with custom GLSL 150 shaders, e.g. to animate or distort it.
+	///
+	/// They get the same inputs as the built-in `src/shaders` and additionally have to declare
+	/// `layout (std140) uniform FrameArgs { vec2 size; float time; vec4 user; };`
+	/// with the framebuffer size, the absolute time in seconds and `OverlayUniforms::user`.
+	pub fn with_shaders(mut self, vertex: &'static [u8], fragment: &'static [u8]) -> Self {
+		self.shaders = Some((vertex, fragment));
+		self
+	}
+
+	/// Keeps imgui's colors sRGB-encoded, as they're authored, and converts the whole overlay to linear while
+	/// compositing it. Colors then match other imgui apps, including ones set at runtime, and blending happens
+	/// in sRGB like in imgui itself. By default only the style is converted to linear once at startup.
+	///
+	/// Set it on mirror passes as well. Registered textures should be `unorm` in this mode.
+	pub fn with_srgb_colors(mut self, srgb_colors: bool) -> Self {
+		self.srgb_colors = srgb_colors;
+		self
+	}
+
+	pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+		self.blend_mode = blend_mode;
+		self
+	}
+
+	/// Size of the overlay when there's no `ScreenDimensions` resource, e.g. when rendering offscreen only.
+	pub fn with_size(mut self, width: f32, height: f32) -> Self {
+		self.size = (width, height);
+		self
+	}
+}
+
+/// The overlay texture the main `DrawUi` rendered this frame, composited by mirror passes.
+#[derive(Default)]
+pub struct ImguiOverlay {
+	view: Option<gfx::handle::ShaderResourceView<Resources, [f32; 4]>>,
+}
+
+fn composite(
+	encoder: &mut Encoder,
+	effect: &mut Effect,
+	factory: &mut amethyst::renderer::Factory,
+	view: &gfx::handle::ShaderResourceView<Resources, [f32; 4]>,
+	mesh: &Mesh,
+	(width, height): (f32, f32),
+	frame_args: Option<FrameArgs>,
+	overlay_args: Option<OverlayArgs>,
+) {
+	let vertex_args = VertexArgs {
+		proj_vec: [2. / width, -2. / height, 0., 1.].into(),
+		coord: [0., 0.].into(),
+		dimension: [width, height].into(),
+	};
+
+	{
+		use gfx::texture::{FilterMethod, SamplerInfo, WrapMode};
+		let sampler = factory.create_sampler(SamplerInfo::new(FilterMethod::Trilinear, WrapMode::Clamp));
+		effect.data.samplers.push(sampler);
+	}
+
+	effect.update_constant_buffer("VertexArgs", &vertex_args.std140(), encoder);
+	if let Some(frame_args) = frame_args {
+		effect.update_constant_buffer("FrameArgs", &frame_args.std140(), encoder);
+	}
+	if let Some(overlay_args) = overlay_args {
+		effect.update_constant_buffer("OverlayArgs", &overlay_args.std140(), encoder);
+	}
+	effect.data.textures.push(view.raw().clone());
+	effect
+		.data
+		.vertex_bufs
+		.push(mesh.buffer(PosTex::ATTRIBUTES).unwrap().clone());
+
+	effect.draw(mesh.slice(), encoder);
+
+	effect.data.textures.clear();
+	effect.data.samplers.clear();
 }
 
 pub struct ImguiState {
 	imgui: ImGui,
 	mouse_state: MouseState,
 	size: (u16, u16),
+	capture_request: Option<std::path::PathBuf>,
+	suspended: bool,
+	window_id: Option<amethyst::winit::WindowId>,
+	focused: bool,
+	click_through: bool,
+	shaped_windows: Vec<String>,
+	hit_shapes: Vec<hit_shape::HitShape>,
+	hovered_files: Vec<std::path::PathBuf>,
+	dropped_files: Vec<std::path::PathBuf>,
+	delta_time: DeltaTime,
+	unfocused_rate: Option<f32>,
+	throttled: bool,
+	skipped_seconds: f32,
+	last_frame: f64,
+	clipboard: Option<Box<clipboard::ClipboardContext>>,
+	linear_style: bool,
+	config: theme::ImguiConfig,
+	/// Shared with the threads that save captures.
+	render_error: std::sync::Arc<std::sync::Mutex<Option<ImguiRenderError>>>,
+	/// Show imgui's demo window, managed by `open_frame`.
+	pub show_demo_window: bool,
+	/// Show imgui's metrics window, managed by `open_frame`.
+	pub show_metrics_window: bool,
 }
 
-type FormattedT = (gfx::format::R8_G8_B8_A8, gfx::format::Unorm);
+/// Where `open_frame` takes the time step for imgui's animations and double-click detection from.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DeltaTime {
+	/// `Time::delta_seconds`, which follows the game's time scale and stops while it's paused.
+	Scaled,
+	/// `Time::delta_real_seconds`, keeps the UI animating when the game is paused or slowed down.
+	Real,
+	/// The same step every frame, in seconds.
+	Fixed(f32),
+}
 
-impl<'a> PassData<'a> for DrawUi {
-	type Data = (
-		ReadExpect<'a, amethyst::renderer::ScreenDimensions>,
-		Write<'a, Option<ImguiState>>,
-	);
+impl Default for DeltaTime {
+	fn default() -> Self { DeltaTime::Scaled }
 }
 
-impl Pass for DrawUi {
-	fn compile(&mut self, mut effect: NewEffect<'_>) -> Result<Effect> {
-		let mut imgui = ImGui::init();
+impl ImguiState {
+	pub(crate) fn new(imgui: ImGui, size: (u16, u16), linear_style: bool) -> Self {
+		Self {
+			imgui,
+			mouse_state: MouseState::default(),
+			size,
+			capture_request: None,
+			suspended: false,
+			window_id: None,
+			focused: true,
+			click_through: false,
+			shaped_windows: Vec::new(),
+			hit_shapes: Vec::new(),
+			hovered_files: Vec::new(),
+			dropped_files: Vec::new(),
+			delta_time: DeltaTime::default(),
+			unfocused_rate: None,
+			throttled: false,
+			skipped_seconds: 0.,
+			last_frame: 0.,
+			clipboard: None,
+			linear_style,
+			config: theme::ImguiConfig::default(),
+			render_error: Default::default(),
+			show_demo_window: false,
+			show_metrics_window: false,
+		}
+	}
+
+	/// `new` with the platform integrations every entry point installs: the OS clipboard with `os-clipboard`.
+	pub(crate) fn with_platform(imgui: ImGui, size: (u16, u16), linear_style: bool) -> Self {
+		let mut imgui_state = Self::new(imgui, size, linear_style);
+		#[cfg(feature = "os-clipboard")]
 		{
-			// Fix incorrect colors with sRGB framebuffer
-			fn imgui_gamma_to_linear(col: ImVec4) -> ImVec4 {
-				let x = col.x.powf(2.2);
-				let y = col.y.powf(2.2);
-				let z = col.z.powf(2.2);
-				let w = 1.0 - (1.0 - col.w).powf(2.2);
-				ImVec4::new(x, y, z, w)
+			if let Some(x) = clipboard::OsClipboard::new() {
+				imgui_state.set_clipboard_backend(x);
 			}
+		}
+		imgui_state
+	}
 
-			let style = imgui.style_mut();
-			for col in 0..style.colors.len() {
-				style.colors[col] = imgui_gamma_to_linear(style.colors[col]);
-			}
+	/// Routes imgui's copy and paste through `backend`.
+	pub fn set_clipboard_backend<B: clipboard::ClipboardBackend + 'static>(&mut self, backend: B) {
+		let mut context = Box::new(clipboard::ClipboardContext::new(backend));
+		unsafe { clipboard::install(&mut context) };
+		self.clipboard = Some(context);
+	}
+
+	/// Replaces the style's colors with `theme`'s, then daltonizes them for `remap` if it's set, for players
+	/// with a color vision deficiency using a theme that isn't designed for it. An `ImguiConfig` resource
+	/// overrides it.
+	pub fn set_theme(&mut self, theme: theme::Theme, remap: Option<theme::ColorBlindness>) {
+		let config = theme::ImguiConfig { theme, remap, ..self.config.clone() };
+		self.set_config(&config);
+	}
+
+	/// Applies a theme and accessibility options; `open_frame` calls it when the `ImguiConfig` resource changes.
+	pub fn set_config(&mut self, config: &theme::ImguiConfig) {
+		let linear = self.linear_style;
+		theme::apply(self.imgui.style_mut(), config, linear);
+		unsafe { (*imgui::sys::igGetIO()).config_input_text_cursor_blink = !config.reduced_motion };
+		self.config = config.clone();
+	}
+
+	pub fn config(&self) -> &theme::ImguiConfig { &self.config }
+
+	/// The last failure to render the overlay or save a capture, e.g. to show in a status window; `DrawUi` skips
+	/// the frame and carries on.
+	pub fn take_render_error(&mut self) -> Option<ImguiRenderError> {
+		self.render_error.lock().unwrap_or_else(|e| e.into_inner()).take()
+	}
+
+	fn report(&self, error: ImguiRenderError) {
+		*self.render_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(error);
+	}
+
+	/// Saves the overlay layer of the next rendered frame to a PNG at `path`.
+	///
+	/// The copy is read back and encoded asynchronously, so the file shows up a frame or two later.
+	pub fn capture_next_frame<P: Into<std::path::PathBuf>>(&mut self, path: P) {
+		self.capture_request = Some(path.into());
+	}
+
+	/// Only react to events from window `id`; by default events from every window are handled.
+	pub fn set_window(&mut self, id: Option<amethyst::winit::WindowId>) { self.window_id = id; }
+
+	pub fn window(&self) -> Option<amethyst::winit::WindowId> { self.window_id }
+
+	/// Whether the overlay's window currently has keyboard focus.
+	pub fn is_focused(&self) -> bool { self.focused }
+
+	/// Keeps the mouse away from imgui while set: the overlay is still drawn, but nothing in it can be hovered or
+	/// clicked.
+	pub fn set_click_through(&mut self, click_through: bool) {
+		self.click_through = click_through;
+		self.sync_mouse();
+	}
+
+	pub fn is_click_through(&self) -> bool { self.click_through }
+
+	/// Lets the mouse through where the window called `name` didn't draw anything, for custom-shaped HUD panels
+	/// drawn with draw lists. The shape is point-tested against the previous frame's draw data, so overlapping
+	/// windows lose the mouse over the transparent parts of a shaped one too.
+	pub fn set_shaped(&mut self, name: &str, shaped: bool) {
+		self.shaped_windows.retain(|x| x != name);
+		if shaped {
+			self.shaped_windows.push(name.to_owned());
 		}
-		imgui.set_ini_filename(None);
-
-		let font_size = 13.;
-
-		let _ = imgui.fonts().add_font_with_config(
-			include_bytes!("../mplus-1p-regular.ttf"),
-			ImFontConfig::new()
-				.oversample_h(1)
-				.pixel_snap_h(true)
-				.size_pixels(font_size)
-				.rasterizer_multiply(1.75),
-			&FontGlyphRange::japanese(),
-		);
+	}
 
-		let _ = imgui.fonts().add_default_font_with_config(
-			ImFontConfig::new()
-				.merge_mode(true)
-				.oversample_h(1)
-				.pixel_snap_h(true)
-				.size_pixels(font_size),
-		);
+	pub fn is_shaped(&self, name: &str) -> bool { self.shaped_windows.iter().any(|x| x == name) }
 
-		{
-			macro_rules! set_keys {
-				($($key:ident => $id:expr),+$(,)*) => {
-					$(imgui.set_imgui_key(imgui::ImGuiKey::$key, $id);)+
-				};
-			}
+	pub fn set_delta_time(&mut self, delta_time: DeltaTime) { self.delta_time = delta_time; }
+
+	/// Only build frames `rate` times per second while the window is unfocused; `open_frame` returns `None` in
+	/// between and the last frame stays on screen. Saves power in long-running editor sessions.
+	pub fn set_unfocused_rate(&mut self, rate: Option<f32>) { self.unfocused_rate = rate; }
+
+	/// Whether imgui uses the mouse this frame, so the game should ignore it.
+	pub fn wants_mouse(&self) -> bool { !self.click_through && self.imgui.want_capture_mouse() }
+}
+
+/// Window flags for a read-only window that lets every click through to the game, even with click-through off.
+pub fn click_through_flags() -> imgui::ImGuiWindowFlags {
+	use imgui::ImGuiWindowFlags;
+	ImGuiWindowFlags::NoInputs | ImGuiWindowFlags::NoFocusOnAppearing | ImGuiWindowFlags::NoBringToFrontOnFocus
+}
 
-			set_keys![
-				Tab => 0,
-				LeftArrow => 1,
-				RightArrow => 2,
-				UpArrow => 3,
-				DownArrow => 4,
-				PageUp => 5,
-				PageDown => 6,
-				Home => 7,
-				End => 8,
-				Delete => 9,
-				Backspace => 10,
-				Enter => 11,
-				Escape => 12,
-				A => 13,
-				C => 14,
-				V => 15,
-				X => 16,
-				Y => 17,
-				Z => 18,
-			];
+/// Creates an imgui context configured the way `DrawUi` renders it: fonts, key map and sRGB-corrected style.
+pub(crate) fn create_imgui(linear_style: bool) -> ImGui {
+	let mut imgui = ImGui::init();
+	if linear_style {
+		// Fix incorrect colors with sRGB framebuffer
+		let style = imgui.style_mut();
+		for col in 0..style.colors.len() {
+			style.colors[col] = theme::gamma_to_linear(style.colors[col]);
 		}
+	}
+	imgui.set_ini_filename(None);
 
-		let data = vec![
-			PosTex {
-				position: Vector3::new(0., 1., 0.),
-				tex_coord: Vector2::new(0., 0.),
-			},
-			PosTex {
-				position: Vector3::new(1., 1., 0.),
-				tex_coord: Vector2::new(1., 0.),
-			},
-			PosTex {
-				position: Vector3::new(1., 0., 0.),
-				tex_coord: Vector2::new(1., 1.),
-			},
-			PosTex {
-				position: Vector3::new(0., 1., 0.),
-				tex_coord: Vector2::new(0., 0.),
-			},
-			PosTex {
-				position: Vector3::new(1., 0., 0.),
-				tex_coord: Vector2::new(1., 1.),
-			},
-			PosTex {
-				position: Vector3::new(0., 0., 0.),
-				tex_coord: Vector2::new(0., 1.),
-			},
+	let font_size = 13.;
+
+	let _ = imgui.fonts().add_font_with_config(
+		include_bytes!("../mplus-1p-regular.ttf"),
+		ImFontConfig::new()
+			.oversample_h(1)
+			.pixel_snap_h(true)
+			.size_pixels(font_size)
+			.rasterizer_multiply(1.75),
+		&FontGlyphRange::japanese(),
+	);
+
+	let _ = imgui.fonts().add_default_font_with_config(
+		ImFontConfig::new()
+			.merge_mode(true)
+			.oversample_h(1)
+			.pixel_snap_h(true)
+			.size_pixels(font_size),
+	);
+
+	{
+		macro_rules! set_keys {
+			($($key:ident => $id:expr),+$(,)*) => {
+				$(imgui.set_imgui_key(imgui::ImGuiKey::$key, $id);)+
+			};
+		}
+
+		set_keys![
+			Tab => 0,
+			LeftArrow => 1,
+			RightArrow => 2,
+			UpArrow => 3,
+			DownArrow => 4,
+			PageUp => 5,
+			PageDown => 6,
+			Home => 7,
+			End => 8,
+			Delete => 9,
+			Backspace => 10,
+			Enter => 11,
+			Escape => 12,
+			A => 13,
+			C => 14,
+			V => 15,
+			X => 16,
+			Y => 17,
+			Z => 18,
 		];
+	}
 
-		let (texture, shader_resource_view, target) = effect.factory.create_render_target::<FormattedT>(1024, 1024).unwrap();
-		let renderer = ImguiRenderer::init(&mut imgui, effect.factory, Shaders::GlSl130, target).unwrap();
-		self.renderer = Some(RendererThing {
-			renderer,
-			texture,
-			shader_resource_view,
-			mesh: Mesh::build(data).build(&mut effect.factory)?,
-		});
-		self.imgui = Some(imgui);
+	imgui
+}
+
+type FormattedT = (gfx::format::R8_G8_B8_A8, gfx::format::Unorm);
+
+impl<'a> PassData<'a> for DrawUi {
+	type Data = (
+		Option<Read<'a, amethyst::renderer::ScreenDimensions>>,
+		Write<'a, Option<ImguiState>>,
+		Write<'a, draw_data::DrawDataCapture>,
+		Write<'a, ImguiOverlay>,
+		Read<'a, amethyst::assets::AssetStorage<amethyst::renderer::Texture>>,
+		Write<'a, textures::ImguiTextures>,
+		Write<'a, metrics::ImguiMetrics>,
+		Write<'a, metrics::ImguiIoMetrics>,
+		Write<'a, gpu_memory::ImguiGpuMemory>,
+		ReadExpect<'a, amethyst::core::timing::Time>,
+		Read<'a, OverlayUniforms>,
+	);
+}
+
+impl Pass for DrawUi {
+	fn compile(&mut self, mut effect: NewEffect<'_>) -> Result<Effect> {
+		if let Some(ref mut mesh) = self.mirror {
+			*mesh = Some(quad_mesh(effect.factory)?);
+		} else {
+			let mut imgui = create_imgui(!self.srgb_colors);
+
+			self.renderer = Some(RendererThing::new(&mut imgui, effect.factory, (1024, 1024))?);
+			self.imgui = Some(imgui);
+		}
 
-		effect
-			.simple(VERT_SRC, FRAG_SRC)
+		let default_shaders = (VERT_SRC, if self.srgb_colors { FRAG_SRGB_SRC } else { FRAG_SRC });
+		let (vertex, fragment) = self.shaders.unwrap_or(default_shaders);
+		let mut builder = effect.simple(vertex, fragment);
+		builder
 			.with_raw_constant_buffer("VertexArgs", std::mem::size_of::<<VertexArgs as Uniform>::Std140>(), 1)
 			.with_raw_vertex_buffer(PosTex::ATTRIBUTES, PosTex::size() as ElemStride, 0)
 			.with_texture("albedo")
-			.with_blended_output("color", ColorMask::all(), blend::ALPHA, None)
-			.build()
+			.with_blended_output("color", ColorMask::all(), self.blend_mode.blend(), None);
+		if self.shaders.is_some() {
+			builder.with_raw_constant_buffer("FrameArgs", std::mem::size_of::<<FrameArgs as Uniform>::Std140>(), 1);
+		} else {
+			builder.with_raw_constant_buffer("OverlayArgs", std::mem::size_of::<<OverlayArgs as Uniform>::Std140>(), 1);
+		}
+		builder.build()
 	}
 
 	fn apply<'ui, 'apply_pd: 'ui>(
@@ -189,61 +680,148 @@ impl Pass for DrawUi {
 		encoder: &mut Encoder,
 		effect: &mut Effect,
 		mut factory: amethyst::renderer::Factory,
-		(screen_dimensions, mut imgui_state): <Self as PassData<'apply_pd>>::Data,
+		(
+			screen_dimensions,
+			mut imgui_state,
+			mut capture,
+			mut overlay,
+			texture_storage,
+			mut textures,
+			mut metrics,
+			mut io_metrics,
+			mut gpu_memory,
+			time,
+			uniforms,
+		): <Self as PassData<'apply_pd>>::Data,
 	) {
-		let imgui_state = imgui_state.get_or_insert_with(|| ImguiState {
-			imgui: self.imgui.take().unwrap(),
-			mouse_state: MouseState::default(),
-			size: (1024, 1024),
+		let (width, height) = match screen_dimensions {
+			Some(ref x) => (x.width(), x.height()),
+			None => self.size,
+		};
+		let frame_args = self.shaders.map(|_| FrameArgs {
+			size: [width, height].into(),
+			time: time.absolute_time_seconds() as f32,
+			user: uniforms.user.into(),
 		});
-		let (width, height) = (screen_dimensions.width(), screen_dimensions.height());
+		let overlay_args = match self.shaders {
+			Some(_) => None,
+			None => Some(OverlayArgs {
+				tint: uniforms.tint(self.blend_mode).into(),
+			}),
+		};
+		if let Some(ref mut mesh) = self.mirror {
+			if imgui_state.as_ref().map_or(false, |x| x.suspended) {
+				*mesh = None;
+				return;
+			}
+			if mesh.is_none() {
+				*mesh = quad_mesh(&mut factory).ok();
+			}
+			if let (Some(ref mesh), Some(ref view)) = (mesh.as_ref(), overlay.view.as_ref()) {
+				if width > 0. && height > 0. {
+					composite(encoder, effect, &mut factory, view, mesh, (width, height), frame_args, overlay_args);
+				}
+			}
+			return;
+		}
+
+		let (imgui, linear) = (&mut self.imgui, !self.srgb_colors);
+		let imgui_state =
+			imgui_state.get_or_insert_with(|| ImguiState::with_platform(imgui.take().unwrap(), (1024, 1024), linear));
+		if imgui_state.suspended {
+			// The GL context may be gone while suspended, drop everything that lives on the GPU
+			self.renderer = None;
+			self.readback = None;
+			overlay.view = None;
+			textures.reset();
+			gpu_memory.reset();
+			return;
+		}
+		if let Some(readback) = self.readback.take() {
+			screenshot::finish(&mut factory, readback, imgui_state.render_error.clone());
+		}
 		if width <= 0. || height <= 0. { return; }
+		if self.renderer.is_none() {
+			// Resumed: re-create the render target and re-upload the font atlas
+			let size = (width as u16, height as u16);
+			match RendererThing::new(&mut imgui_state.imgui, &mut factory, size) {
+				Ok(x) => self.renderer = Some(x),
+				Err(e) => {
+					imgui_state.report(e);
+					return;
+				},
+			}
+			imgui_state.size = size;
+			textures.reset();
+		}
 		let renderer_thing = self.renderer.as_mut().unwrap();
+		textures.sync(&mut renderer_thing.renderer, &texture_storage, &mut factory, encoder);
 
-		let vertex_args = VertexArgs {
-			proj_vec: [2. / width, -2. / height, 0., 1.].into(),
-			coord: [0., 0.].into(),
-			dimension: [width, height].into(),
-		};
-
-		if imgui_state.size.0 != width as u16 || imgui_state.size.1 != height as u16 {
-			let (texture, shader_resource_view, target) = factory.create_render_target::<FormattedT>(width as u16, height as u16).unwrap();
+		let resized = imgui_state.size.0 != width as u16 || imgui_state.size.1 != height as u16;
+		if resized {
+			let created = factory.create_render_target::<FormattedT>(width as u16, height as u16);
+			let (texture, shader_resource_view, target) = match created {
+				Ok(x) => x,
+				Err(e) => {
+					imgui_state.report(e.into());
+					return;
+				},
+			};
 			renderer_thing.renderer.update_render_target(target);
 			renderer_thing.shader_resource_view = shader_resource_view;
 			renderer_thing.texture = texture;
+			imgui_state.size = (width as u16, height as u16);
 		}
 
-		encoder.clear(
-			&factory
-				.view_texture_as_render_target::<FormattedT>(&renderer_thing.texture, 0, None)
-				.unwrap(),
-			[0., 0., 0., 0.],
-		);
+		// Keep showing the last frame while building frames is throttled
+		let building = unsafe { imgui::Ui::current_ui() }.is_some();
+		if building || !imgui_state.throttled || resized {
+			match factory.view_texture_as_render_target::<FormattedT>(&renderer_thing.texture, 0, None) {
+				Ok(target) => encoder.clear(&target, [0., 0., 0., 0.]),
+				Err(e) => {
+					imgui_state.report(e.into());
+					return;
+				},
+			}
+		}
 
 		unsafe {
 			if let Some(ui) = imgui::Ui::current_ui() {
 				let ui = ui as *const imgui::Ui;
-				renderer_thing.renderer.render(ui.read(), &mut factory, encoder).unwrap();
+				let start = Instant::now();
+				if let Err(e) = renderer_thing.renderer.render(ui.read(), &mut factory, encoder) {
+					imgui_state.report(e.into());
+				}
+				metrics.prepare_time = start.elapsed();
+				metrics.count();
+				io_metrics.update();
+				if capture.enabled {
+					capture.data = draw_data::capture();
+				}
 			}
 		}
 
-		{
-			use gfx::texture::{FilterMethod, SamplerInfo, WrapMode};
-			let sampler = factory.create_sampler(SamplerInfo::new(FilterMethod::Trilinear, WrapMode::Clamp));
-			effect.data.samplers.push(sampler);
+		if let Some(path) = imgui_state.capture_request.take() {
+			match screenshot::queue(&mut factory, encoder, &renderer_thing.texture, path, !self.srgb_colors) {
+				Ok(readback) => self.readback = Some(readback),
+				Err(e) => imgui_state.report(e),
+			}
 		}
 
-		effect.update_constant_buffer("VertexArgs", &vertex_args.std140(), encoder);
-		effect.data.textures.push(renderer_thing.shader_resource_view.raw().clone());
-		effect
-			.data
-			.vertex_bufs
-			.push(renderer_thing.mesh.buffer(PosTex::ATTRIBUTES).unwrap().clone());
-
-		effect.draw(renderer_thing.mesh.slice(), encoder);
-
-		effect.data.textures.clear();
-		effect.data.samplers.clear();
+		unsafe { gpu_memory.update(imgui_state.size, &textures, self.readback.is_some()) };
+		overlay.view = Some(renderer_thing.shader_resource_view.clone());
+		let start = Instant::now();
+		composite(
+			encoder,
+			effect,
+			&mut factory,
+			&renderer_thing.shader_resource_view,
+			&renderer_thing.mesh,
+			(width, height),
+			frame_args,
+			overlay_args,
+		);
+		metrics.draw_time = start.elapsed();
 	}
 }
 
@@ -254,32 +832,243 @@ struct MouseState {
 	wheel: f32,
 }
 
+/// Index of P in imgui's key state, for the command palette's Ctrl+P; imgui has no key map entry for it.
+pub(crate) const KEY_P: u8 = 19;
+
+/// Input as imgui sees it, decoupled from winit so it can be recorded, replayed or sent over the wire.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ImguiInput {
+	/// Key index as registered with `set_imgui_key`, and whether it's pressed.
+	Key(u8, bool),
+	Ctrl(bool),
+	Shift(bool),
+	Alt(bool),
+	Super(bool),
+	CursorMoved(i32, i32),
+	/// 0 = left, 1 = right, 2 = middle.
+	MouseButton(u8, bool),
+	MouseWheel(f32),
+	Character(char),
+	/// The window lost focus or the cursor left it: release every key and button and move the mouse off-screen.
+	ReleaseAll,
+}
+
+impl ImguiInput {
+	pub fn from_event(event: &amethyst::renderer::Event) -> Option<Self> {
+		use amethyst::{
+			renderer::{
+				ElementState,
+				Event,
+				MouseButton,
+				VirtualKeyCode as VK,
+				WindowEvent::{self, ReceivedCharacter},
+			},
+			winit::{MouseScrollDelta, TouchPhase},
+		};
+
+		let event = match event {
+			Event::WindowEvent { event, .. } => event,
+			_ => return None,
+		};
+		Some(match event {
+			WindowEvent::KeyboardInput { input, .. } => {
+				let pressed = input.state == ElementState::Pressed;
+				match input.virtual_keycode {
+					Some(VK::Tab) => ImguiInput::Key(0, pressed),
+					Some(VK::Left) => ImguiInput::Key(1, pressed),
+					Some(VK::Right) => ImguiInput::Key(2, pressed),
+					Some(VK::Up) => ImguiInput::Key(3, pressed),
+					Some(VK::Down) => ImguiInput::Key(4, pressed),
+					Some(VK::PageUp) => ImguiInput::Key(5, pressed),
+					Some(VK::PageDown) => ImguiInput::Key(6, pressed),
+					Some(VK::Home) => ImguiInput::Key(7, pressed),
+					Some(VK::End) => ImguiInput::Key(8, pressed),
+					Some(VK::Delete) => ImguiInput::Key(9, pressed),
+					Some(VK::Back) => ImguiInput::Key(10, pressed),
+					Some(VK::Return) => ImguiInput::Key(11, pressed),
+					Some(VK::Escape) => ImguiInput::Key(12, pressed),
+					Some(VK::A) => ImguiInput::Key(13, pressed),
+					Some(VK::C) => ImguiInput::Key(14, pressed),
+					Some(VK::V) => ImguiInput::Key(15, pressed),
+					Some(VK::X) => ImguiInput::Key(16, pressed),
+					Some(VK::Y) => ImguiInput::Key(17, pressed),
+					Some(VK::Z) => ImguiInput::Key(18, pressed),
+					Some(VK::P) => ImguiInput::Key(KEY_P, pressed),
+					Some(VK::LControl) | Some(VK::RControl) => ImguiInput::Ctrl(pressed),
+					Some(VK::LShift) | Some(VK::RShift) => ImguiInput::Shift(pressed),
+					Some(VK::LAlt) | Some(VK::RAlt) => ImguiInput::Alt(pressed),
+					Some(VK::LWin) | Some(VK::RWin) => ImguiInput::Super(pressed),
+					_ => return None,
+				}
+			},
+			WindowEvent::CursorMoved { position: pos, .. } => ImguiInput::CursorMoved(pos.x as i32, pos.y as i32),
+			WindowEvent::MouseInput { state, button, .. } => {
+				let pressed = *state == ElementState::Pressed;
+				match button {
+					MouseButton::Left => ImguiInput::MouseButton(0, pressed),
+					MouseButton::Right => ImguiInput::MouseButton(1, pressed),
+					MouseButton::Middle => ImguiInput::MouseButton(2, pressed),
+					_ => return None,
+				}
+			},
+			WindowEvent::MouseWheel { delta, phase: TouchPhase::Moved, .. } => match delta {
+				MouseScrollDelta::LineDelta(_, y) => ImguiInput::MouseWheel(*y),
+				MouseScrollDelta::PixelDelta(lp) => ImguiInput::MouseWheel(lp.y as f32),
+			},
+			ReceivedCharacter(c) => ImguiInput::Character(*c),
+			WindowEvent::Focused(false) | WindowEvent::CursorLeft { .. } => ImguiInput::ReleaseAll,
+			_ => return None,
+		})
+	}
+}
+
+impl ImguiState {
+	/// Feeds a single input to imgui, as `handle_imgui_events` does for window events.
+	pub fn apply_input(&mut self, input: ImguiInput) {
+		let imgui = &mut self.imgui;
+		let mouse_state = &mut self.mouse_state;
+
+		match input {
+			ImguiInput::Key(key, pressed) => imgui.set_key(key, pressed),
+			ImguiInput::Ctrl(pressed) => imgui.set_key_ctrl(pressed),
+			ImguiInput::Shift(pressed) => imgui.set_key_shift(pressed),
+			ImguiInput::Alt(pressed) => imgui.set_key_alt(pressed),
+			ImguiInput::Super(pressed) => imgui.set_key_super(pressed),
+			ImguiInput::CursorMoved(x, y) => mouse_state.pos = (x, y),
+			ImguiInput::MouseButton(0, pressed) => mouse_state.pressed.0 = pressed,
+			ImguiInput::MouseButton(1, pressed) => mouse_state.pressed.1 = pressed,
+			ImguiInput::MouseButton(2, pressed) => mouse_state.pressed.2 = pressed,
+			ImguiInput::MouseButton(..) => {},
+			ImguiInput::MouseWheel(wheel) => mouse_state.wheel = wheel,
+			ImguiInput::Character(c) => imgui.add_input_character(c),
+			ImguiInput::ReleaseAll => {
+				for key in 0..=KEY_P {
+					imgui.set_key(key, false);
+				}
+				imgui.set_key_ctrl(false);
+				imgui.set_key_shift(false);
+				imgui.set_key_alt(false);
+				imgui.set_key_super(false);
+				// Far outside the window, so nothing stays hovered
+				mouse_state.pos = (std::i32::MIN, std::i32::MIN);
+				mouse_state.pressed = (false, false, false);
+			},
+		}
+
+		self.sync_mouse();
+	}
+
+	fn sync_mouse(&mut self) {
+		let wheel = if self.sync_mouse_position() { 0. } else { self.mouse_state.wheel };
+		self.imgui.set_mouse_wheel(wheel);
+		self.mouse_state.wheel = 0.0;
+	}
+
+	/// Feeds the cursor and buttons to imgui, or hides them while the mouse is let through; returns whether it is.
+	fn sync_mouse_position(&mut self) -> bool {
+		let imgui = &mut self.imgui;
+		let mouse_state = &self.mouse_state;
+
+		let pos = (mouse_state.pos.0 as f32, mouse_state.pos.1 as f32);
+		let pressed = mouse_state.pressed.0 || mouse_state.pressed.1 || mouse_state.pressed.2;
+		// Keep drags that started on the UI going over transparent parts
+		let dragging = pressed && imgui.want_capture_mouse();
+		if self.click_through || (!dragging && hit_shape::is_transparent(&self.hit_shapes, pos)) {
+			imgui.set_mouse_pos(-std::f32::MAX, -std::f32::MAX);
+			imgui.set_mouse_down([false; 5]);
+			return true;
+		}
+		imgui.set_mouse_pos(pos.0, pos.1);
+		imgui.set_mouse_down([mouse_state.pressed.0, mouse_state.pressed.1, mouse_state.pressed.2, false, false]);
+		false
+	}
+}
+
 type Data<'system_data> = (
-	ReadExpect<'system_data, amethyst::renderer::ScreenDimensions>,
+	Option<Read<'system_data, amethyst::renderer::ScreenDimensions>>,
 	ReadExpect<'system_data, amethyst::core::timing::Time>,
 	Write<'system_data, Option<ImguiState>>,
 );
 
+/// imgui's demo and metrics windows, while `ImguiState::show_demo_window`/`show_metrics_window` are set.
+pub(crate) fn builtin_windows(ui: &imgui::Ui, show_demo_window: &mut bool, show_metrics_window: &mut bool) {
+	if *show_demo_window {
+		ui.show_demo_window(show_demo_window);
+	}
+	if *show_metrics_window {
+		ui.show_metrics_window(show_metrics_window);
+	}
+}
+
 pub fn open_frame<'ui>(world: &amethyst::ecs::World) -> Option<&imgui::Ui<'ui>> {
 	let resources = std::borrow::Borrow::<amethyst::ecs::Resources>::borrow(world);
 	let (dimensions, time, mut imgui_state) = Data::fetch(resources);
+	let mut recorder = resources.try_fetch_mut::<recording::InputRecorder>();
+	let config = resources.try_fetch::<theme::ImguiConfig>();
 
-	let dimensions: &amethyst::renderer::ScreenDimensions = &dimensions;
 	let time: &amethyst::core::timing::Time = &time;
 	let imgui_state: &mut Option<ImguiState> = &mut imgui_state;
 
-	if dimensions.width() <= 0. || dimensions.height() <= 0. {
+	let imgui_state = match imgui_state {
+		Some(x) => x,
+		_ => return None,
+	};
+	if imgui_state.suspended {
 		return None;
 	}
+	if let Some(config) = config {
+		if *config != imgui_state.config {
+			imgui_state.set_config(&config);
+		}
+	}
 
-	let imgui = match imgui_state {
-		Some(x) => &mut x.imgui,
-		_ => return None,
+	// Without a window, use the size the `DrawUi` pass renders at
+	let (width, height) = match dimensions {
+		Some(ref x) => (x.width(), x.height()),
+		None => (f32::from(imgui_state.size.0), f32::from(imgui_state.size.1)),
 	};
+	if width <= 0. || height <= 0. {
+		return None;
+	}
 
-	let frame = imgui.frame(FrameSize::new(f64::from(dimensions.width()), f64::from(dimensions.height()), 1.), time.delta_seconds());
+	let mut delta_seconds = match imgui_state.delta_time {
+		DeltaTime::Scaled => time.delta_seconds(),
+		DeltaTime::Real => time.delta_real_seconds(),
+		DeltaTime::Fixed(x) => x,
+	};
+
+	let now = time.absolute_real_time_seconds();
+	imgui_state.throttled = match imgui_state.unfocused_rate {
+		Some(rate) if !imgui_state.focused && rate > 0. => now - imgui_state.last_frame < 1. / f64::from(rate),
+		_ => false,
+	};
+	if imgui_state.throttled {
+		imgui_state.skipped_seconds += delta_seconds;
+		return None;
+	}
+	imgui_state.last_frame = now;
+	delta_seconds += std::mem::replace(&mut imgui_state.skipped_seconds, 0.);
+	if let Some(ref mut recorder) = recorder {
+		delta_seconds = recorder.next_frame(imgui_state, delta_seconds);
+	}
+	if let Some(mut keyboard) = resources.try_fetch_mut::<keyboard::OnScreenKeyboard>() {
+		for input in keyboard.frame_input() {
+			imgui_state.apply_input(input);
+		}
+	}
+
+	if !imgui_state.shaped_windows.is_empty() || !imgui_state.hit_shapes.is_empty() {
+		imgui_state.hit_shapes = unsafe { hit_shape::collect(&imgui_state.shaped_windows) };
+		if imgui_state.sync_mouse_position() {
+			imgui_state.imgui.set_mouse_wheel(0.);
+		}
+	}
+
+	let frame = imgui_state.imgui.frame(FrameSize::new(f64::from(width), f64::from(height), 1.), delta_seconds);
 	std::mem::forget(frame);
-	unsafe { imgui::Ui::current_ui() }
+	let ui = unsafe { imgui::Ui::current_ui() }?;
+	builtin_windows(ui, &mut imgui_state.show_demo_window, &mut imgui_state.show_metrics_window);
+	Some(ui)
 }
 
 pub fn close_frame(ui: &imgui::Ui) {
@@ -289,17 +1078,6 @@ pub fn close_frame(ui: &imgui::Ui) {
 }
 
 pub fn handle_imgui_events(world: &amethyst::ecs::World, event: &amethyst::renderer::Event) {
-	use amethyst::{
-		renderer::{
-			ElementState,
-			Event,
-			MouseButton,
-			VirtualKeyCode as VK,
-			WindowEvent::{self, ReceivedCharacter},
-		},
-		winit::{MouseScrollDelta, TouchPhase},
-	};
-
 	let resources = std::borrow::Borrow::<amethyst::ecs::Resources>::borrow(world);
 
 	let mut imgui_state: Option<FetchMut<'_, Option<ImguiState>>> = resources.try_fetch_mut::<Option<ImguiState>>();
@@ -312,60 +1090,49 @@ pub fn handle_imgui_events(world: &amethyst::ecs::World, event: &amethyst::rende
 		_ => return,
 	};
 
-	let imgui = &mut imgui_state.imgui;
-	let mouse_state = &mut imgui_state.mouse_state;
+	let mut recorder = resources.try_fetch_mut::<recording::InputRecorder>();
+	process_event(imgui_state, recorder.as_mut().map(|x| &mut **x), event);
+	let mut file_drops = resources.try_fetch_mut::<amethyst::shrev::EventChannel<file_drop::ImguiFileDrop>>();
+	file_drop::publish(imgui_state, file_drops.as_mut().map(|x| &mut **x));
+}
 
-	if let Event::WindowEvent { event, .. } = event {
-		match event {
-			WindowEvent::KeyboardInput { input, .. } => {
-				let pressed = input.state == ElementState::Pressed;
-				match input.virtual_keycode {
-					Some(VK::Tab) => imgui.set_key(0, pressed),
-					Some(VK::Left) => imgui.set_key(1, pressed),
-					Some(VK::Right) => imgui.set_key(2, pressed),
-					Some(VK::Up) => imgui.set_key(3, pressed),
-					Some(VK::Down) => imgui.set_key(4, pressed),
-					Some(VK::PageUp) => imgui.set_key(5, pressed),
-					Some(VK::PageDown) => imgui.set_key(6, pressed),
-					Some(VK::Home) => imgui.set_key(7, pressed),
-					Some(VK::End) => imgui.set_key(8, pressed),
-					Some(VK::Delete) => imgui.set_key(9, pressed),
-					Some(VK::Back) => imgui.set_key(10, pressed),
-					Some(VK::Return) => imgui.set_key(11, pressed),
-					Some(VK::Escape) => imgui.set_key(12, pressed),
-					Some(VK::A) => imgui.set_key(13, pressed),
-					Some(VK::C) => imgui.set_key(14, pressed),
-					Some(VK::V) => imgui.set_key(15, pressed),
-					Some(VK::X) => imgui.set_key(16, pressed),
-					Some(VK::Y) => imgui.set_key(17, pressed),
-					Some(VK::Z) => imgui.set_key(18, pressed),
-					Some(VK::LControl) | Some(VK::RControl) => imgui.set_key_ctrl(pressed),
-					Some(VK::LShift) | Some(VK::RShift) => imgui.set_key_shift(pressed),
-					Some(VK::LAlt) | Some(VK::RAlt) => imgui.set_key_alt(pressed),
-					Some(VK::LWin) | Some(VK::RWin) => imgui.set_key_super(pressed),
-					_ => {},
-				}
-			},
-			WindowEvent::CursorMoved { position: pos, .. } => {
-				mouse_state.pos = (pos.x as i32, pos.y as i32);
-			},
-			WindowEvent::MouseInput { state, button, .. } => match button {
-				MouseButton::Left => mouse_state.pressed.0 = *state == ElementState::Pressed,
-				MouseButton::Right => mouse_state.pressed.1 = *state == ElementState::Pressed,
-				MouseButton::Middle => mouse_state.pressed.2 = *state == ElementState::Pressed,
-				_ => {},
-			},
-			WindowEvent::MouseWheel { delta, phase: TouchPhase::Moved, .. } => match delta {
-				MouseScrollDelta::LineDelta(_, y) => mouse_state.wheel = *y,
-				MouseScrollDelta::PixelDelta(lp) => mouse_state.wheel = lp.y as f32,
+pub(crate) fn process_event(
+	imgui_state: &mut ImguiState,
+	recorder: Option<&mut recording::InputRecorder>,
+	event: &amethyst::renderer::Event,
+) {
+	if let amethyst::renderer::Event::Suspended(suspended) = *event {
+		imgui_state.suspended = suspended;
+		imgui_state.mouse_state = MouseState::default();
+		imgui_state.sync_mouse();
+		return;
+	}
+
+	if let amethyst::renderer::Event::WindowEvent { window_id, ref event } = *event {
+		if imgui_state.window_id.map_or(false, |x| x != window_id) {
+			return;
+		}
+		match *event {
+			amethyst::renderer::WindowEvent::Focused(focused) => imgui_state.focused = focused,
+			amethyst::renderer::WindowEvent::HoveredFile(ref path) => imgui_state.hovered_files.push(path.clone()),
+			amethyst::renderer::WindowEvent::HoveredFileCancelled => imgui_state.hovered_files.clear(),
+			amethyst::renderer::WindowEvent::DroppedFile(ref path) => {
+				imgui_state.hovered_files.clear();
+				imgui_state.dropped_files.push(path.clone());
 			},
-			ReceivedCharacter(c) => imgui.add_input_character(*c),
-			_ => (),
+			_ => {},
 		}
 	}
 
-	imgui.set_mouse_pos(mouse_state.pos.0 as f32, mouse_state.pos.1 as f32);
-	imgui.set_mouse_down([mouse_state.pressed.0, mouse_state.pressed.1, mouse_state.pressed.2, false, false]);
-	imgui.set_mouse_wheel(mouse_state.wheel);
-	mouse_state.wheel = 0.0;
+	let input = ImguiInput::from_event(event);
+	if let Some(recorder) = recorder {
+		if !recorder.record(input) {
+			return;
+		}
+	}
+
+	match input {
+		Some(input) => imgui_state.apply_input(input),
+		None => imgui_state.sync_mouse(),
+	}
 }
@@ -0,0 +1,92 @@
+use amethyst::{
+	ecs::prelude::*,
+	renderer::{AmbientColor, Light, Rgba},
+};
+use imgui::{ImGuiColorEditFlags, ImGuiCond, ImStr, Ui};
+
+use crate::{inspect::ImguiInspect, selection::SelectedEntity};
+
+impl ImguiInspect for Rgba {
+	/// A hue wheel, since that's how light colors are usually picked.
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool {
+		let mut color = [self.0, self.1, self.2, self.3];
+		let changed = ui
+			.color_picker(label, &mut color)
+			.flags(ImGuiColorEditFlags::PickerHueWheel | ImGuiColorEditFlags::NoAlpha)
+			.build();
+		*self = Rgba(color[0], color[1], color[2], color[3]);
+		changed
+	}
+}
+
+fn slider(ui: &Ui, label: &ImStr, value: &mut f32, max: f32) -> bool { ui.slider_float(label, value, 0., max).build() }
+
+impl ImguiInspect for Light {
+	fn inspect(&mut self, ui: &Ui, label: &ImStr) -> bool {
+		ui.text(label);
+		match *self {
+			Light::Point(ref mut x) => {
+				slider(ui, im_str!("Intensity"), &mut x.intensity, 100.)
+					| slider(ui, im_str!("Radius"), &mut x.radius, 100.)
+					| slider(ui, im_str!("Smoothness"), &mut x.smoothness, 1.)
+					| x.color.inspect(ui, im_str!("Color"))
+			},
+			Light::Directional(ref mut x) => {
+				x.direction.inspect(ui, im_str!("Direction")) | x.color.inspect(ui, im_str!("Color"))
+			},
+			Light::Spot(ref mut x) => {
+				slider(ui, im_str!("Intensity"), &mut x.intensity, 100.)
+					| slider(ui, im_str!("Range"), &mut x.range, 100.)
+					| slider(ui, im_str!("Angle"), &mut x.angle, 180.)
+					| slider(ui, im_str!("Smoothness"), &mut x.smoothness, 1.)
+					| x.direction.inspect(ui, im_str!("Direction"))
+					| x.color.inspect(ui, im_str!("Color"))
+			},
+			Light::Sun(ref mut x) => {
+				slider(ui, im_str!("Intensity"), &mut x.intensity, 100.)
+					| slider(ui, im_str!("Angular radius"), &mut x.ang_rad, 1.)
+					| x.direction.inspect(ui, im_str!("Direction"))
+					| x.color.inspect(ui, im_str!("Color"))
+			},
+			_ => {
+				ui.text_disabled("Not editable");
+				false
+			},
+		}
+	}
+}
+
+/// Light editing window drawn by `light_window`, for the `SelectedEntity`'s `Light` and the `AmbientColor`.
+#[derive(Default)]
+pub struct LightEditor {
+	pub open: bool,
+}
+
+pub fn light_window(ui: &Ui, world: &World) {
+	let mut open = match world.res.try_fetch::<LightEditor>() {
+		Some(ref x) if x.open => true,
+		_ => return,
+	};
+	ui.window(im_str!("Lights"))
+		.size((320., 520.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			if let Some(mut ambient) = world.res.try_fetch_mut::<AmbientColor>() {
+				if ui.collapsing_header(im_str!("Ambient")).build() {
+					ambient.0.inspect(ui, im_str!("Ambient color"));
+				}
+			}
+			let selected = world.res.try_fetch::<SelectedEntity>().and_then(|x| x.0);
+			let mut lights = world.write_storage::<Light>();
+			match selected.map(|x| (x, lights.get_mut(x))) {
+				Some((entity, Some(light))) => {
+					light.inspect(ui, &im_str!("Entity {}", entity.id()));
+				},
+				Some((entity, None)) => ui.text_disabled(format!("Entity {} has no light", entity.id())),
+				None => ui.text_disabled("Nothing selected"),
+			}
+		});
+	if let Some(mut editor) = world.res.try_fetch_mut::<LightEditor>() {
+		editor.open = open;
+	}
+}
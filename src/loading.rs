@@ -0,0 +1,99 @@
+use amethyst::{
+	assets::{Handle, ProgressCounter},
+	ecs::prelude::*,
+	renderer::Texture,
+};
+use imgui::{sys, ImGuiCond, ImGuiWindowFlags, ImString, StyleVar, Ui};
+
+use crate::textures::{self, ImguiTextures};
+
+/// Looks of the screen drawn by `loading_screen`; without this resource it only shows the progress bar.
+pub struct LoadingScreen {
+	pub title: String,
+	/// Registered with `ImguiTextures`, drawn above the progress bar.
+	pub logo: Option<(Handle<Texture>, (f32, f32))>,
+	pub tips: Vec<String>,
+	/// How long each tip is shown.
+	pub tip_seconds: f32,
+}
+
+impl Default for LoadingScreen {
+	fn default() -> Self {
+		Self {
+			title: "Loading".to_owned(),
+			logo: None,
+			tips: Vec::new(),
+			tip_seconds: 5.,
+		}
+	}
+}
+
+/// Fills the screen with a progress bar bound to `progress`, plus the `LoadingScreen`'s logo and rotating tips.
+///
+/// ```rust,ignore
+/// if let Some(ui) = &ui {
+///     amethyst_imgui::loading::loading_screen(ui, data.world, &self.progress);
+/// }
+/// ```
+pub fn loading_screen(ui: &Ui, world: &World, progress: &ProgressCounter) {
+	let default = LoadingScreen::default();
+	let screen = world.res.try_fetch::<LoadingScreen>();
+	let screen = screen.as_ref().map_or(&default, |x| &**x);
+	let (width, height) = ui.frame_size().logical_size;
+	let (width, height) = (width as f32, height as f32);
+
+	let total = progress.num_assets();
+	let finished = progress.num_finished();
+	let fraction = if total == 0 { 1. } else { finished as f32 / total as f32 };
+	let time = unsafe { sys::igGetTime() } as f32;
+
+	ui.with_style_vars(&[StyleVar::WindowBorderSize(0.)], || {
+		ui.window(im_str!("##loading"))
+			.position((0., 0.), ImGuiCond::Always)
+			.size((width, height), ImGuiCond::Always)
+			.flags(
+				ImGuiWindowFlags::NoTitleBar
+					| ImGuiWindowFlags::NoResize
+					| ImGuiWindowFlags::NoMove
+					| ImGuiWindowFlags::NoScrollbar
+					| ImGuiWindowFlags::NoSavedSettings
+					| ImGuiWindowFlags::NoBringToFrontOnFocus,
+			)
+			.build(|| {
+				let bar_width = (width * 0.6).min(600.);
+				let left = (width - bar_width) / 2.;
+				let mut y = height * 0.3;
+
+				if let Some((ref logo, size)) = screen.logo {
+					if let Some(textures) = world.res.try_fetch::<ImguiTextures>() {
+						ui.set_cursor_pos(((width - size.0) / 2., (y - size.1).max(0.)));
+						textures::image(ui, &textures, logo, size);
+					}
+				}
+
+				y += 20.;
+				let title = ImString::new(screen.title.as_str());
+				ui.set_cursor_pos(((width - ui.calc_text_size(&title, false, -1.).x) / 2., y));
+				ui.text(&title);
+
+				y += 30.;
+				ui.set_cursor_pos((left, y));
+				ui.progress_bar(fraction)
+					.size((bar_width, 0.))
+					.overlay_text(&im_str!("{} / {}", finished, total))
+					.build();
+				if progress.num_failed() > 0 {
+					ui.set_cursor_pos((left, y + 30.));
+					ui.text_colored([1., 0.35, 0.35, 1.], format!("{} failed to load", progress.num_failed()));
+				}
+
+				if !screen.tips.is_empty() {
+					let tip = &screen.tips[(time / screen.tip_seconds.max(0.1)) as usize % screen.tips.len()];
+					ui.set_cursor_pos((left, y + 60.));
+					unsafe { sys::igPushTextWrapPos(left + bar_width) };
+					ui.text(tip.as_str());
+					unsafe { sys::igPopTextWrapPos() };
+				}
+			});
+	});
+}
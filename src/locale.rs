@@ -0,0 +1,115 @@
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImStr, ImString, Ui};
+
+use crate::tables::Table;
+
+/// Glue between `LocalePanel` and the game's localization, e.g. fluent bundles or a custom string table.
+pub trait Localization: Send + Sync {
+	fn locales(&self, world: &World) -> Vec<String>;
+	fn active(&self, world: &World) -> String;
+	fn set_active(&self, world: &World, locale: &str);
+	/// Every message key known in any locale.
+	fn keys(&self, world: &World) -> Vec<String>;
+	fn message(&self, world: &World, locale: &str, key: &str) -> Option<String>;
+}
+
+/// Locale switching and message search, drawn by `locale_window`. Keys missing from the active locale are
+/// shown in red.
+pub struct LocalePanel {
+	pub open: bool,
+	/// Only list keys missing from the active locale.
+	pub missing_only: bool,
+	search: ImString,
+	table: Table,
+	backend: Option<Box<dyn Localization>>,
+}
+
+impl Default for LocalePanel {
+	fn default() -> Self {
+		Self {
+			open: false,
+			missing_only: false,
+			search: ImString::with_capacity(64),
+			table: Table::default(),
+			backend: None,
+		}
+	}
+}
+
+impl LocalePanel {
+	pub fn set_backend<B: Localization + 'static>(&mut self, backend: B) { self.backend = Some(Box::new(backend)); }
+}
+
+pub fn locale_window(ui: &Ui, world: &World) {
+	let mut panel = match world.res.try_fetch_mut::<LocalePanel>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let LocalePanel {
+		ref mut open,
+		ref mut missing_only,
+		ref mut search,
+		ref mut table,
+		ref backend,
+	} = *panel;
+	if !*open {
+		return;
+	}
+	let backend = match *backend {
+		Some(ref x) => x,
+		_ => return,
+	};
+
+	ui.window(im_str!("Localization"))
+		.size((520., 440.), ImGuiCond::FirstUseEver)
+		.opened(open)
+		.build(|| {
+			let locales = backend.locales(world);
+			let active = backend.active(world);
+			let names = locales.iter().map(|x| ImString::new(x.as_str())).collect::<Vec<_>>();
+			let items = names.iter().map(|x| x.as_ref()).collect::<Vec<&ImStr>>();
+			let mut current = locales.iter().position(|x| *x == active).map_or(-1, |x| x as i32);
+			if ui.combo(im_str!("Locale"), &mut current, &items, 10) && current >= 0 {
+				backend.set_active(world, &locales[current as usize]);
+			}
+			ui.input_text(im_str!("Search"), search).build();
+			ui.checkbox(im_str!("Missing only"), missing_only);
+
+			let search = search.to_str().to_lowercase();
+			let mut rows = backend
+				.keys(world)
+				.into_iter()
+				.map(|key| {
+					let message = backend.message(world, &active, &key);
+					(key, message)
+				})
+				.filter(|(key, message)| {
+					let matches = search.is_empty()
+						|| key.to_lowercase().contains(&search)
+						|| message.as_ref().map_or(false, |x| x.to_lowercase().contains(&search));
+					matches && (!*missing_only || message.is_none())
+				})
+				.collect::<Vec<_>>();
+			let missing = rows.iter().filter(|x| x.1.is_none()).count();
+			if missing > 0 {
+				ui.text_colored([1., 0.35, 0.35, 1.], format!("{} listed keys missing from {}", missing, active));
+			}
+			ui.separator();
+
+			table.sort(&mut rows, |a, b, column| match column {
+				0 => a.0.cmp(&b.0),
+				_ => a.1.cmp(&b.1),
+			});
+			table.begin(ui, im_str!("messages"), &["Key", "Message"]);
+			for (key, message) in &rows {
+				ui.text(key);
+				ui.next_column();
+				match message {
+					Some(message) => ui.text(message),
+					None => ui.text_colored([1., 0.35, 0.35, 1.], "missing"),
+				}
+				ui.next_column();
+			}
+			table.end(ui);
+		});
+}
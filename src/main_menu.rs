@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImString, Ui};
+
+type Action = Arc<dyn Fn(&World) + Send + Sync>;
+type Content = Arc<dyn Fn(&Ui, &World) + Send + Sync>;
+
+#[derive(Clone)]
+enum Entry {
+	Item { label: ImString, action: Action },
+	Separator,
+	Custom(Content),
+}
+
+struct Menu {
+	name: ImString,
+	priority: i32,
+	entries: Vec<(i32, Entry)>,
+}
+
+/// A single main menu bar that any system can add menus and items to, drawn by `main_menu`.
+///
+/// Menus and the entries inside them are sorted by priority, lowest first; equal priorities keep the
+/// order they were added in.
+///
+/// ```rust,ignore
+/// let mut menu = world.write_resource::<MainMenu>();
+/// menu.set_priority("File", -100);
+/// menu.add_item("File", "Quit", 100, |world| world.write_resource::<Quit>().0 = true);
+/// menu.add_custom("Windows", 0, |ui, world| world.write_resource::<ToolWindows>().menu_items(ui));
+/// ```
+#[derive(Default)]
+pub struct MainMenu {
+	menus: Vec<Menu>,
+}
+
+impl MainMenu {
+	fn menu(&mut self, name: &str) -> &mut Menu {
+		let index = match self.menus.iter().position(|x| x.name.to_str() == name) {
+			Some(x) => x,
+			None => {
+				self.menus.push(Menu {
+					name: ImString::new(name),
+					priority: 0,
+					entries: Vec::new(),
+				});
+				self.menus.len() - 1
+			},
+		};
+		&mut self.menus[index]
+	}
+
+	fn add(&mut self, menu: &str, priority: i32, entry: Entry) {
+		let entries = &mut self.menu(menu).entries;
+		let index = entries.iter().position(|x| x.0 > priority).unwrap_or_else(|| entries.len());
+		entries.insert(index, (priority, entry));
+	}
+
+	/// Where `menu` goes in the bar, creating it if needed.
+	pub fn set_priority(&mut self, menu: &str, priority: i32) {
+		self.menu(menu).priority = priority;
+		// Stable, so menus with the same priority stay in order
+		self.menus.sort_by_key(|x| x.priority);
+	}
+
+	pub fn add_item<S, F>(&mut self, menu: &str, label: S, priority: i32, action: F)
+	where
+		S: Into<String>,
+		F: Fn(&World) + Send + Sync + 'static,
+	{
+		let label = ImString::new(label.into());
+		self.add(menu, priority, Entry::Item {
+			label,
+			action: Arc::new(action),
+		});
+	}
+
+	pub fn add_separator(&mut self, menu: &str, priority: i32) { self.add(menu, priority, Entry::Separator); }
+
+	/// Draws arbitrary widgets inside `menu`, e.g. checkable items or submenus.
+	pub fn add_custom<F: Fn(&Ui, &World) + Send + Sync + 'static>(&mut self, menu: &str, priority: i32, content: F) {
+		self.add(menu, priority, Entry::Custom(Arc::new(content)));
+	}
+
+	pub fn remove_menu(&mut self, menu: &str) { self.menus.retain(|x| x.name.to_str() != menu); }
+}
+
+pub fn main_menu(ui: &Ui, world: &World) {
+	// Snapshot the menus so items can use the resource themselves
+	let menus = match world.res.try_fetch::<MainMenu>() {
+		Some(x) => x
+			.menus
+			.iter()
+			.map(|x| (x.name.clone(), x.entries.iter().map(|x| x.1.clone()).collect::<Vec<_>>()))
+			.collect::<Vec<_>>(),
+		_ => return,
+	};
+	if menus.is_empty() {
+		return;
+	}
+
+	let mut clicked = None;
+	ui.main_menu_bar(|| {
+		for (name, entries) in &menus {
+			ui.menu(name).build(|| {
+				for entry in entries {
+					match entry {
+						Entry::Item { label, action } => {
+							if ui.menu_item(label).build() {
+								clicked = Some(action.clone());
+							}
+						},
+						Entry::Separator => ui.separator(),
+						Entry::Custom(content) => content(ui, world),
+					}
+				}
+			});
+		}
+	});
+	if let Some(action) = clicked {
+		action(world);
+	}
+}
@@ -0,0 +1,209 @@
+use imgui::{sys, ImGuiCol, ImMouseButton, ImString, ImVec2, Ui};
+
+use crate::code_view::{CodeView, Language};
+
+const LINK: [f32; 4] = [0.4, 0.65, 1., 1.];
+const CODE: [f32; 4] = [0.9, 0.75, 0.35, 1.];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Style {
+	Text,
+	Bold,
+	Code,
+	Link,
+}
+
+/// Splits a paragraph into styled runs of `**bold**`, `` `code` `` and `[links](url)`, with the url of links.
+fn spans(text: &str) -> Vec<(Style, &str, &str)> {
+	let mut out = Vec::new();
+	let mut plain = 0;
+	let mut i = 0;
+	while i < text.len() {
+		let rest = &text[i..];
+		let span = if rest.starts_with("**") {
+			rest[2..].find("**").map(|end| ((Style::Bold, &rest[2..end + 2], ""), end + 4))
+		} else if rest.starts_with('`') {
+			rest[1..].find('`').map(|end| ((Style::Code, &rest[1..end + 1], ""), end + 2))
+		} else if rest.starts_with('[') {
+			rest.find("](").and_then(|middle| {
+				let end = middle + rest[middle..].find(')')?;
+				Some(((Style::Link, &rest[1..middle], &rest[middle + 2..end]), end + 1))
+			})
+		} else {
+			None
+		};
+		match span {
+			Some((span, len)) => {
+				out.push((Style::Text, &text[plain..i], ""));
+				out.push(span);
+				i += len;
+				plain = i;
+			},
+			None => i += rest.chars().next().map_or(1, char::len_utf8),
+		}
+	}
+	out.push((Style::Text, &text[plain..], ""));
+	out.retain(|x| !x.1.is_empty());
+	out
+}
+
+/// Lays out `text` word by word, wrapping at the window's edge, and calls `on_link` for clicked links.
+fn paragraph(ui: &Ui, text: &str, muted: bool, on_link: &mut dyn FnMut(&str)) {
+	let mut words = Vec::new();
+	let mut space = false;
+	for (style, text, url) in spans(text) {
+		for (i, word) in text.split(' ').enumerate() {
+			space |= i > 0;
+			if !word.is_empty() {
+				words.push((style, word, url, space));
+				space = false;
+			}
+		}
+	}
+
+	let space_width = ui.calc_text_size(im_str!(" "), false, -1.).x;
+	let text_color = if muted { ImGuiCol::TextDisabled } else { ImGuiCol::Text };
+	for (i, &(style, word, url, space)) in words.iter().enumerate() {
+		let word = ImString::new(word);
+		let size = ui.calc_text_size(&word, false, -1.);
+		if i > 0 {
+			unsafe { sys::igSameLine(0., if space { space_width } else { 0. }) };
+			if ui.get_content_region_avail().0 < size.x {
+				unsafe { sys::igNewLine() };
+			}
+		}
+		let (x, y) = ui.get_cursor_screen_pos();
+		match style {
+			Style::Text if muted => ui.text_disabled(&word),
+			Style::Text => ui.text(&word),
+			Style::Bold => unsafe {
+				ui.text_colored((*sys::igGetStyle()).colors[text_color as usize], &word);
+				// No bold font to switch to, so draw it again a pixel to the right
+				let list = sys::igGetWindowDrawList();
+				let text = word.as_ptr();
+				let color = sys::igGetColorU32(text_color, 1.);
+				sys::ImDrawList_AddText(list, ImVec2::new(x + 1., y), color, text, text.add(word.to_str().len()));
+			},
+			Style::Code => ui.text_colored(CODE, &word),
+			Style::Link => {
+				ui.text_colored(LINK, &word);
+				if ui.is_item_hovered() {
+					unsafe {
+						let list = sys::igGetWindowDrawList();
+						let (a, b) = (ImVec2::new(x, y + size.y), ImVec2::new(x + size.x, y + size.y));
+						sys::ImDrawList_AddLine(list, a, b, 0xff_ff_a6_66, 1.);
+					}
+					ui.tooltip(|| ui.text(url));
+					if ui.imgui().is_mouse_clicked(ImMouseButton::Left) {
+						on_link(url);
+					}
+				}
+			},
+		}
+	}
+}
+
+/// Draws `source` as markdown: `#` headings, paragraphs, `**bold**`, `` `code` ``, `-` and `1.` lists,
+/// `>` quotes, `---` rules, fenced code blocks and `[links](url)`, calling `on_link` with the url of clicked
+/// links. Italics and tables are shown as they're written.
+///
+/// ```rust,ignore
+/// markdown(ui, include_str!("../CHANGELOG.md"), |url| open_url(url));
+/// ```
+pub fn markdown<F: FnMut(&str)>(ui: &Ui, source: &str, mut on_link: F) {
+	let spacing = unsafe { sys::igGetTreeNodeToLabelSpacing() };
+	let mut text = String::new();
+	let mut code: Option<(Language, String)> = None;
+	let mut blocks = 0;
+
+	let flush = |text: &mut String, on_link: &mut dyn FnMut(&str)| {
+		if !text.is_empty() {
+			paragraph(ui, text, false, on_link);
+			text.clear();
+		}
+	};
+	for line in source.lines() {
+		let trimmed = line.trim();
+		if let Some((language, ref mut block)) = code {
+			if trimmed.starts_with("```") {
+				let rows = block.lines().count().min(20) + 1;
+				let height = rows as f32 * unsafe { sys::igGetTextLineHeightWithSpacing() };
+				CodeView::new(block, language).size((0., height)).build(ui, &im_str!("##markdown{}", blocks));
+				blocks += 1;
+				code = None;
+			} else {
+				block.push_str(line);
+				block.push('\n');
+			}
+			continue;
+		}
+
+		let depth = (line.len() - line.trim_start().len()) / 2;
+		let heading = trimmed.chars().take_while(|&x| x == '#').count();
+		let number = trimmed.chars().take_while(char::is_ascii_digit).count();
+		let rule = trimmed.len() >= 3 && ["-", "*", "_"].iter().any(|x| trimmed.chars().all(|c| x.starts_with(c)));
+		if trimmed.starts_with("```") {
+			flush(&mut text, &mut on_link);
+			let language = match &trimmed[3..] {
+				"ron" => Language::Ron,
+				"glsl" => Language::Glsl,
+				"rhai" => Language::Rhai,
+				_ => Language::Plain,
+			};
+			code = Some((language, String::new()));
+		} else if trimmed.is_empty() {
+			flush(&mut text, &mut on_link);
+			unsafe { sys::igSpacing() };
+		} else if heading > 0 && heading <= 6 && trimmed[heading..].starts_with(' ') {
+			flush(&mut text, &mut on_link);
+			let scale = [1.6, 1.35, 1.15][(heading - 1).min(2)];
+			unsafe { sys::igSetWindowFontScale(scale) };
+			paragraph(ui, &format!("**{}**", trimmed[heading..].trim()), false, &mut on_link);
+			unsafe { sys::igSetWindowFontScale(1.) };
+			if heading == 1 {
+				ui.separator();
+			}
+		} else if rule {
+			flush(&mut text, &mut on_link);
+			ui.separator();
+		} else if ["- ", "* ", "+ "].iter().any(|x| trimmed.starts_with(x))
+			|| (number > 0 && trimmed[number..].starts_with(". "))
+		{
+			flush(&mut text, &mut on_link);
+			// `igIndent(0.)` would indent by the style's default
+			let indent = depth as f32 * spacing;
+			if depth > 0 {
+				unsafe { sys::igIndent(indent) };
+			}
+			let item = match number {
+				0 => {
+					unsafe { sys::igBullet() };
+					&trimmed[2..]
+				},
+				_ => {
+					ui.text(&trimmed[..=number]);
+					unsafe { sys::igSameLine(0., 0.) };
+					&trimmed[number + 2..]
+				},
+			};
+			// Wrapped lines of the item line up with its first word
+			unsafe { sys::igIndent(spacing) };
+			paragraph(ui, item.trim(), false, &mut on_link);
+			unsafe { sys::igUnindent(indent + spacing) };
+		} else if trimmed.starts_with('>') {
+			flush(&mut text, &mut on_link);
+			unsafe { sys::igIndent(spacing) };
+			paragraph(ui, trimmed[1..].trim(), true, &mut on_link);
+			unsafe { sys::igUnindent(spacing) };
+		} else {
+			if !text.is_empty() {
+				text.push(' ');
+			}
+			text.push_str(trimmed);
+		}
+	}
+	flush(&mut text, &mut on_link);
+	if let Some((language, block)) = code {
+		CodeView::new(&block, language).build(ui, &im_str!("##markdown{}", blocks));
+	}
+}
@@ -0,0 +1,133 @@
+use std::{fs, sync::Arc};
+
+use amethyst::{core::timing::Time, ecs::prelude::*};
+use imgui::{ImGuiCond, ImString, Ui};
+
+use crate::{history::History, textures::ImguiTextures};
+
+type Estimate = Arc<dyn Fn(&World) -> Option<u64> + Send + Sync>;
+
+struct Series {
+	name: ImString,
+	estimate: Estimate,
+	history: History,
+}
+
+/// Memory use sampled every `interval` seconds, drawn by `memory_window`.
+///
+/// Shows the process' resident set (on Linux) and the imgui font atlas out of the box. gfx doesn't report
+/// GPU allocations, so everything else, like asset storages or render targets, is estimated by the game:
+///
+/// ```rust,ignore
+/// memory.add("Level meshes", |world| Some(world.read_resource::<LevelStats>().mesh_bytes));
+/// ```
+pub struct MemoryPanel {
+	pub open: bool,
+	pub interval: f32,
+	series: Vec<Series>,
+	elapsed: f32,
+}
+
+impl Default for MemoryPanel {
+	fn default() -> Self {
+		let mut panel = Self {
+			open: false,
+			interval: 1.,
+			series: Vec::new(),
+			elapsed: std::f32::MAX,
+		};
+		panel.add("Process (RSS)", |_| resident_bytes());
+		panel.add("imgui font atlas", |world| {
+			let textures = world.res.try_fetch::<ImguiTextures>()?;
+			let (_, (width, height)) = textures.font_atlas()?;
+			Some(width as u64 * height as u64 * 4)
+		});
+		panel
+	}
+}
+
+impl MemoryPanel {
+	/// Adds a sampled estimate in bytes; `None` means unavailable.
+	pub fn add<F: Fn(&World) -> Option<u64> + Send + Sync + 'static>(&mut self, name: &str, estimate: F) {
+		self.series.retain(|x| x.name.to_str() != name);
+		self.series.push(Series {
+			name: ImString::new(name),
+			estimate: Arc::new(estimate),
+			history: History::new(120),
+		});
+	}
+
+	pub fn remove(&mut self, name: &str) { self.series.retain(|x| x.name.to_str() != name); }
+}
+
+/// Resident set size of this process, from `/proc/self/status`.
+pub fn resident_bytes() -> Option<u64> {
+	let status = fs::read_to_string("/proc/self/status").ok()?;
+	let line = status.lines().find(|x| x.starts_with("VmRSS:"))?;
+	let kilobytes = line["VmRSS:".len()..].trim().trim_end_matches("kB").trim().parse::<u64>().ok()?;
+	Some(kilobytes * 1024)
+}
+
+fn format_bytes(bytes: f32) -> String {
+	if bytes >= 1024. * 1024. * 1024. {
+		format!("{:.2} GiB", bytes / (1024. * 1024. * 1024.))
+	} else if bytes >= 1024. * 1024. {
+		format!("{:.2} MiB", bytes / (1024. * 1024.))
+	} else if bytes >= 1024. {
+		format!("{:.2} KiB", bytes / 1024.)
+	} else {
+		format!("{:.0} B", bytes)
+	}
+}
+
+pub fn memory_window(ui: &Ui, world: &World) {
+	// Snapshot the estimates so they can fetch any resource
+	let estimates = match world.res.try_fetch_mut::<MemoryPanel>() {
+		Some(mut panel) => {
+			panel.elapsed += world.res.try_fetch::<Time>().map_or(0., |x| x.delta_real_seconds());
+			if panel.elapsed < panel.interval {
+				None
+			} else {
+				panel.elapsed = 0.;
+				Some(panel.series.iter().map(|x| x.estimate.clone()).collect::<Vec<_>>())
+			}
+		},
+		_ => return,
+	};
+	let samples = estimates.map(|x| x.iter().map(|estimate| estimate(world)).collect::<Vec<_>>());
+
+	let mut panel = match world.res.try_fetch_mut::<MemoryPanel>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if let Some(samples) = samples {
+		for (series, sample) in panel.series.iter_mut().zip(samples) {
+			if let Some(bytes) = sample {
+				series.history.push(bytes as f32);
+			}
+		}
+	}
+	if !panel.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Memory"))
+		.size((420., 360.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			for series in &panel.series {
+				let values = series.history.to_vec();
+				let current = match series.history.last() {
+					Some(x) => format_bytes(x),
+					None => "n/a".to_owned(),
+				};
+				ui.plot_lines(&series.name, &values)
+					.overlay_text(&im_str!("{}", current))
+					.scale_min(0.)
+					.graph_size((0., 50.))
+					.build();
+			}
+		});
+	panel.open = open;
+}
@@ -0,0 +1,184 @@
+use bincode;
+use imgui::{ImGuiSelectableFlags, ImString, Ui};
+use serde::{de::DeserializeOwned, Serialize};
+
+const ROWS_PER_PAGE: usize = 256;
+
+/// Hex viewer/editor over byte slices, in the spirit of imgui_club's memory editor.
+///
+/// Keep one per window; it holds the cursor, paging and in-progress edit.
+pub struct MemoryEditor {
+	pub columns: usize,
+	pub read_only: bool,
+	pub show_ascii: bool,
+	pub base_address: usize,
+	page: i32,
+	selected: Option<usize>,
+	editing: Option<(usize, ImString)>,
+	goto: ImString,
+}
+
+impl Default for MemoryEditor {
+	fn default() -> Self {
+		Self {
+			columns: 16,
+			read_only: false,
+			show_ascii: true,
+			base_address: 0,
+			page: 0,
+			selected: None,
+			editing: None,
+			goto: ImString::with_capacity(16),
+		}
+	}
+}
+
+impl MemoryEditor {
+	pub fn read_only() -> Self {
+		Self {
+			read_only: true,
+			..Self::default()
+		}
+	}
+
+	/// Draws a read-only view, regardless of `read_only`.
+	pub fn view(&mut self, ui: &Ui, data: &[u8]) {
+		let mut copy = data.to_vec();
+		let read_only = std::mem::replace(&mut self.read_only, true);
+		self.draw(ui, &mut copy);
+		self.read_only = read_only;
+	}
+
+	/// Draws the editor; returns `true` if a byte was changed.
+	pub fn draw(&mut self, ui: &Ui, data: &mut [u8]) -> bool {
+		let columns = self.columns.max(1);
+		let rows = (data.len() + columns - 1) / columns;
+		let pages = ((rows + ROWS_PER_PAGE - 1) / ROWS_PER_PAGE).max(1) as i32;
+		let mut changed = false;
+
+		ui.text(format!("{} bytes", data.len()));
+		if let Some(selected) = self.selected.filter(|x| *x < data.len()) {
+			ui.same_line(0.);
+			ui.text(format!(
+				"| {:08X}: {} (u8) {} (i8)",
+				self.base_address + selected,
+				data[selected],
+				data[selected] as i8
+			));
+		}
+		ui.push_item_width(100.);
+		if ui
+			.input_text(im_str!("Go to"), &mut self.goto)
+			.chars_hexadecimal(true)
+			.enter_returns_true(true)
+			.build()
+		{
+			if let Ok(address) = usize::from_str_radix(self.goto.to_str(), 16) {
+				let offset = address.saturating_sub(self.base_address).min(data.len().saturating_sub(1));
+				self.selected = Some(offset);
+				self.page = (offset / columns / ROWS_PER_PAGE) as i32;
+			}
+		}
+		ui.pop_item_width();
+		if pages > 1 {
+			ui.same_line(0.);
+			ui.push_item_width(150.);
+			ui.slider_int(im_str!("Page"), &mut self.page, 0, pages - 1).build();
+			ui.pop_item_width();
+		}
+		self.page = self.page.max(0).min(pages - 1);
+		ui.separator();
+
+		let byte_width = ui.calc_text_size(im_str!("FF"), false, -1.).x;
+		let first_row = self.page as usize * ROWS_PER_PAGE;
+		let last_row = (first_row + ROWS_PER_PAGE).min(rows);
+		ui.child_frame(im_str!("##memory"), (0., 0.)).build(|| {
+			for row in first_row..last_row {
+				let start = row * columns;
+				let end = (start + columns).min(data.len());
+				ui.text_disabled(format!("{:08X}:", self.base_address + start));
+				for offset in start..end {
+					ui.same_line(0.);
+					if columns > 8 && offset - start == columns / 2 {
+						ui.text(" ");
+						ui.same_line(0.);
+					}
+					let is_editing = self.editing.as_ref().map_or(false, |x| x.0 == offset);
+					if is_editing {
+						let commit = {
+							let buffer = &mut self.editing.as_mut().unwrap().1;
+							ui.push_item_width(byte_width + 4.);
+							let commit = ui
+								.input_text(&im_str!("##edit{}", offset), buffer)
+								.chars_hexadecimal(true)
+								.auto_select_all(true)
+								.enter_returns_true(true)
+								.build();
+							ui.pop_item_width();
+							commit
+						};
+						if commit {
+							let (_, buffer) = self.editing.take().unwrap();
+							if let Ok(value) = u8::from_str_radix(buffer.to_str(), 16) {
+								changed |= data[offset] != value;
+								data[offset] = value;
+							}
+							self.selected = Some((offset + 1).min(data.len() - 1));
+						}
+						continue;
+					}
+
+					let label = im_str!("{:02X}##{}", data[offset], offset);
+					let color = if data[offset] == 0 { [0.5, 0.5, 0.5, 1.] } else { [1., 1., 1., 1.] };
+					let clicked = ui.with_color_var(imgui::ImGuiCol::Text, color, || {
+						ui.selectable(
+							&label,
+							self.selected == Some(offset),
+							ImGuiSelectableFlags::AllowDoubleClick,
+							(byte_width, 0.),
+						)
+					});
+					if clicked {
+						self.selected = Some(offset);
+						if !self.read_only && ui.imgui().is_mouse_double_clicked(imgui::ImMouseButton::Left) {
+							let mut buffer = ImString::with_capacity(3);
+							buffer.push_str(&format!("{:02X}", data[offset]));
+							self.editing = Some((offset, buffer));
+						}
+					}
+				}
+				if self.show_ascii {
+					let ascii = data[start..end]
+						.iter()
+						.map(|x| if x.is_ascii_graphic() || *x == b' ' { *x as char } else { '.' })
+						.collect::<String>();
+					ui.same_line(0.);
+					ui.text(format!(" {}", ascii));
+				}
+			}
+		});
+
+		changed
+	}
+
+	/// Edits a value through its bincode encoding, writing it back when the bytes still decode.
+	pub fn draw_serialized<T: Serialize + DeserializeOwned>(&mut self, ui: &Ui, value: &mut T) -> bool {
+		let mut bytes = match bincode::serialize(value) {
+			Ok(x) => x,
+			Err(e) => {
+				ui.text_colored([1., 0.35, 0.35, 1.], format!("Failed to serialize: {}", e));
+				return false;
+			},
+		};
+		if !self.draw(ui, &mut bytes) {
+			return false;
+		}
+		match bincode::deserialize(&bytes) {
+			Ok(x) => {
+				*value = x;
+				true
+			},
+			Err(_) => false,
+		}
+	}
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashSet, time::Duration};
+
+use imgui::sys;
+
+/// Cost of the overlay itself in the last rendered frame, updated by the `DrawUi` pass.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ImguiMetrics {
+	pub vertices: usize,
+	pub indices: usize,
+	pub draw_lists: usize,
+	/// Draw commands, not counting user callbacks.
+	pub draw_calls: usize,
+	/// Distinct textures referenced by the draw commands.
+	pub textures: usize,
+	/// CPU time spent uploading the draw lists and encoding their draw calls.
+	pub prepare_time: Duration,
+	/// CPU time spent compositing the overlay into the stage.
+	pub draw_time: Duration,
+}
+
+impl ImguiMetrics {
+	/// Counts the draw data of the last rendered frame; same safety rules as `draw_data::capture`.
+	pub(crate) unsafe fn count(&mut self) {
+		self.vertices = 0;
+		self.indices = 0;
+		self.draw_lists = 0;
+		self.draw_calls = 0;
+		self.textures = 0;
+
+		let draw_data = sys::igGetDrawData();
+		if draw_data.is_null() || !(*draw_data).valid || (*draw_data).cmd_lists.is_null() {
+			return;
+		}
+		let draw_data = &*draw_data;
+		self.vertices = draw_data.total_vtx_count.max(0) as usize;
+		self.indices = draw_data.total_idx_count.max(0) as usize;
+		self.draw_lists = draw_data.cmd_lists_count.max(0) as usize;
+
+		let mut textures = HashSet::new();
+		for &list in std::slice::from_raw_parts(draw_data.cmd_lists, self.draw_lists) {
+			let commands = &(*list).cmd_buffer;
+			if commands.data.is_null() || commands.size <= 0 {
+				continue;
+			}
+			for command in std::slice::from_raw_parts(commands.data, commands.size as usize) {
+				if command.user_callback.is_none() {
+					self.draw_calls += 1;
+					textures.insert(command.texture_id as usize);
+				}
+			}
+		}
+		self.textures = textures.len();
+	}
+}
+
+/// imgui's own IO statistics, copied by the `DrawUi` pass after rendering each frame.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ImguiIoMetrics {
+	/// Frames per second, averaged by imgui over the last 120 frames.
+	pub framerate: f32,
+	pub render_vertices: i32,
+	pub render_indices: i32,
+	pub active_windows: i32,
+	pub want_capture_mouse: bool,
+	pub want_capture_keyboard: bool,
+	pub want_text_input: bool,
+	pub any_item_hovered: bool,
+	pub any_item_active: bool,
+}
+
+impl ImguiIoMetrics {
+	pub(crate) unsafe fn update(&mut self) {
+		let io = &*sys::igGetIO();
+		self.framerate = io.framerate;
+		self.render_vertices = io.metrics_render_vertices;
+		self.render_indices = io.metrics_render_indices;
+		self.active_windows = io.metrics_active_windows;
+		self.want_capture_mouse = io.want_capture_mouse;
+		self.want_capture_keyboard = io.want_capture_keyboard;
+		self.want_text_input = io.want_text_input;
+		self.any_item_hovered = sys::igIsAnyItemHovered();
+		self.any_item_active = sys::igIsAnyItemActive();
+	}
+}
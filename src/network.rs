@@ -0,0 +1,160 @@
+use std::marker::PhantomData;
+
+use amethyst::{
+	core::timing::Time,
+	ecs::prelude::*,
+	network::{ConnectionState, NetConnection},
+};
+use imgui::{ImGuiCond, Ui};
+
+use crate::history::History;
+
+/// Rolling network statistics, sampled once per second by `NetworkStatsSystem`.
+///
+/// `amethyst_network` does not expose transport counters, so byte/packet totals and RTT are
+/// reported by the game through the `record_*` methods.
+#[derive(Default)]
+pub struct NetworkStats {
+	pub open: bool,
+	pub peers: usize,
+	pub connecting: usize,
+	pub rtt_ms: Option<f32>,
+	pub total_sent_packets: u64,
+	pub total_sent_bytes: u64,
+	pub total_received_packets: u64,
+	pub total_received_bytes: u64,
+	window: Counters,
+	elapsed: f32,
+	pub sent_packets_per_sec: History,
+	pub received_packets_per_sec: History,
+	pub sent_bytes_per_sec: History,
+	pub received_bytes_per_sec: History,
+	pub rtt_history: History,
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+struct Counters {
+	sent_packets: u64,
+	sent_bytes: u64,
+	received_packets: u64,
+	received_bytes: u64,
+}
+
+impl NetworkStats {
+	pub fn record_sent(&mut self, bytes: usize) {
+		self.window.sent_packets += 1;
+		self.window.sent_bytes += bytes as u64;
+		self.total_sent_packets += 1;
+		self.total_sent_bytes += bytes as u64;
+	}
+
+	pub fn record_received(&mut self, bytes: usize) {
+		self.window.received_packets += 1;
+		self.window.received_bytes += bytes as u64;
+		self.total_received_packets += 1;
+		self.total_received_bytes += bytes as u64;
+	}
+
+	pub fn record_rtt(&mut self, rtt_ms: f32) { self.rtt_ms = Some(rtt_ms); }
+
+	fn advance(&mut self, delta_seconds: f32) {
+		self.elapsed += delta_seconds;
+		if self.elapsed < 1. {
+			return;
+		}
+
+		let scale = 1. / self.elapsed;
+		self.sent_packets_per_sec.push(self.window.sent_packets as f32 * scale);
+		self.received_packets_per_sec.push(self.window.received_packets as f32 * scale);
+		self.sent_bytes_per_sec.push(self.window.sent_bytes as f32 * scale);
+		self.received_bytes_per_sec.push(self.window.received_bytes as f32 * scale);
+		self.rtt_history.push(self.rtt_ms.unwrap_or(0.));
+		self.window = Counters::default();
+		self.elapsed = 0.;
+	}
+}
+
+/// Counts connected peers and rolls the per-second samples of `NetworkStats`.
+pub struct NetworkStatsSystem<E> {
+	_marker: PhantomData<E>,
+}
+
+impl<E> Default for NetworkStatsSystem<E> {
+	fn default() -> Self { Self { _marker: PhantomData } }
+}
+
+impl<'s, E: Send + Sync + 'static> System<'s> for NetworkStatsSystem<E> {
+	type SystemData = (ReadStorage<'s, NetConnection<E>>, Read<'s, Time>, Write<'s, NetworkStats>);
+
+	fn run(&mut self, (connections, time, mut stats): Self::SystemData) {
+		stats.peers = 0;
+		stats.connecting = 0;
+		for connection in (&connections).join() {
+			match connection.state {
+				ConnectionState::Connected => stats.peers += 1,
+				ConnectionState::Connecting => stats.connecting += 1,
+				_ => {},
+			}
+		}
+		stats.advance(time.delta_real_seconds());
+	}
+}
+
+fn format_bytes(bytes: f32) -> String {
+	if bytes >= 1024. * 1024. {
+		format!("{:.2} MiB", bytes / (1024. * 1024.))
+	} else if bytes >= 1024. {
+		format!("{:.2} KiB", bytes / 1024.)
+	} else {
+		format!("{:.0} B", bytes)
+	}
+}
+
+fn plot(ui: &Ui, label: &str, history: &History, format: fn(f32) -> String) {
+	let values = history.to_vec();
+	let current = history.last().unwrap_or(0.);
+	ui.plot_lines(&im_str!("{}", label), &values)
+		.overlay_text(&im_str!("{}", format(current)))
+		.scale_min(0.)
+		.graph_size((0., 50.))
+		.build();
+}
+
+pub fn network_stats_window(ui: &Ui, world: &World) {
+	let mut stats = match world.res.try_fetch_mut::<NetworkStats>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !stats.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Network"))
+		.size((360., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.text(format!("Peers: {} connected, {} connecting", stats.peers, stats.connecting));
+			match stats.rtt_ms {
+				Some(rtt) => ui.text(format!("RTT: {:.1} ms", rtt)),
+				None => ui.text("RTT: n/a"),
+			}
+			ui.text(format!(
+				"Sent: {} packets, {}",
+				stats.total_sent_packets,
+				format_bytes(stats.total_sent_bytes as f32)
+			));
+			ui.text(format!(
+				"Received: {} packets, {}",
+				stats.total_received_packets,
+				format_bytes(stats.total_received_bytes as f32)
+			));
+			ui.separator();
+			plot(ui, "Sent/s", &stats.sent_bytes_per_sec, |x| format!("{}/s", format_bytes(x)));
+			plot(ui, "Received/s", &stats.received_bytes_per_sec, |x| format!("{}/s", format_bytes(x)));
+			plot(ui, "Packets out/s", &stats.sent_packets_per_sec, |x| format!("{:.0}", x));
+			plot(ui, "Packets in/s", &stats.received_packets_per_sec, |x| format!("{:.0}", x));
+			plot(ui, "RTT", &stats.rtt_history, |x| format!("{:.1} ms", x));
+		});
+	stats.open = open;
+}
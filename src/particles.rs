@@ -0,0 +1,105 @@
+use std::{
+	fs,
+	io,
+	path::{Path, PathBuf},
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, Ui};
+use ron;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::inspect::ImguiInspect;
+
+/// Editor window for the parameter resource `T` a particle emitter reads, drawn by `particle_editor::<T>`.
+///
+/// Edits go straight into the resource, so the running emitter previews them live.
+pub struct ParticleEditor<T> {
+	pub open: bool,
+	/// Where Save and Load read and write the parameters as RON.
+	pub path: Option<PathBuf>,
+	/// The parameters when the window was opened, for Revert.
+	snapshot: Option<T>,
+	status: Option<String>,
+}
+
+impl<T> Default for ParticleEditor<T> {
+	fn default() -> Self {
+		Self {
+			open: false,
+			path: None,
+			snapshot: None,
+			status: None,
+		}
+	}
+}
+
+fn save<T: Serialize>(path: &Path, params: &T) -> io::Result<()> {
+	let source = ron::ser::to_string_pretty(params, Default::default())
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+	fs::write(path, source)
+}
+
+fn load<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+	let source = fs::read_to_string(path)?;
+	ron::de::from_str(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+}
+
+pub fn particle_editor<T>(ui: &Ui, world: &World)
+where
+	T: ImguiInspect + Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	let mut editor = match world.res.try_fetch_mut::<ParticleEditor<T>>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let editor = &mut *editor;
+	let mut params = match world.res.try_fetch_mut::<T>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !editor.open {
+		editor.snapshot = None;
+		return;
+	}
+	if editor.snapshot.is_none() {
+		editor.snapshot = Some(params.clone());
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Particles"))
+		.size((360., 480.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			if ui.small_button(im_str!("Revert")) {
+				if let Some(ref snapshot) = editor.snapshot {
+					*params = snapshot.clone();
+				}
+			}
+			if let Some(path) = editor.path.clone() {
+				ui.same_line(0.);
+				if ui.small_button(im_str!("Save")) {
+					editor.status = Some(match save(&path, &*params) {
+						Ok(()) => format!("Saved {}", path.display()),
+						Err(e) => format!("Saving {} failed: {}", path.display(), e),
+					});
+				}
+				ui.same_line(0.);
+				if ui.small_button(im_str!("Load")) {
+					editor.status = Some(match load::<T>(&path) {
+						Ok(x) => {
+							*params = x;
+							format!("Loaded {}", path.display())
+						},
+						Err(e) => format!("Loading {} failed: {}", path.display(), e),
+					});
+				}
+			}
+			if let Some(ref status) = editor.status {
+				ui.text_disabled(status);
+			}
+			ui.separator();
+			params.inspect(ui, im_str!("Parameters"));
+		});
+	editor.open = open;
+}
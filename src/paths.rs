@@ -0,0 +1,229 @@
+use std::{
+	fs,
+	io,
+	path::{Path, PathBuf},
+};
+
+use amethyst::{
+	core::nalgebra::{Point3, Vector3},
+	ecs::prelude::*,
+	renderer::{DebugLines, Rgba},
+	shrev::{EventChannel, ReaderId},
+};
+use imgui::{sys, ImGuiCond, Ui};
+use ron;
+
+use crate::{
+	grid::GridPlane,
+	picking::{PickRequest, ScreenProjection},
+};
+
+/// A Catmull-Rom spline through waypoints, e.g. an AI patrol route or a camera rail.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Waypoints {
+	pub points: Vec<[f32; 3]>,
+	/// Loop back from the last point to the first.
+	pub closed: bool,
+}
+
+impl Waypoints {
+	/// Points along the spline, `segments` per pair of waypoints.
+	pub fn sample(&self, segments: usize) -> Vec<Point3<f32>> {
+		let points = self.points.iter().map(|x| Vector3::new(x[0], x[1], x[2])).collect::<Vec<_>>();
+		let count = points.len();
+		if count < 2 {
+			return points.into_iter().map(Point3::from_coordinates).collect();
+		}
+		let at = |i: isize| {
+			if self.closed {
+				let count = count as isize;
+				points[((i % count + count) % count) as usize]
+			} else {
+				points[i.max(0).min(count as isize - 1) as usize]
+			}
+		};
+		let spans = if self.closed { count } else { count - 1 };
+		let mut out = Vec::with_capacity(spans * segments + 1);
+		for i in 0..spans as isize {
+			let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+			for step in 0..segments {
+				let t = step as f32 / segments as f32;
+				let (t2, t3) = (t * t, t * t * t);
+				let point = ((p1 * 2.)
+					+ (p2 - p0) * t + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * t2
+					+ (p1 * 3. - p0 - p2 * 3. + p3) * t3)
+					* 0.5;
+				out.push(Point3::from_coordinates(point));
+			}
+		}
+		out.push(Point3::from_coordinates(if self.closed { points[0] } else { points[count - 1] }));
+		out
+	}
+
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let source = ron::ser::to_string_pretty(self, Default::default())
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		fs::write(path, source)
+	}
+
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		let source = fs::read_to_string(path)?;
+		ron::de::from_str(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+	}
+}
+
+/// Waypoint editing tool drawn by `path_tool`.
+///
+/// While `active`, clicking on the game adds a waypoint where the cursor meets `plane` through the origin,
+/// and clicking near an existing one drags it. The spline is drawn through amethyst's `DebugLines`.
+pub struct PathTool {
+	pub active: bool,
+	pub plane: GridPlane,
+	pub path: Waypoints,
+	/// Where Save and Load read and write `path` as RON.
+	pub file: Option<PathBuf>,
+	pub color: Rgba,
+	dragging: Option<usize>,
+	status: Option<String>,
+	reader: Option<ReaderId<PickRequest>>,
+}
+
+impl Default for PathTool {
+	fn default() -> Self {
+		Self {
+			active: false,
+			plane: GridPlane::XY,
+			path: Waypoints::default(),
+			file: None,
+			color: Rgba::blue(),
+			dragging: None,
+			status: None,
+			reader: None,
+		}
+	}
+}
+
+/// How close to a waypoint, in pixels, a click has to be to grab it.
+const GRAB_DISTANCE: f32 = 8.;
+
+pub fn path_tool(ui: &Ui, world: &World) {
+	let mut tool = match world.res.try_fetch_mut::<PathTool>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let tool = &mut *tool;
+	let mut requests = match world.res.try_fetch_mut::<EventChannel<PickRequest>>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let reader = match tool.reader {
+		Some(ref mut x) => x,
+		_ => {
+			tool.reader = Some(requests.register_reader());
+			return;
+		},
+	};
+	let requests = requests.read(reader).cloned().collect::<Vec<_>>();
+	if !tool.active {
+		tool.dragging = None;
+		return;
+	}
+
+	let projection = ScreenProjection::new(ui, world);
+	let handles = tool
+		.path
+		.points
+		.iter()
+		.map(|x| projection.and_then(|p| p.project(&Point3::new(x[0], x[1], x[2]))))
+		.collect::<Vec<_>>();
+	for request in requests {
+		if !request.pressed {
+			tool.dragging = None;
+			continue;
+		}
+		let point = match request.ray.on_plane(tool.plane) {
+			Some(x) => [x.x, x.y, x.z],
+			_ => continue,
+		};
+		if request.clicked {
+			let mouse = ui.imgui().mouse_pos();
+			let grabbed = handles.iter().position(|x| match *x {
+				Some(x) => (x.x - mouse.0).powi(2) + (x.y - mouse.1).powi(2) <= GRAB_DISTANCE.powi(2),
+				None => false,
+			});
+			tool.dragging = match grabbed {
+				Some(x) => Some(x),
+				None => {
+					tool.path.points.push(point);
+					Some(tool.path.points.len() - 1)
+				},
+			};
+		} else if let Some(index) = tool.dragging.filter(|x| *x < tool.path.points.len()) {
+			tool.path.points[index] = point;
+		}
+	}
+
+	if let Some(mut lines) = world.res.try_fetch_mut::<DebugLines>() {
+		let points = tool.path.sample(16);
+		for pair in points.windows(2) {
+			let (a, b) = (pair[0], pair[1]);
+			lines.draw_line([a.x, a.y, a.z], [b.x, b.y, b.z], tool.color);
+		}
+	}
+	unsafe {
+		let list = sys::igGetOverlayDrawList();
+		for (i, handle) in handles.iter().enumerate() {
+			if let Some(handle) = *handle {
+				let color = if tool.dragging == Some(i) { 0xff_ff_ff_ff } else { 0xff_ff_a0_40 };
+				sys::ImDrawList_AddCircleFilled(list, handle, 4., color, 8);
+			}
+		}
+	}
+
+	let mut active = tool.active;
+	ui.window(im_str!("Path"))
+		.size((320., 360.), ImGuiCond::FirstUseEver)
+		.opened(&mut active)
+		.build(|| {
+			ui.checkbox(im_str!("Closed"), &mut tool.path.closed);
+			if ui.small_button(im_str!("Clear")) {
+				tool.path.points.clear();
+			}
+			if let Some(file) = tool.file.clone() {
+				ui.same_line(0.);
+				if ui.small_button(im_str!("Save")) {
+					tool.status = Some(match tool.path.save(&file) {
+						Ok(()) => format!("Saved {}", file.display()),
+						Err(e) => format!("Saving {} failed: {}", file.display(), e),
+					});
+				}
+				ui.same_line(0.);
+				if ui.small_button(im_str!("Load")) {
+					tool.status = Some(match Waypoints::load(&file) {
+						Ok(x) => {
+							tool.path = x;
+							format!("Loaded {}", file.display())
+						},
+						Err(e) => format!("Loading {} failed: {}", file.display(), e),
+					});
+				}
+			}
+			if let Some(ref status) = tool.status {
+				ui.text_disabled(status);
+			}
+			ui.separator();
+			let mut removed = None;
+			for (i, point) in tool.path.points.iter_mut().enumerate() {
+				ui.drag_float3(&im_str!("##point{}", i), point).speed(0.05).build();
+				ui.same_line(0.);
+				if ui.small_button(&im_str!("x##remove{}", i)) {
+					removed = Some(i);
+				}
+			}
+			if let Some(i) = removed {
+				tool.path.points.remove(i);
+				tool.dragging = None;
+			}
+		});
+	tool.active = active;
+}
@@ -0,0 +1,129 @@
+use amethyst::{
+	ecs::prelude::*,
+	renderer::{DebugLines, Rgba},
+};
+use imgui::{ImGuiCond, Ui};
+
+use crate::{history::History, selection::SelectedEntity};
+
+/// What the physics panel shows about the selected entity's rigid body.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct RigidBodyInfo {
+	pub kind: String,
+	pub position: [f32; 3],
+	pub linear_velocity: [f32; 3],
+	pub angular_velocity: [f32; 3],
+	pub mass: f32,
+	pub sleeping: bool,
+}
+
+/// Glue between `PhysicsPanel` and the physics engine, e.g. nphysics through specs-physics.
+///
+/// Implemented by the game so the panel doesn't pin a physics crate version.
+pub trait PhysicsDebug: Send + Sync {
+	fn body(&self, world: &World, entity: Entity) -> Option<RigidBodyInfo>;
+	fn gravity(&self, world: &World) -> [f32; 3];
+	fn set_gravity(&self, world: &World, gravity: [f32; 3]);
+	fn contact_count(&self, world: &World) -> usize;
+	/// Collider outlines as world-space line segments.
+	fn collider_lines(&self, world: &World, lines: &mut Vec<([f32; 3], [f32; 3])>);
+}
+
+/// Physics debugging window drawn by `physics_window`.
+pub struct PhysicsPanel {
+	pub open: bool,
+	/// Draw collider wireframes into the `DebugLines` resource.
+	pub draw_colliders: bool,
+	pub collider_color: Rgba,
+	pub contacts: History,
+	backend: Option<Box<dyn PhysicsDebug>>,
+}
+
+impl Default for PhysicsPanel {
+	fn default() -> Self {
+		Self {
+			open: false,
+			draw_colliders: false,
+			collider_color: Rgba::green(),
+			contacts: History::new(240),
+			backend: None,
+		}
+	}
+}
+
+impl PhysicsPanel {
+	pub fn set_backend<B: PhysicsDebug + 'static>(&mut self, backend: B) { self.backend = Some(Box::new(backend)); }
+}
+
+fn vector(ui: &Ui, label: &str, x: [f32; 3]) { ui.text(format!("{}: ({:.3}, {:.3}, {:.3})", label, x[0], x[1], x[2])); }
+
+/// Samples contacts, draws collider wireframes and, while open, the panel itself.
+pub fn physics_window(ui: &Ui, world: &World) {
+	let mut panel = match world.res.try_fetch_mut::<PhysicsPanel>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let panel = &mut *panel;
+	let backend = match panel.backend {
+		Some(ref x) => x,
+		_ => return,
+	};
+	panel.contacts.push(backend.contact_count(world) as f32);
+	if panel.draw_colliders {
+		if let Some(mut debug_lines) = world.res.try_fetch_mut::<DebugLines>() {
+			let mut lines = Vec::new();
+			backend.collider_lines(world, &mut lines);
+			for (start, end) in lines {
+				debug_lines.draw_line(start, end, panel.collider_color);
+			}
+		}
+	}
+	if !panel.open {
+		return;
+	}
+
+	let mut open = true;
+	let mut draw_colliders = panel.draw_colliders;
+	ui.window(im_str!("Physics"))
+		.size((360., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			let mut gravity = backend.gravity(world);
+			if ui.input_float3(im_str!("Gravity"), &mut gravity).build() {
+				backend.set_gravity(world, gravity);
+			}
+			if ui.small_button(im_str!("Zero")) {
+				backend.set_gravity(world, [0., 0., 0.]);
+			}
+			ui.same_line(0.);
+			if ui.small_button(im_str!("Earth")) {
+				backend.set_gravity(world, [0., -9.81, 0.]);
+			}
+			ui.checkbox(im_str!("Collider wireframes"), &mut draw_colliders);
+
+			ui.separator();
+			let values = panel.contacts.to_vec();
+			ui.plot_lines(im_str!("Contacts"), &values)
+				.overlay_text(&im_str!("{}", panel.contacts.last().unwrap_or(0.)))
+				.scale_min(0.)
+				.graph_size((0., 50.))
+				.build();
+
+			ui.separator();
+			let selected = world.res.try_fetch::<SelectedEntity>().and_then(|x| x.0);
+			match selected.map(|x| (x, backend.body(world, x))) {
+				Some((entity, Some(body))) => {
+					ui.text(format!("Entity {} ({})", entity.id(), body.kind));
+					vector(ui, "Position", body.position);
+					vector(ui, "Linear velocity", body.linear_velocity);
+					vector(ui, "Angular velocity", body.angular_velocity);
+					ui.text(format!("Mass: {:.3}", body.mass));
+					ui.text(if body.sleeping { "Sleeping" } else { "Awake" });
+				},
+				Some((entity, None)) => ui.text_disabled(format!("Entity {} has no rigid body", entity.id())),
+				None => ui.text_disabled("Nothing selected"),
+			}
+		});
+	panel.open = open;
+	panel.draw_colliders = draw_colliders;
+}
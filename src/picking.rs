@@ -0,0 +1,237 @@
+use amethyst::{
+	core::{
+		nalgebra::{Matrix4, Point3, Vector3, Vector4},
+		GlobalTransform,
+	},
+	ecs::prelude::*,
+	renderer::{ActiveCamera, Camera},
+	shrev::{EventChannel, ReaderId},
+};
+use imgui::{ImVec2, Ui};
+
+use crate::{grid::GridPlane, selection::SelectedEntity, viewport::Viewport, ImguiState};
+
+/// A world-space ray through the cursor, starting on the camera's near plane.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PickRay {
+	pub origin: Point3<f32>,
+	pub direction: Vector3<f32>,
+}
+
+impl PickRay {
+	/// Where the ray meets `plane` through the origin, if it points towards it.
+	pub fn on_plane(&self, plane: GridPlane) -> Option<Point3<f32>> {
+		let (origin, direction) = match plane {
+			GridPlane::XY => (self.origin.z, self.direction.z),
+			GridPlane::XZ => (self.origin.y, self.direction.y),
+		};
+		if direction == 0. {
+			return None;
+		}
+		let distance = -origin / direction;
+		if distance < 0. {
+			return None;
+		}
+		Some(self.origin + self.direction * distance)
+	}
+}
+
+/// Published by `MousePickSystem` whenever the cursor moves over the game, or the left button goes down or up.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PickRequest {
+	/// Cursor position in game pixels.
+	pub position: (f32, f32),
+	pub ray: PickRay,
+	/// The left button went down this frame.
+	pub clicked: bool,
+	/// The left button is held.
+	pub pressed: bool,
+	/// The cursor is over the `Viewport` window rather than the game behind the UI.
+	pub in_viewport: bool,
+}
+
+/// Sent back by whatever does the hit testing for a `PickRequest`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PickResult {
+	pub entity: Option<Entity>,
+	pub point: Option<Point3<f32>>,
+	pub clicked: bool,
+}
+
+/// The latest `PickResult`, for highlighting what's under the cursor. Clicks go to `SelectedEntity`.
+#[derive(Default)]
+pub struct Picked {
+	pub hovered: Option<Entity>,
+	pub point: Option<Point3<f32>>,
+}
+
+/// The active camera, or the first one if there's no `ActiveCamera`.
+pub(crate) fn camera<'a>(
+	active: Option<Entity>,
+	cameras: &'a ReadStorage<Camera>,
+	transforms: &'a ReadStorage<GlobalTransform>,
+) -> Option<(&'a Camera, &'a GlobalTransform)> {
+	active
+		.and_then(|x| Some((cameras.get(x)?, transforms.get(x)?)))
+		.or_else(|| (cameras, transforms).join().next())
+}
+
+/// Maps world-space points to imgui's screen space through the active camera, for drawing editor overlays.
+///
+/// Points land on the `Viewport` image while it shows the game, otherwise on the whole screen.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ScreenProjection {
+	pub view_projection: Matrix4<f32>,
+	pub camera_position: Point3<f32>,
+	/// Top left corner and size of the game on screen.
+	pub rect: ((f32, f32), (f32, f32)),
+	pub in_viewport: bool,
+}
+
+impl ScreenProjection {
+	pub fn new(ui: &Ui, world: &World) -> Option<Self> {
+		let cameras = world.read_storage::<Camera>();
+		let transforms = world.read_storage::<GlobalTransform>();
+		let active = world.res.try_fetch::<ActiveCamera>().map(|x| x.entity);
+		let (camera, transform) = camera(active, &cameras, &transforms)?;
+		let (rect, in_viewport) = match world.res.try_fetch::<Viewport>().and_then(|x| x.rect) {
+			Some(x) => (x, true),
+			_ => {
+				let size = ui.frame_size().logical_size;
+				(((0., 0.), (size.0 as f32, size.1 as f32)), false)
+			},
+		};
+		let position = transform.0 * Vector4::new(0., 0., 0., 1.);
+		Some(Self {
+			view_projection: camera.proj * transform.0.try_inverse()?,
+			camera_position: Point3::new(position.x, position.y, position.z),
+			rect,
+			in_viewport,
+		})
+	}
+
+	/// `None` for points behind the camera.
+	pub fn project(&self, point: &Point3<f32>) -> Option<ImVec2> {
+		let clip = self.view_projection * Vector4::new(point.x, point.y, point.z, 1.);
+		if clip.w <= 0. {
+			return None;
+		}
+		let ((left, top), (width, height)) = self.rect;
+		let (x, y) = (clip.x / clip.w, clip.y / clip.w);
+		Some(ImVec2::new(left + (x + 1.) / 2. * width, top + (1. - y) / 2. * height))
+	}
+}
+
+/// Unprojects `position`, in pixels of a `size` sized view, through `camera` placed at `transform`.
+pub fn screen_ray(
+	camera: &Camera,
+	transform: &GlobalTransform,
+	position: (f32, f32),
+	size: (f32, f32),
+) -> Option<PickRay> {
+	if size.0 <= 0. || size.1 <= 0. {
+		return None;
+	}
+	let inverse = transform.0 * camera.proj.try_inverse()?;
+	let (x, y) = (position.0 / size.0 * 2. - 1., 1. - position.1 / size.1 * 2.);
+	let unproject = |z| {
+		let point = inverse * Vector4::new(x, y, z, 1.);
+		Point3::new(point.x, point.y, point.z) / point.w
+	};
+	let (near, far) = (unproject(-1.), unproject(1.));
+	Some(PickRay {
+		origin: near,
+		direction: (far - near).normalize(),
+	})
+}
+
+/// Turns the cursor into `PickRequest`s through the active camera and collects `PickResult`s into `Picked`.
+///
+/// The cursor counts when it's over the `Viewport` window, or over the game while imgui doesn't want the mouse.
+#[derive(Default)]
+pub struct MousePickSystem {
+	reader: Option<ReaderId<PickResult>>,
+	last: Option<(f32, f32)>,
+	pressed: bool,
+}
+
+impl<'s> System<'s> for MousePickSystem {
+	type SystemData = (
+		Read<'s, Option<ImguiState>>,
+		Option<Read<'s, Viewport>>,
+		Option<Read<'s, ActiveCamera>>,
+		ReadStorage<'s, Camera>,
+		ReadStorage<'s, GlobalTransform>,
+		Write<'s, EventChannel<PickRequest>>,
+		Read<'s, EventChannel<PickResult>>,
+		Write<'s, Picked>,
+		Write<'s, SelectedEntity>,
+	);
+
+	fn run(
+		&mut self,
+		(
+			imgui_state,
+			viewport,
+			active,
+			cameras,
+			transforms,
+			mut requests,
+			results,
+			mut picked,
+			mut selected,
+		): Self::SystemData,
+	) {
+		for result in results.read(self.reader.as_mut().expect("MousePickSystem::setup wasn't called")) {
+			picked.hovered = result.entity;
+			picked.point = result.point;
+			if result.clicked {
+				selected.0 = result.entity;
+			}
+		}
+
+		let imgui_state = match *imgui_state {
+			Some(ref x) => x,
+			_ => return,
+		};
+		let pressed = imgui_state.mouse_state.pressed.0;
+		let changed = pressed != self.pressed;
+		let clicked = pressed && changed;
+		self.pressed = pressed;
+
+		let (position, size, in_viewport) = match viewport {
+			Some(ref viewport) if viewport.hovered => match viewport.mouse {
+				Some(x) => (x, viewport.game_size, true),
+				_ => return,
+			},
+			_ if !imgui_state.wants_mouse() => {
+				let (x, y) = imgui_state.mouse_state.pos;
+				((x as f32, y as f32), imgui_state.imgui.display_size(), false)
+			},
+			_ => {
+				self.last = None;
+				return;
+			},
+		};
+		if self.last == Some(position) && !changed {
+			return;
+		}
+		self.last = Some(position);
+
+		let camera = camera(active.map(|x| x.entity), &cameras, &transforms);
+		if let Some(ray) = camera.and_then(|(camera, transform)| screen_ray(camera, transform, position, size)) {
+			requests.single_write(PickRequest {
+				position,
+				ray,
+				clicked,
+				pressed,
+				in_viewport,
+			});
+		}
+	}
+
+	fn setup(&mut self, res: &mut Resources) {
+		Self::SystemData::setup(res);
+		self.reader = Some(res.fetch_mut::<EventChannel<PickResult>>().register_reader());
+	}
+}
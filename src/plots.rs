@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImString, Ui};
+
+use crate::history::History;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PlotStyle {
+	Lines,
+	Histogram,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlotSeries {
+	pub samples: History,
+	pub style: PlotStyle,
+	/// Fixed scale; `None` fits the visible samples.
+	pub range: Option<(f32, f32)>,
+}
+
+impl PlotSeries {
+	fn new(capacity: usize) -> Self {
+		Self {
+			samples: History::new(capacity),
+			style: PlotStyle::Lines,
+			range: None,
+		}
+	}
+}
+
+/// Named time-series any system can push samples into.
+///
+/// ```rust,ignore
+/// plots.push("physics.contacts", contacts.len() as f32);
+/// ```
+pub struct Plots {
+	pub open: bool,
+	pub paused: bool,
+	pub window_length: usize,
+	pub series: BTreeMap<String, PlotSeries>,
+	filter: ImString,
+}
+
+impl Default for Plots {
+	fn default() -> Self {
+		Self {
+			open: false,
+			paused: false,
+			window_length: 240,
+			series: BTreeMap::new(),
+			filter: ImString::with_capacity(64),
+		}
+	}
+}
+
+impl Plots {
+	pub fn push<S: Into<String>>(&mut self, name: S, value: f32) {
+		if self.paused {
+			return;
+		}
+		let window_length = self.window_length;
+		self.series
+			.entry(name.into())
+			.or_insert_with(|| PlotSeries::new(window_length))
+			.samples
+			.push(value);
+	}
+
+	pub fn get(&self, name: &str) -> Option<&PlotSeries> { self.series.get(name) }
+
+	pub fn get_mut(&mut self, name: &str) -> Option<&mut PlotSeries> { self.series.get_mut(name) }
+
+	pub fn set_range(&mut self, name: &str, min: f32, max: f32) {
+		if let Some(series) = self.series.get_mut(name) {
+			series.range = Some((min, max));
+		}
+	}
+
+	pub fn clear(&mut self) {
+		for series in self.series.values_mut() {
+			series.samples.clear();
+		}
+	}
+}
+
+fn series_plot(ui: &Ui, name: &str, series: &mut PlotSeries) {
+	let values = series.samples.to_vec();
+	let (min, max) = series
+		.range
+		.unwrap_or_else(|| (series.samples.min().unwrap_or(0.), series.samples.max().unwrap_or(1.)));
+	let overlay = ImString::new(format!(
+		"{}: {:.3} (min {:.3}, max {:.3}, avg {:.3})",
+		name,
+		series.samples.last().unwrap_or(0.),
+		series.samples.min().unwrap_or(0.),
+		series.samples.max().unwrap_or(0.),
+		series.samples.average()
+	));
+	let label = ImString::new(format!("##plot_{}", name));
+	match series.style {
+		PlotStyle::Lines => ui
+			.plot_lines(&label, &values)
+			.overlay_text(&overlay)
+			.scale_min(min)
+			.scale_max(max)
+			.graph_size((0., 60.))
+			.build(),
+		PlotStyle::Histogram => ui
+			.plot_histogram(&label, &values)
+			.overlay_text(&overlay)
+			.scale_min(min)
+			.scale_max(max)
+			.graph_size((0., 60.))
+			.build(),
+	}
+
+	let mut histogram = series.style == PlotStyle::Histogram;
+	if ui.checkbox(&im_str!("Histogram##{}", name), &mut histogram) {
+		series.style = if histogram { PlotStyle::Histogram } else { PlotStyle::Lines };
+	}
+	ui.same_line(0.);
+	let mut fixed = series.range.is_some();
+	if ui.checkbox(&im_str!("Fixed range##{}", name), &mut fixed) {
+		series.range = if fixed { Some((min, max)) } else { None };
+	}
+	if let Some((ref mut min, ref mut max)) = series.range {
+		ui.same_line(0.);
+		ui.push_item_width(160.);
+		let mut range = [*min, *max];
+		if ui.input_float2(&im_str!("##range_{}", name), &mut range).build() {
+			*min = range[0];
+			*max = range[1].max(range[0]);
+		}
+		ui.pop_item_width();
+	}
+}
+
+pub fn plots_window(ui: &Ui, world: &World) {
+	let mut plots = match world.res.try_fetch_mut::<Plots>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !plots.open {
+		return;
+	}
+
+	let plots = &mut *plots;
+	let mut open = true;
+	ui.window(im_str!("Plots"))
+		.size((480., 520.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.checkbox(im_str!("Pause"), &mut plots.paused);
+			ui.same_line(0.);
+			if ui.small_button(im_str!("Clear")) {
+				plots.clear();
+			}
+			ui.same_line(0.);
+			ui.push_item_width(120.);
+			let mut window_length = plots.window_length as i32;
+			if ui.slider_int(im_str!("Samples"), &mut window_length, 16, 4096).build() {
+				plots.window_length = window_length as usize;
+				for series in plots.series.values_mut() {
+					series.samples.set_capacity(plots.window_length);
+				}
+			}
+			ui.pop_item_width();
+			ui.input_text(im_str!("Filter"), &mut plots.filter).build();
+			ui.separator();
+
+			let filter = plots.filter.to_str().to_lowercase();
+			for (name, series) in plots.series.iter_mut() {
+				if !filter.is_empty() && !name.to_lowercase().contains(&filter) {
+					continue;
+				}
+				if ui.collapsing_header(&ImString::new(name.clone())).default_open(true).build() {
+					series_plot(ui, name, series);
+				}
+			}
+		});
+	plots.open = open;
+}
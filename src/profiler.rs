@@ -0,0 +1,325 @@
+use std::{
+	collections::BTreeMap,
+	fs,
+	io,
+	path::Path,
+	sync::{Arc, Mutex},
+	thread,
+	time::{Duration, Instant},
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImString, Ui};
+use serde_json;
+
+use crate::tables::{export_buttons, Table};
+
+/// A timed piece of work, relative to when the `Profiler` was created.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Span {
+	pub name: String,
+	/// Name of the thread it ran on, or its id if it has none.
+	pub thread: String,
+	pub start: Duration,
+	pub duration: Duration,
+}
+
+struct ProfilerData {
+	epoch: Instant,
+	frame: Vec<Span>,
+	last_frame: Vec<Span>,
+	/// Every span since `start_trace`, while tracing.
+	trace: Option<Vec<Span>>,
+}
+
+/// Collects `Span`s from any thread; cloning it shares the same spans.
+///
+/// Time systems by wrapping them in `Profiled`, or other code with `scope`, and add `ProfilerSystem`
+/// at the end of the dispatcher to close each frame.
+#[derive(Clone)]
+pub struct Profiler {
+	data: Arc<Mutex<ProfilerData>>,
+}
+
+impl Default for Profiler {
+	fn default() -> Self {
+		Self {
+			data: Arc::new(Mutex::new(ProfilerData {
+				epoch: Instant::now(),
+				frame: Vec::new(),
+				last_frame: Vec::new(),
+				trace: None,
+			})),
+		}
+	}
+}
+
+impl Profiler {
+	pub fn record(&self, name: &str, start: Instant, end: Instant) {
+		let current = thread::current();
+		let thread = match current.name() {
+			Some(x) => x.to_owned(),
+			None => format!("{:?}", current.id()),
+		};
+		if let Ok(mut data) = self.data.lock() {
+			let span = Span {
+				name: name.to_owned(),
+				thread,
+				start: start.duration_since(data.epoch),
+				duration: end.duration_since(start),
+			};
+			if let Some(ref mut trace) = data.trace {
+				trace.push(span.clone());
+			}
+			data.frame.push(span);
+		}
+	}
+
+	/// Records a span from now until the returned guard is dropped.
+	pub fn scope<'a>(&'a self, name: &'a str) -> ProfileScope<'a> {
+		ProfileScope {
+			profiler: self,
+			name,
+			start: Instant::now(),
+		}
+	}
+
+	/// Makes the spans recorded since the last call available through `last_frame`.
+	pub fn end_frame(&self) {
+		if let Ok(mut data) = self.data.lock() {
+			let data = &mut *data;
+			data.last_frame.clear();
+			data.last_frame.append(&mut data.frame);
+		}
+	}
+
+	/// Spans of the last finished frame, in the order they ended.
+	pub fn last_frame(&self) -> Vec<Span> { self.data.lock().map(|x| x.last_frame.clone()).unwrap_or_default() }
+
+	/// Starts keeping every span for `save_trace`, dropping the previous trace.
+	pub fn start_trace(&self) {
+		if let Ok(mut data) = self.data.lock() {
+			data.trace = Some(Vec::new());
+		}
+	}
+
+	/// Stops tracing and returns the spans recorded since `start_trace`.
+	pub fn stop_trace(&self) -> Vec<Span> {
+		self.data.lock().ok().and_then(|mut x| x.trace.take()).unwrap_or_default()
+	}
+
+	pub fn is_tracing(&self) -> bool { self.data.lock().map(|x| x.trace.is_some()).unwrap_or(false) }
+
+	/// Number of spans in the running trace.
+	pub fn trace_len(&self) -> usize {
+		self.data.lock().ok().and_then(|x| x.trace.as_ref().map(|x| x.len())).unwrap_or(0)
+	}
+
+	/// Writes the running trace in Chrome's trace-event format, for chrome://tracing or Perfetto. Tracing goes on.
+	pub fn save_trace<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let spans = self.data.lock().ok().and_then(|x| x.trace.clone()).unwrap_or_default();
+		write_trace(&spans, path)
+	}
+}
+
+/// Writes `spans`, e.g. from `stop_trace`, like `Profiler::save_trace`.
+pub fn write_trace<P: AsRef<Path>>(spans: &[Span], path: P) -> io::Result<()> {
+	let source = serde_json::to_string(&trace_events(spans))
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+	fs::write(path, source)
+}
+
+fn micros(duration: Duration) -> f64 { duration.as_secs() as f64 * 1e6 + duration.subsec_nanos() as f64 / 1e3 }
+
+/// Complete ("X") events for `spans`, with one named track per thread.
+fn trace_events(spans: &[Span]) -> serde_json::Value {
+	let mut threads = BTreeMap::new();
+	for span in spans {
+		let next = threads.len();
+		threads.entry(span.thread.as_str()).or_insert(next);
+	}
+	let names = threads.iter().map(|(name, tid)| {
+		json!({ "name": "thread_name", "ph": "M", "pid": 1, "tid": tid, "args": { "name": name } })
+	});
+	let events = spans.iter().map(|span| {
+		json!({
+			"name": span.name,
+			"cat": "amethyst",
+			"ph": "X",
+			"ts": micros(span.start),
+			"dur": micros(span.duration),
+			"pid": 1,
+			"tid": threads[span.thread.as_str()],
+		})
+	});
+	json!({ "traceEvents": names.chain(events).collect::<Vec<_>>(), "displayTimeUnit": "ms" })
+}
+
+pub struct ProfileScope<'a> {
+	profiler: &'a Profiler,
+	name: &'a str,
+	start: Instant,
+}
+
+impl<'a> Drop for ProfileScope<'a> {
+	fn drop(&mut self) { self.profiler.record(self.name, self.start, Instant::now()); }
+}
+
+/// Wraps a system to record a span for each of its runs.
+pub struct Profiled<S> {
+	name: String,
+	system: S,
+}
+
+impl<S> Profiled<S> {
+	pub fn new(name: &str, system: S) -> Self {
+		Self {
+			name: name.to_owned(),
+			system,
+		}
+	}
+}
+
+impl<'s, S: System<'s>> System<'s> for Profiled<S> {
+	// Only reads the profiler, so wrapped systems still run in parallel
+	type SystemData = (S::SystemData, Read<'s, Profiler>);
+
+	fn run(&mut self, (data, profiler): Self::SystemData) {
+		let start = Instant::now();
+		self.system.run(data);
+		profiler.record(&self.name, start, Instant::now());
+	}
+
+	fn setup(&mut self, res: &mut Resources) {
+		self.system.setup(res);
+		<Read<'s, Profiler> as SystemData>::setup(res);
+	}
+}
+
+/// Closes the profiler's frame; add it after every `Profiled` system, e.g. behind a barrier.
+#[derive(Default)]
+pub struct ProfilerSystem;
+
+impl<'s> System<'s> for ProfilerSystem {
+	type SystemData = Read<'s, Profiler>;
+
+	fn run(&mut self, profiler: Self::SystemData) { profiler.end_frame(); }
+}
+
+/// Shows the last frame's spans and records Chrome traces; drawn by `profiler_window`.
+pub struct ProfilerPanel {
+	pub open: bool,
+	path: ImString,
+	status: Option<String>,
+	/// Spans of the last stopped trace, kept for "Save" until a new trace starts.
+	stopped: Option<Vec<Span>>,
+	table: Table,
+}
+
+impl Default for ProfilerPanel {
+	fn default() -> Self {
+		let mut path = ImString::with_capacity(256);
+		path.push_str("trace.json");
+		Self {
+			open: false,
+			path,
+			status: None,
+			stopped: None,
+			table: Table::default(),
+		}
+	}
+}
+
+pub fn profiler_window(ui: &Ui, world: &World) {
+	let mut panel = match world.res.try_fetch_mut::<ProfilerPanel>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let panel = &mut *panel;
+	if !panel.open {
+		return;
+	}
+	let profiler = match world.res.try_fetch::<Profiler>() {
+		Some(x) => x.clone(),
+		_ => return,
+	};
+
+	let mut open = true;
+	ui.window(im_str!("Profiler"))
+		.size((440., 400.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			let tracing = profiler.is_tracing();
+			if tracing {
+				if ui.button(im_str!("Stop trace"), (0., 0.)) {
+					panel.stopped = Some(profiler.stop_trace());
+					panel.status = None;
+				}
+			} else if ui.button(im_str!("Start trace"), (0., 0.)) {
+				profiler.start_trace();
+				panel.stopped = None;
+				panel.status = None;
+			}
+			if tracing || panel.stopped.is_some() {
+				ui.same_line(0.);
+				if ui.button(im_str!("Save"), (0., 0.)) {
+					let path = panel.path.to_str().to_owned();
+					let result = match panel.stopped {
+						Some(ref spans) if !tracing => write_trace(spans, &path),
+						_ => profiler.save_trace(&path),
+					};
+					panel.status = Some(match result {
+						Ok(()) => format!("Saved {}", path),
+						Err(e) => format!("Can't write {}: {}", path, e),
+					});
+				}
+				ui.same_line(0.);
+				match panel.stopped {
+					Some(ref spans) if !tracing => ui.text(format!("{} spans, stopped", spans.len())),
+					_ => ui.text(format!("{} spans", profiler.trace_len())),
+				}
+			}
+			ui.input_text(im_str!("File"), &mut panel.path).build();
+			if let Some(ref status) = panel.status {
+				ui.text_wrapped(&im_str!("{}", status));
+			}
+			ui.separator();
+
+			let mut rows = profiler.last_frame();
+			panel.table.sort(&mut rows, |a, b, column| match column {
+				0 => a.name.cmp(&b.name),
+				1 => a.thread.cmp(&b.thread),
+				2 => a.start.cmp(&b.start),
+				_ => a.duration.cmp(&b.duration),
+			});
+			let headers = ["Span", "Thread", "Start", "ms"];
+			let saved = export_buttons(ui, im_str!("profiler_spans"), &headers, || {
+				rows.iter()
+					.map(|x| {
+						vec![
+							x.name.clone(),
+							x.thread.clone(),
+							format!("{:.6}", micros(x.start) / 1e6),
+							format!("{:.3}", micros(x.duration) / 1e3),
+						]
+					})
+					.collect()
+			});
+			if saved.is_some() {
+				panel.status = saved;
+			}
+			panel.table.begin(ui, im_str!("spans"), &headers);
+			for span in &rows {
+				ui.text(span.name.as_str());
+				ui.next_column();
+				ui.text(span.thread.as_str());
+				ui.next_column();
+				ui.text(format!("{:.3}", micros(span.start) / 1e6));
+				ui.next_column();
+				ui.text(format!("{:.3}", micros(span.duration) / 1e3));
+				ui.next_column();
+			}
+			panel.table.end(ui);
+		});
+	panel.open = open;
+}
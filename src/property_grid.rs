@@ -0,0 +1,146 @@
+use imgui::{sys, ImStr, ImString, Ui};
+
+use crate::inspect::ImguiInspect;
+
+/// Label/value rows in two aligned columns, handed out by `property_grid`.
+#[derive(Copy, Clone)]
+pub struct PropertyGrid<'a, 'ui: 'a> {
+	ui: &'a Ui<'ui>,
+}
+
+/// Lays out the rows added by `build` in a label and a value column.
+///
+/// ```rust,ignore
+/// property_grid(ui, im_str!("emitter"), |grid| {
+///     grid.property("Rate", &mut emitter.rate).unit("/s").default(10.).build();
+///     grid.group("Color", || {
+///         grid.property("Start", &mut emitter.start_color).tooltip("Color at birth").build();
+///     });
+/// });
+/// ```
+pub fn property_grid<'a, 'ui, R, F>(ui: &'a Ui<'ui>, id: &ImStr, build: F) -> R
+where
+	F: FnOnce(PropertyGrid<'a, 'ui>) -> R,
+{
+	ui.columns(2, id, false);
+	let result = build(PropertyGrid { ui });
+	ui.columns(1, im_str!("##property_grid_end"), false);
+	result
+}
+
+impl<'a, 'ui> PropertyGrid<'a, 'ui> {
+	pub fn ui(&self) -> &'a Ui<'ui> { self.ui }
+
+	/// A collapsible group of indented rows, open by default.
+	pub fn group<F: FnOnce()>(&self, label: &str, build: F) {
+		let open = self.ui.collapsing_header(&ImString::new(label)).default_open(true).build();
+		self.ui.next_column();
+		self.ui.next_column();
+		if open {
+			unsafe { sys::igIndent(0.) };
+			build();
+			unsafe { sys::igUnindent(0.) };
+		}
+	}
+
+	fn label(&self, label: &str, tooltip: Option<&str>) {
+		self.ui.text(label);
+		if let Some(tooltip) = tooltip {
+			if self.ui.is_item_hovered() {
+				self.ui.tooltip(|| self.ui.text(tooltip));
+			}
+		}
+		self.ui.next_column();
+	}
+
+	/// A row with any widget as its value, filling the value column; returns what `widget` does.
+	pub fn row<R, F: FnOnce() -> R>(&self, label: &str, tooltip: Option<&str>, widget: F) -> R {
+		self.label(label, tooltip);
+		self.ui.push_item_width(-1.);
+		let result = widget();
+		self.ui.pop_item_width();
+		self.ui.next_column();
+		result
+	}
+
+	/// A row editing `value` with its `ImguiInspect` editor; finish it with `build`.
+	pub fn property<'v, T: ImguiInspect>(&self, label: &'v str, value: &'v mut T) -> Property<'a, 'ui, 'v, T> {
+		Property {
+			grid: *self,
+			label,
+			value,
+			unit: None,
+			tooltip: None,
+			default: None,
+		}
+	}
+}
+
+pub struct Property<'a, 'ui: 'a, 'v, T> {
+	grid: PropertyGrid<'a, 'ui>,
+	label: &'v str,
+	value: &'v mut T,
+	unit: Option<&'v str>,
+	tooltip: Option<&'v str>,
+	/// The default, and whether the value differs from it.
+	default: Option<(T, bool)>,
+}
+
+impl<'a, 'ui, 'v, T: ImguiInspect> Property<'a, 'ui, 'v, T> {
+	/// Shown after the value, e.g. "m/s" or "%".
+	pub fn unit(mut self, unit: &'v str) -> Self {
+		self.unit = Some(unit);
+		self
+	}
+
+	/// Shown when hovering the label.
+	pub fn tooltip(mut self, tooltip: &'v str) -> Self {
+		self.tooltip = Some(tooltip);
+		self
+	}
+
+	/// Adds a button resetting the value to `default`, shown while it differs.
+	pub fn default(mut self, default: T) -> Self
+	where
+		T: PartialEq,
+	{
+		let changed = *self.value != default;
+		self.default = Some((default, changed));
+		self
+	}
+
+	/// Returns whether the value changed, including by resetting it.
+	pub fn build(self) -> bool {
+		let ui = self.grid.ui;
+		self.grid.label(self.label, self.tooltip);
+
+		let unit = self.unit.map(ImString::new);
+		let reset = im_str!("Reset##{}", self.label);
+		let show_reset = self.default.as_ref().map_or(false, |x| x.1);
+		let spacing = unsafe { (*sys::igGetStyle()).item_spacing.x };
+		let mut trailing = 0.;
+		if let Some(ref unit) = unit {
+			trailing += ui.calc_text_size(unit, false, -1.).x + spacing;
+		}
+		if show_reset {
+			trailing += ui.calc_text_size(im_str!("Reset"), false, -1.).x + spacing * 3.;
+		}
+
+		ui.push_item_width(-trailing.max(1.));
+		let mut changed = self.value.inspect(ui, &im_str!("##{}", self.label));
+		ui.pop_item_width();
+		if let Some(ref unit) = unit {
+			ui.same_line(0.);
+			ui.text_disabled(unit);
+		}
+		if let (true, Some((default, _))) = (show_reset, self.default) {
+			ui.same_line(0.);
+			if ui.small_button(&reset) {
+				*self.value = default;
+				changed = true;
+			}
+		}
+		ui.next_column();
+		changed
+	}
+}
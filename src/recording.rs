@@ -0,0 +1,204 @@
+use std::{collections::VecDeque, fs, io, path::Path};
+
+use ron;
+
+use crate::{ImguiInput, ImguiState};
+
+/// Inputs received before a frame, and the delta time that frame was opened with.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RecordedFrame {
+	pub delta_seconds: f32,
+	pub inputs: Vec<ImguiInput>,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Recording {
+	pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+	pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		let source = fs::read_to_string(path)?;
+		ron::de::from_str(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+	}
+
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let source = ron::ser::to_string_pretty(self, Default::default())
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		fs::write(path, source)
+	}
+}
+
+enum Mode {
+	Idle,
+	Recording { recording: Recording, pending: Vec<ImguiInput> },
+	Playing { frames: VecDeque<RecordedFrame> },
+}
+
+/// Records the input fed to imgui by `handle_imgui_events`, frame by frame, and replays it.
+///
+/// While playing back, window input is ignored and each frame gets the recorded delta time, so a
+/// replay drives the UI exactly like the recorded session did.
+pub struct InputRecorder {
+	mode: Mode,
+}
+
+impl Default for InputRecorder {
+	fn default() -> Self { Self { mode: Mode::Idle } }
+}
+
+impl InputRecorder {
+	pub fn start_recording(&mut self) {
+		self.mode = Mode::Recording {
+			recording: Recording::default(),
+			pending: Vec::new(),
+		};
+	}
+
+	/// Stops recording, returning what was recorded so far.
+	pub fn stop_recording(&mut self) -> Option<Recording> {
+		match std::mem::replace(&mut self.mode, Mode::Idle) {
+			Mode::Recording { recording, .. } => Some(recording),
+			mode => {
+				self.mode = mode;
+				None
+			},
+		}
+	}
+
+	pub fn play(&mut self, recording: Recording) {
+		self.mode = Mode::Playing {
+			frames: recording.frames.into(),
+		};
+	}
+
+	pub fn stop_playing(&mut self) {
+		if let Mode::Playing { .. } = self.mode {
+			self.mode = Mode::Idle;
+		}
+	}
+
+	pub fn is_recording(&self) -> bool {
+		match self.mode {
+			Mode::Recording { .. } => true,
+			_ => false,
+		}
+	}
+
+	/// Whether a replay is in progress; it ends on its own after the last recorded frame.
+	pub fn is_playing(&self) -> bool {
+		match self.mode {
+			Mode::Playing { .. } => true,
+			_ => false,
+		}
+	}
+
+	/// Returns `false` when window input should be dropped because a replay is driving imgui.
+	pub(crate) fn record(&mut self, input: Option<ImguiInput>) -> bool {
+		match self.mode {
+			Mode::Recording { ref mut pending, .. } => {
+				pending.extend(input);
+				true
+			},
+			Mode::Playing { .. } => false,
+			Mode::Idle => true,
+		}
+	}
+
+	/// Called when a frame is opened; returns the delta time to open it with.
+	pub(crate) fn next_frame(&mut self, state: &mut ImguiState, delta_seconds: f32) -> f32 {
+		let (delta_seconds, finished) = match self.mode {
+			Mode::Idle => return delta_seconds,
+			Mode::Recording {
+				ref mut recording,
+				ref mut pending,
+			} => {
+				recording.frames.push(RecordedFrame {
+					delta_seconds,
+					inputs: std::mem::replace(pending, Vec::new()),
+				});
+				return delta_seconds;
+			},
+			Mode::Playing { ref mut frames } => match frames.pop_front() {
+				Some(frame) => {
+					for input in frame.inputs {
+						state.apply_input(input);
+					}
+					(frame.delta_seconds, frames.is_empty())
+				},
+				None => (delta_seconds, true),
+			},
+		};
+		if finished {
+			self.mode = Mode::Idle;
+		}
+		delta_seconds
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use imgui::{ImGuiCond, ImGuiWindowFlags};
+
+	use super::*;
+	use crate::testing::{tests::IMGUI, HeadlessImgui};
+
+	/// A window with one button under (50, 30); returns whether it was clicked.
+	fn button_frame(harness: &mut HeadlessImgui) -> bool {
+		let mut clicked = false;
+		harness.frame(|ui| {
+			ui.window(im_str!("Recorded"))
+				.position((0., 0.), ImGuiCond::Always)
+				.size((200., 100.), ImGuiCond::Always)
+				.flags(ImGuiWindowFlags::NoTitleBar | ImGuiWindowFlags::NoMove | ImGuiWindowFlags::NoResize)
+				.build(|| clicked = ui.button(im_str!("Click"), (100., 50.)));
+		});
+		clicked
+	}
+
+	/// Inputs fed before each frame: hover the button, press it, release it.
+	fn frame_inputs() -> Vec<Vec<ImguiInput>> {
+		vec![
+			vec![],
+			vec![ImguiInput::CursorMoved(50, 30)],
+			vec![ImguiInput::MouseButton(0, true)],
+			vec![ImguiInput::MouseButton(0, false)],
+			vec![],
+		]
+	}
+
+	#[test]
+	fn recording_round_trips_through_a_file() {
+		let _lock = IMGUI.lock().unwrap_or_else(|e| e.into_inner());
+
+		let mut harness = HeadlessImgui::new(800., 600.);
+		harness.recorder.start_recording();
+		let mut recorded = Vec::new();
+		for inputs in frame_inputs() {
+			for input in inputs {
+				harness.input(input);
+			}
+			recorded.push(button_frame(&mut harness));
+		}
+		let recording = harness.recorder.stop_recording().expect("Recorder wasn't recording");
+		drop(harness);
+		assert_eq!(recorded.iter().filter(|x| **x).count(), 1);
+
+		let path = std::env::temp_dir().join(format!("amethyst-imgui-recording-{}.ron", std::process::id()));
+		recording.save(&path).unwrap();
+		let loaded = Recording::load(&path).unwrap();
+		let _ = fs::remove_file(&path);
+		assert_eq!(loaded, recording);
+
+		let mut harness = HeadlessImgui::new(800., 600.);
+		harness.recorder.play(loaded);
+		let mut replayed = Vec::new();
+		for _ in frame_inputs() {
+			// Window input is ignored while the recording plays
+			harness.input(ImguiInput::CursorMoved(500, 500));
+			replayed.push(button_frame(&mut harness));
+		}
+		assert!(!harness.recorder.is_playing());
+		assert_eq!(replayed, recorded);
+	}
+}
@@ -0,0 +1,301 @@
+use std::{
+	io::{self, ErrorKind},
+	net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+	time::{Duration, Instant},
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, Ui};
+use tungstenite::{
+	handshake::{
+		server::{NoCallback, ServerHandshake},
+		HandshakeError,
+		MidHandshake,
+	},
+	server,
+	Error as WsError,
+	Message,
+	WebSocket,
+};
+
+use crate::{
+	draw_data::{CapturedDrawData, DrawDataCapture},
+	ImguiInput,
+	ImguiState,
+};
+
+const FONT_MESSAGE: u8 = 0;
+const FRAME_MESSAGE: u8 = 1;
+/// Connections that haven't finished the WebSocket handshake by then are dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Handshake = MidHandshake<ServerHandshake<TcpStream, NoCallback>>;
+
+struct Client {
+	address: SocketAddr,
+	socket: WebSocket<TcpStream>,
+	sent_font: bool,
+}
+
+/// The viewers connected to `RemoteDebugSystem` and its last connection error, shown by `remote_window`.
+#[derive(Default)]
+pub struct RemoteStatus {
+	pub open: bool,
+	pub address: Option<SocketAddr>,
+	pub clients: Vec<SocketAddr>,
+	/// Why the last handshake, accept or connection failed.
+	pub last_error: Option<String>,
+}
+
+/// Streams the overlay's draw data to remote viewers over WebSocket and feeds their input back.
+///
+/// Binary messages sent to the viewer (little endian):
+/// - font atlas: `0u8, width: u32, height: u32, rgba: [u8]`, sent once per client
+/// - frame: `1u8, display_w: f32, display_h: f32, list_count: u32`, then per list
+///   `vtx_count: u32, [pos: [f32; 2], uv: [f32; 2], col: u32]`, `idx_count: u32, [u16]`,
+///   `cmd_count: u32, [elem_count: u32, clip_rect: [f32; 4], texture_id: u64]`
+///
+/// Text messages accepted from the viewer, one command per line:
+/// `mouse <x> <y>`, `button <0-2> <0|1>`, `wheel <dy>`, `key <imgui key index> <0|1>`,
+/// `mods <ctrl> <shift> <alt> <super>`, `release` (let go of everything) and `char <utf-8 text>`.
+pub struct RemoteDebugSystem {
+	listener: TcpListener,
+	handshakes: Vec<(SocketAddr, Instant, Handshake)>,
+	clients: Vec<Client>,
+	/// Moved to `RemoteStatus` on the next run.
+	last_error: Option<String>,
+}
+
+impl RemoteDebugSystem {
+	pub fn bind<A: ToSocketAddrs>(address: A) -> io::Result<Self> {
+		let listener = TcpListener::bind(address)?;
+		listener.set_nonblocking(true)?;
+		Ok(Self {
+			listener,
+			handshakes: Vec::new(),
+			clients: Vec::new(),
+			last_error: None,
+		})
+	}
+
+	pub fn local_addr(&self) -> io::Result<SocketAddr> { self.listener.local_addr() }
+
+	fn update_status(&mut self, status: &mut RemoteStatus) {
+		status.address = self.listener.local_addr().ok();
+		status.clients = self.clients.iter().map(|x| x.address).collect();
+		if let Some(error) = self.last_error.take() {
+			status.last_error = Some(error);
+		}
+	}
+
+	fn handshake_done(
+		&mut self,
+		address: SocketAddr,
+		started: Instant,
+		result: Result<WebSocket<TcpStream>, HandshakeError<ServerHandshake<TcpStream, NoCallback>>>,
+	) {
+		match result {
+			Ok(socket) => {
+				let _ = socket.get_ref().set_nodelay(true);
+				self.clients.push(Client {
+					address,
+					socket,
+					sent_font: false,
+				});
+			},
+			Err(HandshakeError::Interrupted(handshake)) if started.elapsed() < HANDSHAKE_TIMEOUT => {
+				self.handshakes.push((address, started, handshake))
+			},
+			Err(HandshakeError::Interrupted(_)) => {
+				self.last_error = Some(format!("Handshake with {} timed out", address))
+			},
+			Err(HandshakeError::Failure(e)) => {
+				self.last_error = Some(format!("Handshake with {} failed: {}", address, e))
+			},
+		}
+	}
+
+	/// Accepts connections and advances their handshakes without blocking the frame.
+	fn accept(&mut self) {
+		for (address, started, handshake) in std::mem::replace(&mut self.handshakes, Vec::new()) {
+			self.handshake_done(address, started, handshake.handshake());
+		}
+		loop {
+			let (stream, address) = match self.listener.accept() {
+				Ok(x) => x,
+				Err(ref e) if e.kind() == ErrorKind::WouldBlock => return,
+				Err(e) => {
+					self.last_error = Some(format!("Accept failed: {}", e));
+					return;
+				},
+			};
+			// Accepted sockets don't inherit the listener's non-blocking mode everywhere
+			if let Err(e) = stream.set_nonblocking(true) {
+				self.last_error = Some(format!("Dropped {}: {}", address, e));
+				continue;
+			}
+			self.handshake_done(address, Instant::now(), server::accept(stream));
+		}
+	}
+}
+
+/// Queues `data`; what doesn't fit in the socket's buffer is sent by `write_pending` on later frames.
+fn queue(socket: &mut WebSocket<TcpStream>, data: Vec<u8>) -> Result<(), WsError> {
+	match socket.write_message(Message::Binary(data)) {
+		Err(WsError::Io(ref e)) if e.kind() == ErrorKind::WouldBlock => Ok(()),
+		x => x,
+	}
+}
+
+fn write_frame(data: &CapturedDrawData) -> Vec<u8> {
+	fn u32le(out: &mut Vec<u8>, x: u32) { out.extend_from_slice(&x.to_le_bytes()); }
+	fn f32le(out: &mut Vec<u8>, x: f32) { out.extend_from_slice(&x.to_bits().to_le_bytes()); }
+
+	let mut out = Vec::with_capacity(16 + data.total_vertices() * 20 + data.total_indices() * 2);
+	out.push(FRAME_MESSAGE);
+	f32le(&mut out, data.display_size[0]);
+	f32le(&mut out, data.display_size[1]);
+	u32le(&mut out, data.lists.len() as u32);
+	for list in &data.lists {
+		u32le(&mut out, list.vertices.len() as u32);
+		for vertex in &list.vertices {
+			f32le(&mut out, vertex.pos[0]);
+			f32le(&mut out, vertex.pos[1]);
+			f32le(&mut out, vertex.uv[0]);
+			f32le(&mut out, vertex.uv[1]);
+			u32le(&mut out, vertex.col);
+		}
+		u32le(&mut out, list.indices.len() as u32);
+		for index in &list.indices {
+			out.extend_from_slice(&index.to_le_bytes());
+		}
+		u32le(&mut out, list.commands.len() as u32);
+		for command in &list.commands {
+			u32le(&mut out, command.elem_count);
+			for x in &command.clip_rect {
+				f32le(&mut out, *x);
+			}
+			out.extend_from_slice(&(command.texture_id as u64).to_le_bytes());
+		}
+	}
+	out
+}
+
+fn parse_input(line: &str) -> Vec<ImguiInput> {
+	let mut parts = line.split_whitespace();
+	let command = parts.next().unwrap_or("");
+	let mut next = || parts.next().and_then(|x| x.parse::<f32>().ok()).unwrap_or(0.);
+	match command {
+		"mouse" => vec![ImguiInput::CursorMoved(next() as i32, next() as i32)],
+		"button" => vec![ImguiInput::MouseButton(next() as u8, next() != 0.)],
+		"wheel" => vec![ImguiInput::MouseWheel(next())],
+		"key" => vec![ImguiInput::Key(next() as u8, next() != 0.)],
+		"mods" => vec![
+			ImguiInput::Ctrl(next() != 0.),
+			ImguiInput::Shift(next() != 0.),
+			ImguiInput::Alt(next() != 0.),
+			ImguiInput::Super(next() != 0.),
+		],
+		"release" => vec![ImguiInput::ReleaseAll],
+		"char" => line["char".len()..].trim_start().chars().map(ImguiInput::Character).collect(),
+		_ => Vec::new(),
+	}
+}
+
+impl<'s> System<'s> for RemoteDebugSystem {
+	type SystemData = (Write<'s, Option<ImguiState>>, Write<'s, DrawDataCapture>, Write<'s, RemoteStatus>);
+
+	fn run(&mut self, (mut imgui_state, mut capture, mut status): Self::SystemData) {
+		self.accept();
+		capture.enabled = !self.clients.is_empty();
+		self.update_status(&mut status);
+		let imgui_state = match *imgui_state {
+			Some(ref mut x) => x,
+			_ => return,
+		};
+
+		let frame = capture.data.take().map(|x| write_frame(&x));
+		let mut font = None;
+		let mut disconnected = Vec::new();
+		for (index, client) in self.clients.iter_mut().enumerate() {
+			let result = (|| -> Result<bool, WsError> {
+				loop {
+					match client.socket.read_message() {
+						Ok(Message::Text(text)) => {
+							for input in text.lines().flat_map(parse_input) {
+								imgui_state.apply_input(input);
+							}
+						},
+						Ok(Message::Close(_)) => return Ok(false),
+						Ok(_) => {},
+						Err(WsError::Io(ref e)) if e.kind() == ErrorKind::WouldBlock => break,
+						Err(e) => return Err(e),
+					}
+				}
+
+				// Only send a frame once the previous ones are out, so slow viewers skip frames instead of lagging
+				let flushed = match client.socket.write_pending() {
+					Err(WsError::Io(ref e)) if e.kind() == ErrorKind::WouldBlock => false,
+					x => x.map(|_| true)?,
+				};
+				if !client.sent_font {
+					let font = font.get_or_insert_with(|| {
+						imgui_state.imgui.prepare_texture(|handle| {
+							let mut out = vec![FONT_MESSAGE];
+							out.extend_from_slice(&handle.width.to_le_bytes());
+							out.extend_from_slice(&handle.height.to_le_bytes());
+							out.extend_from_slice(handle.pixels);
+							out
+						})
+					});
+					queue(&mut client.socket, font.clone())?;
+					client.sent_font = true;
+				} else if let Some(frame) = frame.as_ref().filter(|_| flushed) {
+					queue(&mut client.socket, frame.clone())?;
+				}
+				Ok(true)
+			})();
+			match result {
+				Ok(true) => {},
+				Ok(false) => disconnected.push(index),
+				Err(e) => {
+					self.last_error = Some(format!("Dropped {}: {}", client.address, e));
+					disconnected.push(index);
+				},
+			}
+		}
+		for index in disconnected.into_iter().rev() {
+			self.clients.remove(index);
+		}
+		self.update_status(&mut status);
+	}
+}
+
+/// Lists the connected viewers and the last connection error while `RemoteStatus::open` is set.
+pub fn remote_window(ui: &Ui, world: &World) {
+	let mut status = match world.res.try_fetch_mut::<RemoteStatus>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !status.open {
+		return;
+	}
+	let mut open = true;
+	ui.window(im_str!("Remote debugging"))
+		.size((320., 160.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			if let Some(address) = status.address {
+				ui.text(format!("Listening on {}", address));
+			}
+			ui.text(format!("{} viewers", status.clients.len()));
+			for address in &status.clients {
+				ui.text(address.to_string());
+			}
+			if let Some(ref error) = status.last_error {
+				ui.separator();
+				ui.text_colored([1., 0.35, 0.35, 1.], error.as_str());
+			}
+		});
+	status.open = open;
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImString, Ui};
+
+use crate::{inspect::ImguiInspect, property_grid::property_grid};
+
+type Editor = Arc<dyn Fn(&Ui, &World, &ImString) + Send + Sync>;
+
+#[derive(Clone)]
+struct Parameter {
+	group: ImString,
+	name: ImString,
+	editor: Editor,
+}
+
+/// Parameters render plugins publish for tuning, e.g. bloom threshold or exposure, drawn by `rendering_window`.
+///
+/// Each one edits a field of a resource in place, usually the one the plugin fills its uniforms from.
+///
+/// ```rust,ignore
+/// let mut params = world.write_resource::<RenderParams>();
+/// params.add_float("Bloom", "Threshold", (0., 2.), |x: &mut BloomSettings| &mut x.threshold);
+/// params.add("Vignette", "Color", |x: &mut VignetteSettings| &mut x.color);
+/// ```
+#[derive(Default)]
+pub struct RenderParams {
+	pub open: bool,
+	params: Vec<Parameter>,
+}
+
+impl RenderParams {
+	fn push(&mut self, group: &str, name: &str, editor: Editor) {
+		let (group, name) = (ImString::new(group), ImString::new(name));
+		self.params.retain(|x| x.group != group || x.name != name);
+		self.params.push(Parameter { group, name, editor });
+	}
+
+	/// A slider between `range` for the `f32` that `field` picks out of resource `R`.
+	pub fn add_float<R>(&mut self, group: &str, name: &str, range: (f32, f32), field: fn(&mut R) -> &mut f32)
+	where
+		R: Send + Sync + 'static,
+	{
+		self.push(
+			group,
+			name,
+			Arc::new(move |ui, world, label| {
+				if let Some(mut resource) = world.res.try_fetch_mut::<R>() {
+					ui.slider_float(label, field(&mut *resource), range.0, range.1).build();
+				}
+			}),
+		);
+	}
+
+	/// Any `ImguiInspect` value, e.g. `[f32; 3]` for vectors or `[f32; 4]` for colors.
+	pub fn add<R, T>(&mut self, group: &str, name: &str, field: fn(&mut R) -> &mut T)
+	where
+		R: Send + Sync + 'static,
+		T: ImguiInspect + 'static,
+	{
+		self.push(
+			group,
+			name,
+			Arc::new(move |ui, world, label| {
+				if let Some(mut resource) = world.res.try_fetch_mut::<R>() {
+					field(&mut *resource).inspect(ui, label);
+				}
+			}),
+		);
+	}
+
+	pub fn remove_group(&mut self, group: &str) { self.params.retain(|x| x.group.to_str() != group); }
+}
+
+pub fn rendering_window(ui: &Ui, world: &World) {
+	// Snapshot so the editors can fetch their resources, which may include this one
+	let params = match world.res.try_fetch::<RenderParams>() {
+		Some(ref x) if x.open => x.params.clone(),
+		_ => return,
+	};
+
+	let mut open = true;
+	ui.window(im_str!("Rendering"))
+		.size((340., 400.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			if params.is_empty() {
+				ui.text_disabled("No parameters published");
+			}
+			let mut groups = Vec::<&ImString>::new();
+			for param in &params {
+				if !groups.contains(&&param.group) {
+					groups.push(&param.group);
+				}
+			}
+			for group in groups {
+				if !ui.collapsing_header(group).default_open(true).build() {
+					continue;
+				}
+				property_grid(ui, group, |grid| {
+					for param in params.iter().filter(|x| x.group == *group) {
+						grid.row(param.name.to_str(), None, || {
+							(param.editor)(ui, world, &im_str!("##{}{}", param.name, param.group))
+						});
+					}
+				});
+			}
+		});
+	if !open {
+		if let Some(mut x) = world.res.try_fetch_mut::<RenderParams>() {
+			x.open = false;
+		}
+	}
+}
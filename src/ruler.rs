@@ -0,0 +1,114 @@
+use amethyst::{
+	core::nalgebra::Point3,
+	ecs::prelude::*,
+	shrev::{EventChannel, ReaderId},
+};
+use imgui::{sys, ImVec2, Ui};
+
+use crate::{
+	grid::GridPlane,
+	picking::{PickRequest, ScreenProjection},
+};
+
+/// Measures between two clicks on the game, drawn by `ruler`.
+///
+/// Clicks come from `MousePickSystem` and are placed where their ray meets `plane` through the origin.
+/// The first click starts a measurement, the second ends it and the next one starts over.
+pub struct Ruler {
+	pub active: bool,
+	pub plane: GridPlane,
+	pub start: Option<Point3<f32>>,
+	pub end: Option<Point3<f32>>,
+	/// Where the cursor is on the plane, to measure to while there's no end yet.
+	pub cursor: Option<Point3<f32>>,
+	reader: Option<ReaderId<PickRequest>>,
+}
+
+impl Default for Ruler {
+	fn default() -> Self {
+		Self {
+			active: false,
+			plane: GridPlane::XY,
+			start: None,
+			end: None,
+			cursor: None,
+			reader: None,
+		}
+	}
+}
+
+/// Line color, ABGR.
+const COLOR: u32 = 0xff_40_d0_ff;
+
+pub fn ruler(ui: &Ui, world: &World) {
+	let mut ruler = match world.res.try_fetch_mut::<Ruler>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let ruler = &mut *ruler;
+	let mut requests = match world.res.try_fetch_mut::<EventChannel<PickRequest>>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let reader = match ruler.reader {
+		Some(ref mut x) => x,
+		_ => {
+			ruler.reader = Some(requests.register_reader());
+			return;
+		},
+	};
+	let requests = requests.read(reader).cloned().collect::<Vec<_>>();
+	if !ruler.active {
+		ruler.start = None;
+		ruler.end = None;
+		ruler.cursor = None;
+		return;
+	}
+	for request in requests {
+		let point = request.ray.on_plane(ruler.plane);
+		ruler.cursor = point;
+		if let (true, Some(point)) = (request.clicked, point) {
+			if ruler.start.is_none() || ruler.end.is_some() {
+				ruler.start = Some(point);
+				ruler.end = None;
+			} else {
+				ruler.end = Some(point);
+			}
+		}
+	}
+
+	let (start, end) = match (ruler.start, ruler.end.or(ruler.cursor)) {
+		(Some(start), Some(end)) => (start, end),
+		_ => return,
+	};
+	let projection = match ScreenProjection::new(ui, world) {
+		Some(x) => x,
+		_ => return,
+	};
+	let (a, b) = match (projection.project(&start), projection.project(&end)) {
+		(Some(a), Some(b)) => (a, b),
+		_ => return,
+	};
+	let delta = end - start;
+	let angle = match ruler.plane {
+		GridPlane::XY => delta.y.atan2(delta.x),
+		GridPlane::XZ => delta.z.atan2(delta.x),
+	};
+	let label = format!(
+		"{:.2}\n{:.1}°\nd ({:.2}, {:.2}, {:.2})",
+		delta.norm(),
+		angle.to_degrees(),
+		delta.x,
+		delta.y,
+		delta.z
+	);
+	unsafe {
+		let list = sys::igGetOverlayDrawList();
+		sys::ImDrawList_AddLine(list, a, b, COLOR, 2.);
+		sys::ImDrawList_AddCircleFilled(list, a, 3., COLOR, 8);
+		sys::ImDrawList_AddCircleFilled(list, b, 3., COLOR, 8);
+		let position = ImVec2::new((a.x + b.x) / 2. + 8., (a.y + b.y) / 2. + 8.);
+		let text = label.as_ptr() as *const _;
+		sys::ImDrawList_AddText(list, position, COLOR, text, text.add(label.len()));
+	}
+}
@@ -0,0 +1,61 @@
+use amethyst::renderer::Event;
+use gfx::{handle::RenderTargetView, CommandBuffer, Encoder, Factory};
+use imgui::{FrameSize, Ui};
+use imgui_gfx_renderer::{Renderer, Shaders};
+
+use crate::{builtin_windows, create_imgui, error::ImguiRenderError, process_event, ImguiState};
+
+/// The overlay without the ECS: for custom game loops that drive amethyst's renderer (or plain gfx)
+/// themselves and can't use the `DrawUi` pass and `open_frame`/`close_frame`.
+///
+/// ```rust,ignore
+/// let mut runtime = ImguiRuntime::new(&mut factory, overlay_target)?;
+/// runtime.handle_event(&event);
+/// runtime.frame(&mut factory, &mut encoder, (width, height), delta_seconds, |ui| {
+/// 	ui.show_demo_window(&mut true);
+/// })?;
+/// ```
+pub struct ImguiRuntime<R: gfx::Resources> {
+	state: ImguiState,
+	renderer: Renderer<R>,
+}
+
+impl<R: gfx::Resources> ImguiRuntime<R> {
+	/// Renders into `target`, which should be cleared before each frame and composited like `DrawUi` does.
+	pub fn new<F: Factory<R>>(
+		factory: &mut F,
+		target: RenderTargetView<R, gfx::format::Rgba8>,
+	) -> Result<Self, ImguiRenderError> {
+		let mut imgui = create_imgui(true);
+		let renderer = Renderer::init(&mut imgui, factory, Shaders::GlSl130, target)?;
+		Ok(Self {
+			state: ImguiState::with_platform(imgui, (0, 0), true),
+			renderer,
+		})
+	}
+
+	pub fn handle_event(&mut self, event: &Event) { process_event(&mut self.state, None, event); }
+
+	pub fn state_mut(&mut self) -> &mut ImguiState { &mut self.state }
+
+	pub fn update_render_target(&mut self, target: RenderTargetView<R, gfx::format::Rgba8>) {
+		self.renderer.update_render_target(target);
+	}
+
+	/// Builds a frame of `size` logical pixels with `build` and records its draw calls into `encoder`.
+	pub fn frame<F: Factory<R>, C: CommandBuffer<R>, B: FnOnce(&Ui)>(
+		&mut self,
+		factory: &mut F,
+		encoder: &mut Encoder<R, C>,
+		size: (f32, f32),
+		delta_seconds: f32,
+		build: B,
+	) -> Result<(), ImguiRenderError> {
+		self.state.size = (size.0 as u16, size.1 as u16);
+		let ui = self.state.imgui.frame(FrameSize::new(f64::from(size.0), f64::from(size.1), 1.), delta_seconds);
+		build(&ui);
+		builtin_windows(&ui, &mut self.state.show_demo_window, &mut self.state.show_metrics_window);
+		self.renderer.render(ui, factory, encoder)?;
+		Ok(())
+	}
+}
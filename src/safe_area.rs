@@ -0,0 +1,126 @@
+use amethyst::ecs::prelude::*;
+use imgui::{sys, ImVec2, Ui};
+
+use crate::layout::Anchor;
+
+/// Part of the screen HUD windows should stay inside, for TV overscan and notched displays.
+///
+/// The game's letterboxed rect is shrunk by `margin` (a fraction of its size on every side, e.g. 0.05 for the
+/// usual 90% title safe area) and then by `insets` in pixels.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SafeArea {
+	pub margin: f32,
+	/// Left, top, right and bottom, e.g. the display's notch and rounded corners.
+	pub insets: [f32; 4],
+	/// Aspect ratio the game is letterboxed or pillarboxed to, if any.
+	pub aspect: Option<f32>,
+}
+
+impl Default for SafeArea {
+	fn default() -> Self {
+		Self {
+			margin: 0.,
+			insets: [0.; 4],
+			aspect: None,
+		}
+	}
+}
+
+impl SafeArea {
+	/// The 90% title safe area of the TV guidelines.
+	pub fn title_safe() -> Self {
+		Self {
+			margin: 0.05,
+			..Self::default()
+		}
+	}
+
+	/// The 93% action safe area of the TV guidelines.
+	pub fn action_safe() -> Self {
+		Self {
+			margin: 0.035,
+			..Self::default()
+		}
+	}
+
+	/// Top left corner and size of the safe area on a screen of `size`.
+	pub fn rect(&self, size: (f32, f32)) -> ((f32, f32), (f32, f32)) {
+		let (mut left, mut top, mut width, mut height) = (0., 0., size.0, size.1);
+		if let Some(aspect) = self.aspect.filter(|x| *x > 0.) {
+			if width / height > aspect {
+				width = height * aspect;
+				left = (size.0 - width) / 2.;
+			} else {
+				height = width / aspect;
+				top = (size.1 - height) / 2.;
+			}
+		}
+		let margin = self.margin.max(0.).min(0.5);
+		let insets = &self.insets;
+		let left = left + width * margin + insets[0];
+		let top = top + height * margin + insets[1];
+		let width = (width * (1. - margin * 2.) - insets[0] - insets[2]).max(0.);
+		let height = (height * (1. - margin * 2.) - insets[1] - insets[3]).max(0.);
+		((left, top), (width, height))
+	}
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Corner {
+	TopLeft,
+	Top,
+	TopRight,
+	Left,
+	Center,
+	Right,
+	BottomLeft,
+	Bottom,
+	BottomRight,
+}
+
+impl Corner {
+	/// Where in the screen and in the window the corner is, from 0 to 1.
+	pub(crate) fn pivot(self) -> (f32, f32) {
+		match self {
+			Corner::TopLeft => (0., 0.),
+			Corner::Top => (0.5, 0.),
+			Corner::TopRight => (1., 0.),
+			Corner::Left => (0., 0.5),
+			Corner::Center => (0.5, 0.5),
+			Corner::Right => (1., 0.5),
+			Corner::BottomLeft => (0., 1.),
+			Corner::Bottom => (0.5, 1.),
+			Corner::BottomRight => (1., 1.),
+		}
+	}
+}
+
+/// The `SafeArea` resource's rect on the current frame, or the whole screen without one.
+pub fn safe_rect(ui: &Ui, world: &World) -> ((f32, f32), (f32, f32)) {
+	let (width, height) = ui.frame_size().logical_size;
+	let area = world.res.try_fetch::<SafeArea>().map(|x| *x).unwrap_or_default();
+	area.rect((width as f32, height as f32))
+}
+
+/// Places the next window at `corner` of the safe area, moved inwards by `offset`.
+///
+/// ```rust,ignore
+/// amethyst_imgui::safe_area::next_window_in_safe_area(ui, world, Corner::TopRight, (10., 10.));
+/// ui.window(im_str!("Score")).always_auto_resize(true).build(|| { /* ... */ });
+/// ```
+pub fn next_window_in_safe_area(ui: &Ui, world: &World, corner: Corner, offset: (f32, f32)) {
+	Anchor::new(corner, offset).apply_in(safe_rect(ui, world));
+}
+
+/// Outlines the safe area on top of everything, to check layouts against it.
+pub fn show_safe_area(ui: &Ui, world: &World) {
+	let ((left, top), (width, height)) = safe_rect(ui, world);
+	unsafe {
+		let list = sys::igGetOverlayDrawList();
+		let corners = [(left, top), (left + width, top), (left + width, top + height), (left, top + height)];
+		for i in 0..corners.len() {
+			let (a, b) = (corners[i], corners[(i + 1) % corners.len()]);
+			sys::ImDrawList_AddLine(list, ImVec2::new(a.0, a.1), ImVec2::new(b.0, b.1), 0xc000_ffff, 1.);
+		}
+	}
+}
@@ -0,0 +1,96 @@
+use std::{fs, io, marker::PhantomData, path::PathBuf};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, Ui};
+use ron;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{self, Value};
+
+use crate::value_editor::edit_value;
+
+/// Editor for save files of type `T` stored as RON, drawn by `save_inspector_window::<T>`.
+///
+/// The save is edited as a generic value tree, then checked by deserializing it back into `T` before writing.
+pub struct SaveInspector<T> {
+	pub open: bool,
+	pub path: PathBuf,
+	value: Option<Value>,
+	status: Option<String>,
+	_marker: PhantomData<fn() -> T>,
+}
+
+impl<T> SaveInspector<T> {
+	pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+		Self {
+			open: false,
+			path: path.into(),
+			value: None,
+			status: None,
+			_marker: PhantomData,
+		}
+	}
+}
+
+fn invalid<E: ToString>(e: E) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, e.to_string()) }
+
+impl<T: Serialize + DeserializeOwned> SaveInspector<T> {
+	pub fn load(&mut self) -> io::Result<()> {
+		let save: T = ron::de::from_str(&fs::read_to_string(&self.path)?).map_err(invalid)?;
+		self.value = Some(serde_json::to_value(&save).map_err(invalid)?);
+		Ok(())
+	}
+
+	pub fn save(&self) -> io::Result<()> {
+		let value = match self.value {
+			Some(ref x) => x.clone(),
+			None => return Ok(()),
+		};
+		let save: T = serde_json::from_value(value).map_err(invalid)?;
+		fs::write(&self.path, ron::ser::to_string_pretty(&save, Default::default()).map_err(invalid)?)
+	}
+}
+
+pub fn save_inspector_window<T>(ui: &Ui, world: &World)
+where
+	T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	let mut inspector = match world.res.try_fetch_mut::<SaveInspector<T>>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let inspector = &mut *inspector;
+	if !inspector.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Save game"))
+		.size((420., 520.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.text(format!("{}", inspector.path.display()));
+			if ui.small_button(im_str!("Load")) {
+				inspector.status = Some(match inspector.load() {
+					Ok(()) => "Loaded".to_owned(),
+					Err(e) => format!("Loading failed: {}", e),
+				});
+			}
+			if inspector.value.is_some() {
+				ui.same_line(0.);
+				if ui.small_button(im_str!("Save")) {
+					inspector.status = Some(match inspector.save() {
+						Ok(()) => "Saved".to_owned(),
+						Err(e) => format!("Not saved, the edited value doesn't fit the save type: {}", e),
+					});
+				}
+			}
+			if let Some(ref status) = inspector.status {
+				ui.text_wrapped(&im_str!("{}", status));
+			}
+			ui.separator();
+			if let Some(ref mut value) = inspector.value {
+				edit_value(ui, "save", value);
+			}
+		});
+	inspector.open = open;
+}
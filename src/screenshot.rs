@@ -0,0 +1,84 @@
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	thread,
+};
+
+use amethyst::renderer::{Encoder, Factory, Resources};
+use gfx::{self, format::ChannelType, traits::Factory as _};
+use image;
+
+use crate::error::ImguiRenderError;
+
+/// Copy of the overlay's render target on its way back from the GPU.
+pub(crate) struct Readback {
+	path: PathBuf,
+	buffer: gfx::handle::Buffer<Resources, [u8; 4]>,
+	size: (u16, u16),
+	linear: bool,
+}
+
+/// Records a copy of `texture` into a download buffer; read it with `finish` once the encoder was flushed.
+///
+/// `linear` tells whether the overlay was rendered in linear space and needs to be gamma-encoded.
+pub(crate) fn queue(
+	factory: &mut Factory,
+	encoder: &mut Encoder,
+	texture: &gfx::handle::Texture<Resources, gfx::format::R8_G8_B8_A8>,
+	path: PathBuf,
+	linear: bool,
+) -> Result<Readback, ImguiRenderError> {
+	let (width, height, _, _) = texture.get_info().kind.get_dimensions();
+	let buffer = factory
+		.create_download_buffer::<[u8; 4]>(usize::from(width) * usize::from(height))
+		.map_err(|e| ImguiRenderError::Readback(format!("{:?}", e)))?;
+	encoder
+		.copy_texture_to_buffer_raw(
+			texture.raw(),
+			None,
+			texture.get_info().to_raw_image_info(ChannelType::Unorm, 0),
+			buffer.raw(),
+			0,
+		)
+		.map_err(|e| ImguiRenderError::Readback(format!("{:?}", e)))?;
+	Ok(Readback {
+		path,
+		buffer,
+		size: (width, height),
+		linear,
+	})
+}
+
+/// Maps the download buffer and encodes the PNG on a background thread, which reports failures to `errors`.
+pub(crate) fn finish(factory: &mut Factory, readback: Readback, errors: Arc<Mutex<Option<ImguiRenderError>>>) {
+	let linear = readback.linear;
+	let (width, height) = readback.size;
+	let pixels = match factory.read_mapping(&readback.buffer) {
+		Ok(mapping) => {
+			let mut pixels = Vec::with_capacity(usize::from(width) * usize::from(height) * 4);
+			// The overlay is rendered in linear space for the sRGB framebuffer
+			let encode = |c: u8| ((f32::from(c) / 255.).powf(1. / 2.2) * 255.).round() as u8;
+			for x in mapping.iter() {
+				if linear {
+					pixels.extend_from_slice(&[encode(x[0]), encode(x[1]), encode(x[2]), x[3]]);
+				} else {
+					pixels.extend_from_slice(x);
+				}
+			}
+			pixels
+		},
+		Err(e) => {
+			let e = ImguiRenderError::Readback(format!("can't map {}: {:?}", readback.path.display(), e));
+			*errors.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+			return;
+		},
+	};
+
+	let path = readback.path;
+	thread::spawn(move || {
+		if let Err(e) = image::save_buffer(&path, &pixels, u32::from(width), u32::from(height), image::RGBA(8)) {
+			let e = ImguiRenderError::Readback(format!("can't save {}: {}", path.display(), e));
+			*errors.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+		}
+	});
+}
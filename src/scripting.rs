@@ -0,0 +1,282 @@
+use std::{
+	collections::HashSet,
+	fs,
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	time::SystemTime,
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImString, Ui};
+use rhai::{Engine, AST};
+
+use crate::{
+	code_view::{code_editor, Language},
+	debug_menus::{MenuValue, MenuValues},
+};
+
+enum Command {
+	BeginWindow(String),
+	EndWindow,
+	Text(String),
+	Separator,
+	SameLine,
+	Button(String),
+	Checkbox(String, String),
+	Slider(String, String, f32, f32),
+}
+
+/// What scripts see of the frame: commands they emitted, and last frame's results.
+#[derive(Default)]
+struct Frame {
+	commands: Vec<Command>,
+	clicked: HashSet<String>,
+	values: Vec<(String, MenuValue)>,
+}
+
+impl Frame {
+	fn value(&self, name: &str) -> Option<&MenuValue> { self.values.iter().find(|x| x.0 == name).map(|x| &x.1) }
+}
+
+struct Script {
+	path: PathBuf,
+	modified: Option<SystemTime>,
+	ast: Option<AST>,
+	/// Reading, compiling or saving the file failed; cleared by the next successful compile.
+	error: Option<String>,
+	/// The last run failed; cleared by the next run that doesn't.
+	runtime_error: Option<String>,
+	/// Text in the editor, replaced whenever the file changes on disk.
+	source: ImString,
+}
+
+/// Rhai scripts executed every frame by `run_scripts`, reloaded when their file changes.
+///
+/// Scripts build UI through a small subset of the `Ui` API:
+///
+/// ```rhai
+/// begin_window("Cheats");
+/// if button("Heal") { text("healed!"); }
+/// let speed = slider("Speed", "player.speed", 0.0, 10.0);
+/// end_window();
+/// ```
+///
+/// `button` reports clicks from the previous frame, and bound values live in `MenuValues`.
+pub struct Scripts {
+	engine: Engine,
+	frame: Arc<Mutex<Frame>>,
+	scripts: Vec<Script>,
+	pub open: bool,
+}
+
+impl Default for Scripts {
+	fn default() -> Self {
+		let frame = Arc::new(Mutex::new(Frame::default()));
+		let mut engine = Engine::new();
+
+		macro_rules! register {
+			($name:expr, |$frame:ident $(, $arg:ident: $ty:ty)*| $body:expr) => {{
+				let frame = frame.clone();
+				engine.register_fn($name, move |$($arg: $ty),*| {
+					let mut $frame = frame.lock().unwrap();
+					$body
+				});
+			}};
+		}
+
+		register!("begin_window", |f, title: &str| f.commands.push(Command::BeginWindow(title.into())));
+		register!("end_window", |f| f.commands.push(Command::EndWindow));
+		register!("text", |f, text: &str| f.commands.push(Command::Text(text.into())));
+		register!("separator", |f| f.commands.push(Command::Separator));
+		register!("same_line", |f| f.commands.push(Command::SameLine));
+		register!("button", |f, label: &str| {
+			f.commands.push(Command::Button(label.into()));
+			f.clicked.contains(label)
+		});
+		register!("checkbox", |f, label: &str, name: &str| {
+			f.commands.push(Command::Checkbox(label.into(), name.into()));
+			match f.value(name) {
+				Some(MenuValue::Bool(x)) => *x,
+				_ => false,
+			}
+		});
+		register!("slider", |f, label: &str, name: &str, min: f64, max: f64| {
+			f.commands.push(Command::Slider(label.into(), name.into(), min as f32, max as f32));
+			match f.value(name) {
+				Some(MenuValue::Float(x)) => f64::from(*x),
+				_ => min,
+			}
+		});
+		register!("value", |f, name: &str| match f.value(name) {
+			Some(MenuValue::Float(x)) => f64::from(*x),
+			Some(MenuValue::Int(x)) => f64::from(*x),
+			Some(MenuValue::Bool(x)) => if *x { 1. } else { 0. },
+			_ => 0.,
+		});
+
+		Self {
+			engine,
+			frame,
+			scripts: Vec::new(),
+			open: false,
+		}
+	}
+}
+
+impl Scripts {
+	pub fn add<P: Into<PathBuf>>(&mut self, path: P) {
+		self.scripts.push(Script {
+			path: path.into(),
+			modified: None,
+			ast: None,
+			error: None,
+			runtime_error: None,
+			source: ImString::default(),
+		});
+	}
+
+	/// Mutable access to the engine, to register game-specific functions.
+	pub fn engine_mut(&mut self) -> &mut Engine { &mut self.engine }
+
+	fn reload(&mut self) {
+		let engine = &self.engine;
+		for script in &mut self.scripts {
+			let modified = fs::metadata(&script.path).and_then(|x| x.modified()).ok();
+			if modified.is_some() && modified == script.modified {
+				continue;
+			}
+			script.modified = modified;
+			match fs::read_to_string(&script.path) {
+				Ok(source) => {
+					match engine.compile(&source) {
+						Ok(ast) => {
+							script.ast = Some(ast);
+							script.error = None;
+						},
+						Err(e) => script.error = Some(format!("{}", e)),
+					}
+					script.source = ImString::new(source);
+				},
+				Err(e) => script.error = Some(format!("{}", e)),
+			}
+		}
+	}
+}
+
+fn render(ui: &Ui, commands: &[Command], values: &mut MenuValues, clicked: &mut HashSet<String>) {
+	let mut index = 0;
+	while index < commands.len() {
+		match commands[index] {
+			Command::BeginWindow(ref title) => {
+				let end = commands[index..]
+					.iter()
+					.position(|x| match x {
+						Command::EndWindow => true,
+						_ => false,
+					})
+					.map_or(commands.len(), |x| index + x);
+				ui.window(&ImString::new(title.as_str()))
+					.size((300., 0.), ImGuiCond::FirstUseEver)
+					.always_auto_resize(true)
+					.build(|| render(ui, &commands[index + 1..end], values, clicked));
+				index = end;
+			},
+			Command::EndWindow => {},
+			Command::Text(ref text) => ui.text(text.as_str()),
+			Command::Separator => ui.separator(),
+			Command::SameLine => ui.same_line(0.),
+			Command::Button(ref label) => {
+				if ui.button(&ImString::new(label.as_str()), (0., 0.)) {
+					clicked.insert(label.clone());
+				}
+			},
+			Command::Checkbox(ref label, ref name) => {
+				let mut x = values.bool(name).unwrap_or(false);
+				if ui.checkbox(&ImString::new(label.as_str()), &mut x) {
+					values.set(name.as_str(), MenuValue::Bool(x));
+				}
+			},
+			Command::Slider(ref label, ref name, min, max) => {
+				let mut x = values.float(name).unwrap_or(min);
+				if ui.slider_float(&ImString::new(label.as_str()), &mut x, min, max).build() {
+					values.set(name.as_str(), MenuValue::Float(x));
+				}
+			},
+		}
+		index += 1;
+	}
+}
+
+/// The line in rhai's `(line 3, position 7)` error suffix.
+fn error_line(error: &str) -> Option<usize> {
+	let start = error.rfind("(line ")? + "(line ".len();
+	error[start..].split(',').next()?.trim().parse().ok()
+}
+
+/// Runs every script, renders what they built and shows compile/runtime errors when `Scripts::open` is set.
+pub fn run_scripts(ui: &Ui, world: &World) {
+	let mut scripts = match world.res.try_fetch_mut::<Scripts>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let mut values = match world.res.try_fetch_mut::<MenuValues>() {
+		Some(x) => x,
+		_ => return,
+	};
+	scripts.reload();
+
+	let scripts = &mut *scripts;
+	let mut clicked = HashSet::new();
+	for script in &mut scripts.scripts {
+		let ast = match script.ast {
+			Some(ref x) => x,
+			_ => continue,
+		};
+		{
+			let mut frame = scripts.frame.lock().unwrap();
+			frame.commands.clear();
+			frame.values = values.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+		}
+		if let Err(e) = scripts.engine.consume_ast(ast) {
+			script.runtime_error = Some(format!("{}", e));
+			continue;
+		}
+		script.runtime_error = None;
+		let commands = std::mem::replace(&mut scripts.frame.lock().unwrap().commands, Vec::new());
+		render(ui, &commands, &mut values, &mut clicked);
+	}
+	scripts.frame.lock().unwrap().clicked = clicked;
+
+	if !scripts.open {
+		return;
+	}
+	let mut open = true;
+	ui.window(im_str!("Scripts"))
+		.size((520., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			for (i, script) in scripts.scripts.iter_mut().enumerate() {
+				let errors = script.error.iter().chain(&script.runtime_error).collect::<Vec<_>>();
+				if errors.is_empty() {
+					ui.text(script.path.display().to_string());
+				} else {
+					ui.text_colored([1., 0.35, 0.35, 1.], script.path.display().to_string());
+				}
+				for error in &errors {
+					ui.text_wrapped(&ImString::new(error.as_str()));
+				}
+				let line = errors.iter().filter_map(|x| error_line(x)).next();
+				if ui.collapsing_header(&im_str!("Source##{}", i)).build() {
+					// Saving changes the file's modification time, so `reload` compiles it next frame
+					if ui.small_button(&im_str!("Save##{}", i)) {
+						if let Err(e) = fs::write(&script.path, script.source.to_str()) {
+							script.error = Some(format!("Can't save: {}", e));
+						}
+					}
+					ui.same_line(0.);
+					code_editor(ui, &im_str!("##script{}", i), &mut script.source, Language::Rhai, (0., 300.), line);
+				}
+			}
+		});
+	scripts.open = open;
+}
@@ -0,0 +1,118 @@
+use std::{
+	cell::RefCell,
+	collections::{BTreeSet, HashMap},
+	ffi::CString,
+};
+
+use imgui::{sys, ImGuiSelectableFlags, ImStr, ImString, Ui};
+
+use crate::clipper::clipped_rows;
+
+thread_local! {
+	/// Filter typed into each widget, by widget id.
+	static FILTERS: RefCell<HashMap<String, ImString>> = RefCell::new(HashMap::new());
+}
+
+/// Indices of `items` containing `filter`, ignoring case.
+fn filtered<S: AsRef<str>>(items: &[S], filter: &str) -> Vec<usize> {
+	let filter = filter.to_lowercase();
+	items
+		.iter()
+		.enumerate()
+		.filter(|(_, x)| filter.is_empty() || x.as_ref().to_lowercase().contains(&filter))
+		.map(|(i, _)| i)
+		.collect()
+}
+
+/// Edits the filter of widget `id`, returning what was typed.
+fn filter_input(ui: &Ui, id: &ImStr, focus: bool) -> String {
+	FILTERS.with(|filters| {
+		let mut filters = filters.borrow_mut();
+		let filter = filters.entry(id.to_str().to_owned()).or_insert_with(|| ImString::with_capacity(64));
+		if focus {
+			unsafe { sys::igSetKeyboardFocusHere(0) };
+		}
+		ui.push_item_width(-1.);
+		ui.input_text(&im_str!("##filter{}", id), filter).build();
+		ui.pop_item_width();
+		filter.to_str().to_owned()
+	})
+}
+
+/// A combo whose popup starts with a filter box, listing only the `items` containing the typed text.
+/// Scales to thousands of items; returns whether `current` changed.
+pub fn searchable_combo<S: AsRef<str>>(ui: &Ui, label: &ImStr, current: &mut usize, items: &[S]) -> bool {
+	let preview = CString::new(items.get(*current).map_or("", |x| x.as_ref())).unwrap_or_default();
+	if !unsafe { sys::igBeginCombo(label.as_ptr(), preview.as_ptr(), sys::ImGuiComboFlags::HeightLargest) } {
+		return false;
+	}
+	let filter = filter_input(ui, label, unsafe { sys::igIsWindowAppearing() });
+	let matches = filtered(items, &filter);
+	let row_height = unsafe { sys::igGetTextLineHeightWithSpacing() };
+	let height = row_height * (matches.len().min(12) as f32) + 4.;
+
+	let mut changed = false;
+	ui.child_frame(&im_str!("##items{}", label), (0., height)).build(|| {
+		clipped_rows(matches.len(), Some(row_height), |row| {
+			let index = matches[row];
+			let item = im_str!("{}##{}", items[index].as_ref(), index);
+			if ui.selectable(&item, index == *current, ImGuiSelectableFlags::empty(), (0., 0.)) {
+				*current = index;
+				changed = true;
+			}
+		});
+	});
+	if matches.is_empty() {
+		ui.text_disabled(im_str!("No matches"));
+	}
+	// Enter picks the only match
+	if matches.len() == 1 && unsafe { sys::igIsKeyPressed(sys::igGetKeyIndex(sys::ImGuiKey::Enter), true) } {
+		*current = matches[0];
+		changed = true;
+	}
+	if changed {
+		unsafe { sys::igCloseCurrentPopup() };
+	}
+	unsafe { sys::igEndCombo() };
+	changed
+}
+
+/// A filterable list of checkboxes toggling indices of `items` in `selected`, `height` pixels tall.
+/// All and None apply to the items matching the filter. Returns whether the selection changed.
+pub fn multi_select<S: AsRef<str>>(
+	ui: &Ui,
+	id: &ImStr,
+	items: &[S],
+	selected: &mut BTreeSet<usize>,
+	height: f32,
+) -> bool {
+	let filter = filter_input(ui, id, false);
+	let matches = filtered(items, &filter);
+	let mut changed = false;
+	if ui.small_button(&im_str!("All##{}", id)) {
+		changed |= matches.iter().fold(false, |changed, x| selected.insert(*x) || changed);
+	}
+	ui.same_line(0.);
+	if ui.small_button(&im_str!("None##{}", id)) {
+		changed |= matches.iter().fold(false, |changed, x| selected.remove(x) || changed);
+	}
+	ui.same_line(0.);
+	ui.text_disabled(&im_str!("{} of {} selected", selected.len(), items.len()));
+
+	let row_height = unsafe { sys::igGetFrameHeightWithSpacing() };
+	ui.child_frame(&im_str!("##items{}", id), (0., height)).show_borders(true).build(|| {
+		clipped_rows(matches.len(), Some(row_height), |row| {
+			let index = matches[row];
+			let mut checked = selected.contains(&index);
+			if ui.checkbox(&im_str!("{}##{}", items[index].as_ref(), index), &mut checked) {
+				if checked {
+					selected.insert(index);
+				} else {
+					selected.remove(&index);
+				}
+				changed = true;
+			}
+		});
+	});
+	changed
+}
@@ -0,0 +1,96 @@
+use amethyst::{
+	assets::AssetStorage,
+	core::{
+		nalgebra::{Point3, Vector3},
+		GlobalTransform,
+	},
+	ecs::prelude::*,
+	renderer::{SpriteRender, SpriteSheet},
+};
+use imgui::{sys, Ui};
+
+use crate::picking::ScreenProjection;
+
+/// The entity inspectors and editing tools are pointing at; clicking a picked entity sets it.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct SelectedEntity(pub Option<Entity>);
+
+/// Local-space bounding box to outline when the entity is selected. Sprites get theirs from the sprite sheet.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SelectionBounds {
+	pub min: Vector3<f32>,
+	pub max: Vector3<f32>,
+}
+
+impl Component for SelectionBounds {
+	type Storage = DenseVecStorage<Self>;
+}
+
+/// Outline color, ABGR.
+const COLOR: u32 = 0xff_00_ff_ff;
+
+fn sprite_bounds(world: &World, entity: Entity) -> Option<SelectionBounds> {
+	let renders = world.read_storage::<SpriteRender>();
+	let render = renders.get(entity)?;
+	let sheets = world.res.try_fetch::<AssetStorage<SpriteSheet>>()?;
+	let sprite = sheets.get(&render.sprite_sheet)?.sprites.get(render.sprite_number)?;
+	// Sprites are centered on their transform, shifted by their offsets
+	let (x, y) = (-sprite.offsets[0], -sprite.offsets[1]);
+	Some(SelectionBounds {
+		min: Vector3::new(x - sprite.width / 2., y - sprite.height / 2., 0.),
+		max: Vector3::new(x + sprite.width / 2., y + sprite.height / 2., 0.),
+	})
+}
+
+/// Outlines the `SelectedEntity`'s bounds on the overlay draw list, projected through the active camera.
+///
+/// When the `Viewport` window shows the game, the outline is drawn over the viewport image instead.
+pub fn highlight_selection(ui: &Ui, world: &World) {
+	let entity = match world.res.try_fetch::<SelectedEntity>().and_then(|x| x.0) {
+		Some(x) => x,
+		_ => return,
+	};
+	let projection = match ScreenProjection::new(ui, world) {
+		Some(x) => x,
+		_ => return,
+	};
+	let bounds = match world.read_storage::<SelectionBounds>().get(entity).cloned() {
+		Some(x) => x,
+		_ => match sprite_bounds(world, entity) {
+			Some(x) => x,
+			_ => return,
+		},
+	};
+	let transforms = world.read_storage::<GlobalTransform>();
+	let transform = match transforms.get(entity) {
+		Some(x) => x,
+		_ => return,
+	};
+
+	let (min, max) = (bounds.min, bounds.max);
+	let corners = (0..8)
+		.map(|i| {
+			let local = Point3::new(
+				if i & 1 == 0 { min.x } else { max.x },
+				if i & 2 == 0 { min.y } else { max.y },
+				if i & 4 == 0 { min.z } else { max.z },
+			);
+			Point3::from_homogeneous(transform.0 * local.to_homogeneous()).and_then(|x| projection.project(&x))
+		})
+		.collect::<Option<Vec<_>>>();
+	let corners = match corners {
+		Some(x) => x,
+		// Part of the box is behind the camera
+		_ => return,
+	};
+	unsafe {
+		let list = sys::igGetOverlayDrawList();
+		// Flat bounds, like sprites, are just the first four corners
+		let count = if min.z == max.z { 4 } else { 8 };
+		for a in 0..count {
+			for axis in (0..3).map(|x| 1 << x).filter(|x| *x < count && a & x == 0) {
+				sys::ImDrawList_AddLine(list, corners[a], corners[a | axis], COLOR, 2.);
+			}
+		}
+	}
+}
@@ -2,11 +2,15 @@
 
 uniform sampler2D albedo;
 
+layout (std140) uniform OverlayArgs {
+	uniform vec4 tint;
+};
+
 in vec2 f_uv;
 in vec4 f_color;
 
 out vec4 color;
 
 void main() {
-	color = texture(albedo, f_uv.st);
+	color = texture(albedo, f_uv.st) * tint;
 }
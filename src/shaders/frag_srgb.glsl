@@ -0,0 +1,23 @@
+#version 150
+
+uniform sampler2D albedo;
+
+layout (std140) uniform OverlayArgs {
+	uniform vec4 tint;
+};
+
+in vec2 f_uv;
+in vec4 f_color;
+
+out vec4 color;
+
+void main() {
+	color = texture(albedo, f_uv.st);
+	// The overlay holds sRGB-encoded colors, the framebuffer expects linear ones. imgui blended them onto a
+	// cleared target, premultiplying them by coverage, so linearize the colors themselves and not the product
+	// or antialiased edges and translucent windows come out too dark.
+	if (color.a > 0.0) {
+		color.rgb = pow(color.rgb / color.a, vec3(2.2)) * color.a;
+	}
+	color *= tint;
+}
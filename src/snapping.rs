@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use amethyst::ecs::prelude::*;
+use imgui::{sys, ImGuiCond, ImMouseButton, ImVec2, Ui};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Rect {
+	pos: (f32, f32),
+	size: (f32, f32),
+	frame: i32,
+}
+
+/// Snaps windows calling `snap_window` to the screen's edges and to each other while they're dragged.
+pub struct WindowSnapping {
+	pub enabled: bool,
+	/// Distance in pixels at which edges pull together.
+	pub threshold: f32,
+	pub screen_edges: bool,
+	/// Gap left between snapped windows.
+	pub spacing: f32,
+	windows: HashMap<String, Rect>,
+}
+
+impl Default for WindowSnapping {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			threshold: 12.,
+			screen_edges: true,
+			spacing: 0.,
+			windows: HashMap::new(),
+		}
+	}
+}
+
+/// The smallest move within `threshold` that lines up one of `edges` with one of `targets`.
+fn snap_axis(edges: [f32; 2], targets: &[f32], threshold: f32) -> Option<f32> {
+	edges
+		.iter()
+		.flat_map(|edge| targets.iter().map(move |target| target - edge))
+		.filter(|x| x.abs() <= threshold)
+		.min_by(|a, b| a.abs().partial_cmp(&b.abs()).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+fn overlaps(a: (f32, f32), b: (f32, f32), threshold: f32) -> bool { a.0 <= b.1 + threshold && b.0 <= a.1 + threshold }
+
+impl WindowSnapping {
+	fn snap(&self, name: &str, pos: (f32, f32), size: (f32, f32), screen: (f32, f32), frame: i32) -> (f32, f32) {
+		let (mut x_targets, mut y_targets) = (Vec::new(), Vec::new());
+		if self.screen_edges {
+			x_targets.extend_from_slice(&[0., screen.0]);
+			y_targets.extend_from_slice(&[0., screen.1]);
+		}
+		let spacing = self.spacing;
+		let others = self.windows.iter().filter(|(x, rect)| x.as_str() != name && rect.frame >= frame - 1);
+		for (_, other) in others {
+			let (left, top) = other.pos;
+			let (right, bottom) = (left + other.size.0, top + other.size.1);
+			// Side by side when they share some height, stacked when they share some width
+			if overlaps((pos.1, pos.1 + size.1), (top, bottom), self.threshold) {
+				x_targets.extend_from_slice(&[left - spacing, right + spacing, left, right]);
+			}
+			if overlaps((pos.0, pos.0 + size.0), (left, right), self.threshold) {
+				y_targets.extend_from_slice(&[top - spacing, bottom + spacing, top, bottom]);
+			}
+		}
+		let dx = snap_axis([pos.0, pos.0 + size.0], &x_targets, self.threshold).unwrap_or(0.);
+		let dy = snap_axis([pos.1, pos.1 + size.1], &y_targets, self.threshold).unwrap_or(0.);
+		(pos.0 + dx, pos.1 + dy)
+	}
+}
+
+/// Call inside a window's `build` closure to make it snap while it's dragged; `name` identifies the window.
+///
+/// imgui 1.66 has no way to tell a window is being moved, so it counts as dragged when it moved without
+/// changing size while the left button is down.
+pub fn snap_window(ui: &Ui, world: &World, name: &str) {
+	let mut snapping = match world.res.try_fetch_mut::<WindowSnapping>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let frame = unsafe { sys::igGetFrameCount() };
+	let mut pos: (f32, f32) = ui.get_window_pos().into();
+	let size: (f32, f32) = ui.get_window_size().into();
+
+	let last = snapping.windows.get(name).cloned();
+	let dragged = match last {
+		Some(last) => last.frame >= frame - 1 && last.pos != pos && last.size == size,
+		None => false,
+	};
+	if snapping.enabled && dragged && ui.imgui().is_mouse_down(ImMouseButton::Left) {
+		let (width, height) = ui.frame_size().logical_size;
+		let snapped = snapping.snap(name, pos, size, (width as f32, height as f32), frame);
+		if snapped != pos {
+			pos = snapped;
+			unsafe { sys::igSetWindowPos(ImVec2::new(pos.0, pos.1), ImGuiCond::Always) };
+		}
+	}
+	snapping.windows.insert(name.to_owned(), Rect { pos, size, frame });
+}
@@ -0,0 +1,103 @@
+use amethyst::{
+	assets::AssetStorage,
+	ecs::prelude::*,
+	renderer::{SpriteRender, SpriteSheet},
+};
+use imgui::{sys, Ui};
+
+use crate::textures::{sprite_uvs, ImguiTextures};
+
+/// Plays a sprite sheet's frames in a loop, for previewing animations without running gameplay.
+///
+/// Keep one per previewed animation and call `draw` inside a window every frame.
+pub struct SpritePreview {
+	pub fps: f32,
+	pub playing: bool,
+	/// First and last frame, inclusive; `None` plays from the rendered sprite to the end of the sheet.
+	pub frames: Option<(usize, usize)>,
+	pub frame: usize,
+	elapsed: f64,
+	last_time: Option<f64>,
+}
+
+impl Default for SpritePreview {
+	fn default() -> Self {
+		Self {
+			fps: 12.,
+			playing: true,
+			frames: None,
+			frame: 0,
+			elapsed: 0.,
+			last_time: None,
+		}
+	}
+}
+
+impl SpritePreview {
+	/// Draws the current frame of `render`'s sheet at `scale` times its size, with playback controls.
+	///
+	/// The sheet's texture is registered with `ImguiTextures` and shows up once it's uploaded.
+	pub fn draw(&mut self, ui: &Ui, world: &World, render: &SpriteRender, scale: f32) {
+		let sheets = match world.res.try_fetch::<AssetStorage<SpriteSheet>>() {
+			Some(x) => x,
+			_ => return,
+		};
+		let sheet = match sheets.get(&render.sprite_sheet) {
+			Some(x) if !x.sprites.is_empty() => x,
+			_ => {
+				ui.text_disabled("Loading sprite sheet...");
+				return;
+			},
+		};
+		let (first, last) = self.frames.unwrap_or((render.sprite_number, sheet.sprites.len() - 1));
+		let last = last.min(sheet.sprites.len() - 1);
+		let first = first.min(last);
+
+		let time = unsafe { sys::igGetTime() } as f64;
+		if self.playing && self.fps > 0. {
+			self.elapsed += time - self.last_time.unwrap_or(time);
+			let step = 1. / self.fps as f64;
+			while self.elapsed >= step {
+				self.elapsed -= step;
+				self.frame += 1;
+			}
+		}
+		self.last_time = Some(time);
+		if self.frame < first || self.frame > last {
+			self.frame = first;
+		}
+
+		let sprite = &sheet.sprites[self.frame];
+		let size = (sprite.width * scale, sprite.height * scale);
+		let texture = match world.res.try_fetch_mut::<ImguiTextures>() {
+			Some(mut textures) => {
+				textures.register(sheet.texture.clone());
+				textures.get(&sheet.texture)
+			},
+			_ => None,
+		};
+		match texture {
+			Some(texture) => {
+				let (uv0, uv1) = sprite_uvs(sprite);
+				ui.image(texture, size).uv0(uv0).uv1(uv1).build();
+			},
+			None => ui.text_disabled("Loading texture..."),
+		}
+
+		ui.text(format!("Frame {} ({}-{})", self.frame, first, last));
+		if ui.small_button(if self.playing { im_str!("Pause") } else { im_str!("Play") }) {
+			self.playing = !self.playing;
+		}
+		ui.same_line(0.);
+		if ui.small_button(im_str!("<")) {
+			self.playing = false;
+			self.frame = if self.frame > first { self.frame - 1 } else { last };
+		}
+		ui.same_line(0.);
+		if ui.small_button(im_str!(">")) {
+			self.playing = false;
+			self.frame = if self.frame < last { self.frame + 1 } else { first };
+		}
+		ui.slider_float(im_str!("FPS"), &mut self.fps, 1., 60.).build();
+	}
+}
@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+
+use amethyst::{
+	core::timing::Time,
+	ecs::prelude::*,
+	State,
+	StateData,
+	Trans,
+};
+use imgui::{ImGuiCond, Ui};
+
+const MAX_TRANSITIONS: usize = 256;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TransitionKind {
+	Start,
+	Stop,
+	Pause,
+	Resume,
+	Push,
+	Pop,
+	Switch,
+	Quit,
+}
+
+#[derive(Clone, Debug)]
+pub struct Transition {
+	pub time: f64,
+	pub state: &'static str,
+	pub kind: TransitionKind,
+}
+
+/// Live state stack and transition timeline, filled in by `Tracked` states.
+#[derive(Default)]
+pub struct StateHistory {
+	pub open: bool,
+	pub stack: Vec<&'static str>,
+	pub transitions: VecDeque<Transition>,
+}
+
+impl StateHistory {
+	fn record(&mut self, time: f64, state: &'static str, kind: TransitionKind) {
+		match kind {
+			TransitionKind::Start => self.stack.push(state),
+			TransitionKind::Stop => {
+				if let Some(index) = self.stack.iter().rposition(|x| *x == state) {
+					self.stack.remove(index);
+				}
+			},
+			_ => {},
+		}
+
+		if self.transitions.len() >= MAX_TRANSITIONS {
+			self.transitions.pop_front();
+		}
+		self.transitions.push_back(Transition { time, state, kind });
+	}
+}
+
+/// Wraps a state so its lifecycle and requested transitions are reported to `StateHistory`.
+///
+/// ```rust,ignore
+/// Application::new(assets, amethyst_imgui::states::Tracked::new("Loading", LoadingState), game_data)
+/// ```
+pub struct Tracked<T, E> {
+	name: &'static str,
+	inner: Box<dyn State<T, E>>,
+}
+
+impl<T, E: Send + Sync + 'static> Tracked<T, E> {
+	pub fn new<S: State<T, E> + 'static>(name: &'static str, state: S) -> Self {
+		Self {
+			name,
+			inner: Box::new(state),
+		}
+	}
+
+	fn record(&self, world: &mut World, kind: TransitionKind) {
+		let time = world.res.try_fetch::<Time>().map_or(0., |x| x.absolute_real_time_seconds());
+		world
+			.res
+			.entry::<StateHistory>()
+			.or_insert_with(StateHistory::default)
+			.record(time, self.name, kind);
+	}
+
+	fn observe(&self, world: &mut World, trans: Trans<T, E>) -> Trans<T, E> {
+		let kind = match trans {
+			Trans::Push(_) => Some(TransitionKind::Push),
+			Trans::Pop => Some(TransitionKind::Pop),
+			Trans::Switch(_) => Some(TransitionKind::Switch),
+			Trans::Quit => Some(TransitionKind::Quit),
+			_ => None,
+		};
+		if let Some(kind) = kind {
+			self.record(world, kind);
+		}
+		trans
+	}
+}
+
+impl<T, E: Send + Sync + 'static> State<T, E> for Tracked<T, E> {
+	fn on_start(&mut self, data: StateData<'_, T>) {
+		self.record(data.world, TransitionKind::Start);
+		self.inner.on_start(data);
+	}
+
+	fn on_stop(&mut self, data: StateData<'_, T>) {
+		self.inner.on_stop(StateData::new(data.world, data.data));
+		self.record(data.world, TransitionKind::Stop);
+	}
+
+	fn on_pause(&mut self, data: StateData<'_, T>) {
+		self.record(data.world, TransitionKind::Pause);
+		self.inner.on_pause(data);
+	}
+
+	fn on_resume(&mut self, data: StateData<'_, T>) {
+		self.record(data.world, TransitionKind::Resume);
+		self.inner.on_resume(data);
+	}
+
+	fn handle_event(&mut self, data: StateData<'_, T>, event: E) -> Trans<T, E> {
+		let trans = self.inner.handle_event(StateData::new(data.world, data.data), event);
+		self.observe(data.world, trans)
+	}
+
+	fn fixed_update(&mut self, data: StateData<'_, T>) -> Trans<T, E> {
+		let trans = self.inner.fixed_update(StateData::new(data.world, data.data));
+		self.observe(data.world, trans)
+	}
+
+	fn update(&mut self, data: StateData<'_, T>) -> Trans<T, E> {
+		let trans = self.inner.update(StateData::new(data.world, data.data));
+		self.observe(data.world, trans)
+	}
+
+	fn shadow_fixed_update(&mut self, data: StateData<'_, T>) { self.inner.shadow_fixed_update(data); }
+
+	fn shadow_update(&mut self, data: StateData<'_, T>) { self.inner.shadow_update(data); }
+}
+
+fn kind_color(kind: TransitionKind) -> [f32; 4] {
+	match kind {
+		TransitionKind::Start | TransitionKind::Push => [0.4, 0.9, 0.4, 1.],
+		TransitionKind::Stop | TransitionKind::Pop => [0.9, 0.5, 0.3, 1.],
+		TransitionKind::Switch => [0.4, 0.7, 1., 1.],
+		TransitionKind::Quit => [1., 0.3, 0.3, 1.],
+		TransitionKind::Pause | TransitionKind::Resume => [0.7, 0.7, 0.7, 1.],
+	}
+}
+
+pub fn state_stack_window(ui: &Ui, world: &World) {
+	let mut history = match world.res.try_fetch_mut::<StateHistory>() {
+		Some(x) => x,
+		_ => return,
+	};
+	if !history.open {
+		return;
+	}
+
+	let mut open = true;
+	let mut clear = false;
+	ui.window(im_str!("States"))
+		.size((320., 400.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.text("Stack (top first):");
+			for (depth, name) in history.stack.iter().rev().enumerate() {
+				if depth == 0 {
+					ui.text_colored([0.4, 0.9, 0.4, 1.], format!("> {}", name));
+				} else {
+					ui.text_disabled(format!("  {}", name));
+				}
+			}
+			ui.separator();
+			ui.text(format!("Transitions ({}):", history.transitions.len()));
+			ui.same_line(0.);
+			clear = ui.small_button(im_str!("Clear"));
+			ui.child_frame(im_str!("transitions"), (0., 0.)).build(|| {
+				for transition in history.transitions.iter().rev() {
+					ui.text(format!("{:>9.3}s", transition.time));
+					ui.same_line(0.);
+					ui.text_colored(kind_color(transition.kind), format!("{:?}", transition.kind));
+					ui.same_line(0.);
+					ui.text(transition.state);
+				}
+			});
+		});
+	history.open = open;
+	if clear {
+		history.transitions.clear();
+	}
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImGuiWindowFlags, ImString, Ui};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum StatusAlign {
+	Left,
+	Center,
+	Right,
+}
+
+struct Segment {
+	name: String,
+	align: StatusAlign,
+	text: Arc<dyn Fn(&World) -> String + Send + Sync>,
+}
+
+/// Text segments shown in a bar along the bottom edge of the screen, drawn by `status_bar`.
+///
+/// ```rust,ignore
+/// world.write_resource::<StatusBar>().add("fps", StatusAlign::Right, |world| {
+/// 	format!("{:.0} FPS", world.read_resource::<FPSCounter>().sampled_fps())
+/// });
+/// ```
+pub struct StatusBar {
+	segments: Vec<Segment>,
+	pub visible: bool,
+	pub height: f32,
+}
+
+impl Default for StatusBar {
+	fn default() -> Self {
+		Self {
+			segments: Vec::new(),
+			visible: true,
+			height: 22.,
+		}
+	}
+}
+
+impl StatusBar {
+	/// Adds a segment, or replaces the one with the same name. Segments with the same alignment are shown in the
+	/// order they were added.
+	pub fn add<S, F>(&mut self, name: S, align: StatusAlign, text: F)
+	where
+		S: Into<String>,
+		F: Fn(&World) -> String + Send + Sync + 'static,
+	{
+		let name = name.into();
+		let segment = Segment {
+			name,
+			align,
+			text: Arc::new(text),
+		};
+		match self.segments.iter().position(|x| x.name == segment.name) {
+			Some(index) => self.segments[index] = segment,
+			None => self.segments.push(segment),
+		}
+	}
+
+	pub fn remove(&mut self, name: &str) { self.segments.retain(|x| x.name != name); }
+}
+
+pub fn status_bar(ui: &Ui, world: &World) {
+	let (height, segments) = match world.res.try_fetch::<StatusBar>() {
+		Some(ref x) if x.visible && !x.segments.is_empty() => {
+			(x.height, x.segments.iter().map(|x| (x.align, x.text.clone())).collect::<Vec<_>>())
+		},
+		_ => return,
+	};
+	let join = |align| {
+		let texts = segments.iter().filter(|x| x.0 == align).map(|x| (x.1)(world)).collect::<Vec<_>>();
+		ImString::new(texts.join("  |  "))
+	};
+	let (left, center, right) = (join(StatusAlign::Left), join(StatusAlign::Center), join(StatusAlign::Right));
+
+	let (width, screen_height) = ui.frame_size().logical_size;
+	let (width, screen_height) = (width as f32, screen_height as f32);
+	ui.window(im_str!("##status_bar"))
+		.position((0., screen_height - height), ImGuiCond::Always)
+		.size((width, height), ImGuiCond::Always)
+		.flags(
+			ImGuiWindowFlags::NoTitleBar
+				| ImGuiWindowFlags::NoResize
+				| ImGuiWindowFlags::NoMove
+				| ImGuiWindowFlags::NoScrollbar
+				| ImGuiWindowFlags::NoSavedSettings
+				| ImGuiWindowFlags::NoFocusOnAppearing
+				| ImGuiWindowFlags::NoBringToFrontOnFocus,
+		)
+		.build(|| {
+			let y = ui.get_cursor_pos().1;
+			let padding = 8.;
+			if !left.is_empty() {
+				ui.set_cursor_pos((padding, y));
+				ui.text(&left);
+			}
+			if !center.is_empty() {
+				let size = ui.calc_text_size(&center, false, -1.);
+				ui.set_cursor_pos(((width - size.x) / 2., y));
+				ui.text(&center);
+			}
+			if !right.is_empty() {
+				let size = ui.calc_text_size(&right, false, -1.);
+				ui.set_cursor_pos((width - size.x - padding, y));
+				ui.text(&right);
+			}
+		});
+}
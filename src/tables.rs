@@ -0,0 +1,113 @@
+use std::{cmp::Ordering, ffi::CString, fs};
+
+use imgui::{sys, ImGuiSelectableFlags, ImStr, ImString, Ui};
+
+/// Sortable table with resizable columns, built on imgui's columns since 0.0.21 has no tables API.
+///
+/// ```rust,ignore
+/// table.sort(&mut rows, |a, b, column| match column {
+/// 	0 => a.name.cmp(&b.name),
+/// 	_ => a.id.cmp(&b.id),
+/// });
+/// table.begin(ui, im_str!("entities"), &["Name", "Id"]);
+/// for row in &rows {
+/// 	ui.text(&row.name);
+/// 	ui.next_column();
+/// 	ui.text(row.id.to_string());
+/// 	ui.next_column();
+/// }
+/// table.end(ui);
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct Table {
+	/// Column to sort by and whether it's ascending; clicking a header sets it.
+	pub sort: Option<(usize, bool)>,
+}
+
+impl Table {
+	/// Starts the columns and draws the header row; fill cells with `ui.next_column()` after each.
+	pub fn begin(&mut self, ui: &Ui, id: &ImStr, headers: &[&str]) {
+		ui.columns(headers.len() as i32, id, true);
+		for (i, header) in headers.iter().enumerate() {
+			let arrow = match self.sort {
+				Some((column, true)) if column == i => " ^",
+				Some((column, false)) if column == i => " v",
+				_ => "",
+			};
+			let label = ImString::new(format!("{}{}##{}", header, arrow, i));
+			if ui.selectable(&label, false, ImGuiSelectableFlags::empty(), (0., 0.)) {
+				self.sort = match self.sort {
+					Some((column, ascending)) if column == i => Some((i, !ascending)),
+					_ => Some((i, true)),
+				};
+			}
+			ui.next_column();
+		}
+		ui.separator();
+	}
+
+	pub fn end(&self, ui: &Ui) {
+		ui.columns(1, im_str!("##table_end"), false);
+	}
+
+	/// Sorts `rows` by the selected column, `compare` gets the column index.
+	pub fn sort<T, F: Fn(&T, &T, usize) -> Ordering>(&self, rows: &mut [T], compare: F) {
+		if let Some((column, ascending)) = self.sort {
+			rows.sort_by(|a, b| {
+				let ordering = compare(a, b, column);
+				if ascending { ordering } else { ordering.reverse() }
+			});
+		}
+	}
+}
+
+/// Quotes a CSV field when it has to be.
+pub(crate) fn csv_field(value: &str) -> String {
+	if value.contains(|c| c == ',' || c == '"' || c == '\n') {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_owned()
+	}
+}
+
+/// Formats `headers` and `rows` of cells as CSV.
+pub fn to_csv<S: AsRef<str>>(headers: &[&str], rows: &[Vec<S>]) -> String {
+	let mut out = headers.iter().map(|x| csv_field(x)).collect::<Vec<_>>().join(",");
+	out.push('\n');
+	for row in rows {
+		out += &row.iter().map(|x| csv_field(x.as_ref())).collect::<Vec<_>>().join(",");
+		out.push('\n');
+	}
+	out
+}
+
+/// "Copy CSV" and "Save CSV" buttons exporting a table as it's shown, sorted and filtered, for spreadsheets.
+/// `rows` formats its cells and only runs on a click; saving writes `<id>.csv` to the working directory and
+/// returns a status line saying whether it worked, for the table's window to show.
+pub fn export_buttons<F: FnOnce() -> Vec<Vec<String>>>(
+	ui: &Ui,
+	id: &ImStr,
+	headers: &[&str],
+	rows: F,
+) -> Option<String> {
+	let copy = ui.small_button(&im_str!("Copy CSV##{}", id.to_str()));
+	ui.same_line(0.);
+	let save = ui.small_button(&im_str!("Save CSV##{}", id.to_str()));
+	if !copy && !save {
+		return None;
+	}
+	let csv = to_csv(headers, &rows());
+	if copy {
+		// Through imgui, so it goes to whichever `ClipboardBackend` is set
+		if let Ok(text) = CString::new(csv) {
+			unsafe { sys::igSetClipboardText(text.as_ptr()) };
+		}
+		None
+	} else {
+		let path = format!("{}.csv", id.to_str().trim_start_matches('#'));
+		Some(match fs::write(&path, csv) {
+			Ok(()) => format!("Saved {}", path),
+			Err(e) => format!("Can't write {}: {}", path, e),
+		})
+	}
+}
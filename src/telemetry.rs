@@ -0,0 +1,224 @@
+use std::{
+	collections::{BTreeMap, BTreeSet, VecDeque},
+	fs,
+	io::{self, Write as _},
+	path::Path,
+	time::Instant,
+};
+
+use amethyst::{core::timing::Time, ecs::prelude::*};
+use imgui::{ImGuiCond, ImString, Ui};
+use serde_json;
+
+use crate::{metrics::ImguiMetrics, plots::Plots, profiler::Profiler, tables::csv_field};
+
+/// Everything `telemetry_window` sampled in one frame.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct FrameSample {
+	/// Seconds since the recording started.
+	pub time: f64,
+	pub frame_ms: f32,
+	/// Time spent in each `Profiled` system during the last profiler frame.
+	pub systems_ms: BTreeMap<String, f32>,
+	/// Latest value of every `Plots` series.
+	pub plots: BTreeMap<String, f32>,
+	pub ui_draw_calls: usize,
+	pub ui_vertices: usize,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct LogLine {
+	pub time: f64,
+	pub message: String,
+}
+
+#[derive(Serialize)]
+struct Session<'a> {
+	frames: &'a VecDeque<FrameSample>,
+	log: &'a [LogLine],
+}
+
+/// A recording of per-frame metrics and log lines, exported to JSON or CSV by `telemetry_window` or from code.
+pub struct Telemetry {
+	pub open: bool,
+	pub recording: bool,
+	/// Frames kept before the oldest are dropped.
+	pub max_frames: usize,
+	frames: VecDeque<FrameSample>,
+	log: Vec<LogLine>,
+	start: Instant,
+	path: ImString,
+	status: Option<String>,
+}
+
+impl Default for Telemetry {
+	fn default() -> Self {
+		let mut path = ImString::with_capacity(256);
+		path.push_str("telemetry");
+		Self {
+			open: false,
+			recording: false,
+			max_frames: 60 * 60 * 10,
+			frames: VecDeque::new(),
+			log: Vec::new(),
+			start: Instant::now(),
+			path,
+			status: None,
+		}
+	}
+}
+
+impl Telemetry {
+	fn now(&self) -> f64 {
+		let elapsed = self.start.elapsed();
+		elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9
+	}
+
+	/// Adds a line to the exported log while recording.
+	pub fn log<S: Into<String>>(&mut self, message: S) {
+		if self.recording {
+			let time = self.now();
+			self.log.push(LogLine {
+				time,
+				message: message.into(),
+			});
+		}
+	}
+
+	pub fn frames(&self) -> &VecDeque<FrameSample> { &self.frames }
+
+	pub fn clear(&mut self) {
+		self.frames.clear();
+		self.log.clear();
+		self.start = Instant::now();
+	}
+
+	/// Samples `world` into a new frame while recording; `telemetry_window` calls it every frame.
+	pub fn sample(&mut self, world: &World) {
+		if !self.recording {
+			return;
+		}
+		let systems_ms = match world.res.try_fetch::<Profiler>() {
+			Some(profiler) => profiler.last_frame().into_iter().fold(BTreeMap::new(), |mut systems, span| {
+				let ms = span.duration.as_secs() as f32 * 1000. + span.duration.subsec_nanos() as f32 / 1e6;
+				*systems.entry(span.name).or_insert(0.) += ms;
+				systems
+			}),
+			None => BTreeMap::new(),
+		};
+		let plots = match world.res.try_fetch::<Plots>() {
+			Some(plots) => plots.series.iter().filter_map(|(k, v)| Some((k.clone(), v.samples.last()?))).collect(),
+			None => BTreeMap::new(),
+		};
+		let metrics = world.res.try_fetch::<ImguiMetrics>().map(|x| x.clone()).unwrap_or_default();
+		let frame = FrameSample {
+			time: self.now(),
+			frame_ms: world.res.try_fetch::<Time>().map_or(0., |x| x.delta_real_seconds() * 1000.),
+			systems_ms,
+			plots,
+			ui_draw_calls: metrics.draw_calls,
+			ui_vertices: metrics.vertices,
+		};
+		if self.frames.len() >= self.max_frames.max(1) {
+			self.frames.pop_front();
+		}
+		self.frames.push_back(frame);
+	}
+
+	/// Writes the frames and log as `{ "frames": [..], "log": [..] }`.
+	pub fn export_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let session = Session {
+			frames: &self.frames,
+			log: &self.log,
+		};
+		let source = serde_json::to_string_pretty(&session)
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		fs::write(path, source)
+	}
+
+	/// Writes one row per frame, with a column for every system and plot seen during the recording.
+	/// The log goes to a second file next to it, with `.log.csv` as its extension.
+	pub fn export_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		let systems = self.frames.iter().flat_map(|x| x.systems_ms.keys()).collect::<BTreeSet<_>>();
+		let plots = self.frames.iter().flat_map(|x| x.plots.keys()).collect::<BTreeSet<_>>();
+
+		let mut out = io::BufWriter::new(fs::File::create(path)?);
+		write!(out, "time,frame_ms,ui_draw_calls,ui_vertices")?;
+		for name in &systems {
+			write!(out, ",{}", csv_field(&format!("system:{}", name)))?;
+		}
+		for name in &plots {
+			write!(out, ",{}", csv_field(&format!("plot:{}", name)))?;
+		}
+		writeln!(out)?;
+		for frame in &self.frames {
+			write!(out, "{:.6},{},{},{}", frame.time, frame.frame_ms, frame.ui_draw_calls, frame.ui_vertices)?;
+			for name in &systems {
+				write!(out, ",{}", frame.systems_ms.get(*name).map(|x| x.to_string()).unwrap_or_default())?;
+			}
+			for name in &plots {
+				write!(out, ",{}", frame.plots.get(*name).map(|x| x.to_string()).unwrap_or_default())?;
+			}
+			writeln!(out)?;
+		}
+
+		let mut log = io::BufWriter::new(fs::File::create(path.with_extension("log.csv"))?);
+		writeln!(log, "time,message")?;
+		for line in &self.log {
+			writeln!(log, "{:.6},{}", line.time, csv_field(&line.message))?;
+		}
+		Ok(())
+	}
+}
+
+/// Samples a frame while recording and, while open, shows the recording controls.
+pub fn telemetry_window(ui: &Ui, world: &World) {
+	let mut telemetry = match world.res.try_fetch_mut::<Telemetry>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let telemetry = &mut *telemetry;
+	telemetry.sample(world);
+	if !telemetry.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Telemetry"))
+		.size((360., 200.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			if ui.checkbox(im_str!("Recording"), &mut telemetry.recording) && telemetry.recording {
+				if telemetry.frames.is_empty() {
+					telemetry.start = Instant::now();
+				}
+			}
+			ui.same_line(0.);
+			if ui.small_button(im_str!("Clear")) {
+				telemetry.clear();
+			}
+			ui.text(format!("{} frames, {} log lines", telemetry.frames.len(), telemetry.log.len()));
+			ui.input_text(im_str!("File"), &mut telemetry.path).build();
+			let path = telemetry.path.to_str().to_owned();
+			let mut result = None;
+			if ui.button(im_str!("Export JSON"), (0., 0.)) {
+				let path = Path::new(&path).with_extension("json");
+				result = Some((telemetry.export_json(&path), path));
+			}
+			ui.same_line(0.);
+			if ui.button(im_str!("Export CSV"), (0., 0.)) {
+				let path = Path::new(&path).with_extension("csv");
+				result = Some((telemetry.export_csv(&path), path));
+			}
+			match result {
+				Some((Ok(()), path)) => telemetry.status = Some(format!("Saved {}", path.display())),
+				Some((Err(e), path)) => telemetry.status = Some(format!("Can't write {}: {}", path.display(), e)),
+				None => {},
+			}
+			if let Some(ref status) = telemetry.status {
+				ui.text_wrapped(&im_str!("{}", status));
+			}
+		});
+	telemetry.open = open;
+}
@@ -0,0 +1,124 @@
+use std::{fs, path::Path};
+
+use imgui::{FrameSize, Ui};
+use ron;
+
+use crate::{
+	create_imgui,
+	draw_data::{self, CapturedDrawData},
+	recording::InputRecorder,
+	ImguiInput,
+	ImguiState,
+};
+
+/// Runs imgui frames without a window or GPU and captures the generated draw data.
+///
+/// ```rust,ignore
+/// let mut harness = HeadlessImgui::new(800., 600.);
+/// harness.input(ImguiInput::CursorMoved(20, 30));
+/// let draw_data = harness.frame(|ui| ui.window(im_str!("Tool")).build(|| ui.text("hi")));
+/// assert_eq!(draw_data.lists.len(), 1);
+/// ```
+pub struct HeadlessImgui {
+	state: ImguiState,
+	size: (f32, f32),
+	pub delta_seconds: f32,
+	/// Records and replays input like the `InputRecorder` resource does for `open_frame`.
+	pub recorder: InputRecorder,
+}
+
+impl HeadlessImgui {
+	pub fn new(width: f32, height: f32) -> Self {
+		let mut imgui = create_imgui(true);
+		// Building the font atlas is normally done when the renderer uploads it
+		imgui.prepare_texture(|_| ());
+		Self {
+			state: ImguiState::new(imgui, (width as u16, height as u16), true),
+			size: (width, height),
+			delta_seconds: 1. / 60.,
+			recorder: InputRecorder::default(),
+		}
+	}
+
+	pub fn resize(&mut self, width: f32, height: f32) { self.size = (width, height); }
+
+	/// Feeds `input` to imgui, unless a replay is driving it.
+	pub fn input(&mut self, input: ImguiInput) {
+		if self.recorder.record(Some(input)) {
+			self.state.apply_input(input);
+		}
+	}
+
+	pub fn state_mut(&mut self) -> &mut ImguiState { &mut self.state }
+
+	/// Builds a frame with `f` and returns the draw data imgui generated for it.
+	pub fn frame<F: FnOnce(&Ui)>(&mut self, f: F) -> CapturedDrawData {
+		let delta_seconds = self.recorder.next_frame(&mut self.state, self.delta_seconds);
+		let ui = self
+			.state
+			.imgui
+			.frame(FrameSize::new(f64::from(self.size.0), f64::from(self.size.1), 1.), delta_seconds);
+		f(&ui);
+		let _ = ui.render(|_, _| Ok::<(), ()>(()));
+		unsafe { draw_data::capture() }.unwrap_or_default()
+	}
+}
+
+/// Compares draw data against a RON golden file, (re)writing it instead when `AMETHYST_IMGUI_UPDATE_GOLDEN` is set.
+/// A missing golden fails, so a deleted file can't make the check pass.
+pub fn assert_golden<P: AsRef<Path>>(path: P, draw_data: &CapturedDrawData) {
+	let path = path.as_ref();
+	if std::env::var_os("AMETHYST_IMGUI_UPDATE_GOLDEN").is_some() {
+		let source = ron::ser::to_string_pretty(draw_data, Default::default()).expect("Failed to serialize draw data");
+		fs::write(path, source).unwrap_or_else(|e| panic!("Failed to write {}: {}", path.display(), e));
+		return;
+	}
+	if !path.exists() {
+		panic!("Missing golden {}, run with AMETHYST_IMGUI_UPDATE_GOLDEN=1 to write it", path.display());
+	}
+
+	let source = fs::read_to_string(path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path.display(), e));
+	let golden: CapturedDrawData =
+		ron::de::from_str(&source).unwrap_or_else(|e| panic!("Failed to parse {}: {}", path.display(), e));
+	assert!(
+		golden == *draw_data,
+		"Draw data differs from {} ({} lists/{} vertices/{} indices, expected {}/{}/{})",
+		path.display(),
+		draw_data.lists.len(),
+		draw_data.total_vertices(),
+		draw_data.total_indices(),
+		golden.lists.len(),
+		golden.total_vertices(),
+		golden.total_indices()
+	);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use std::sync::Mutex;
+
+	use super::*;
+
+	/// imgui's context is global, so tests driving it can't run in parallel.
+	pub(crate) static IMGUI: Mutex<()> = Mutex::new(());
+
+	#[test]
+	fn empty_frame_matches_golden() {
+		let _lock = IMGUI.lock().unwrap_or_else(|e| e.into_inner());
+		let mut harness = HeadlessImgui::new(800., 600.);
+		let draw_data = harness.frame(|_| {});
+		assert_golden(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/empty_frame.ron"), &draw_data);
+	}
+
+	#[test]
+	fn window_is_drawn() {
+		let _lock = IMGUI.lock().unwrap_or_else(|e| e.into_inner());
+		let mut harness = HeadlessImgui::new(800., 600.);
+		let draw_data = harness.frame(|ui| ui.window(im_str!("Tool")).build(|| ui.text("hi")));
+		assert_eq!(draw_data.display_size, [800., 600.]);
+		assert_eq!(draw_data.lists.len(), 1);
+		assert!(draw_data.total_vertices() > 0);
+		let elements = draw_data.lists[0].commands.iter().map(|x| x.elem_count).sum::<u32>();
+		assert_eq!(draw_data.total_indices(), elements as usize);
+	}
+}
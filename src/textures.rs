@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use amethyst::{
+	assets::{AssetStorage, Handle},
+	renderer::{Encoder, Factory, Resources, Sprite, Texture},
+};
+use gfx::{
+	texture::{AaMode, FilterMethod, Kind, Mipmap, SamplerInfo, WrapMode},
+	traits::Factory as _,
+};
+use imgui::{ImTexture, Ui};
+use imgui_gfx_renderer::Renderer;
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct TextureOptions {
+	/// Fill the mip chain from the top level when uploading, so large textures don't shimmer when drawn small.
+	/// The texture has to be loaded with more than one mip level.
+	pub generate_mipmaps: bool,
+	/// Sampler to use instead of the texture's own, e.g. a trilinear one to actually sample the mips.
+	pub sampler: Option<SamplerInfo>,
+}
+
+/// Amethyst textures made available to imgui widgets like `ui.image`.
+///
+/// The `DrawUi` pass uploads registered textures once they finished loading, so `get` returns `None`
+/// until then. Ids can change when the renderer is re-created, look them up every frame.
+#[derive(Default)]
+pub struct ImguiTextures {
+	registered: Vec<(Handle<Texture>, TextureOptions)>,
+	ids: HashMap<Handle<Texture>, ImTexture>,
+	removed: Vec<ImTexture>,
+	placeholder: Option<ImTexture>,
+	font_atlas: Option<(ImTexture, (f32, f32))>,
+}
+
+impl ImguiTextures {
+	pub fn register(&mut self, handle: Handle<Texture>) { self.register_with(handle, TextureOptions::default()); }
+
+	/// Registers `handle`, or re-uploads it if it was registered with different options.
+	pub fn register_with(&mut self, handle: Handle<Texture>, options: TextureOptions) {
+		if self.registered.iter().any(|x| x.0 == handle && x.1 == options) {
+			return;
+		}
+		self.unregister(&handle);
+		self.registered.push((handle, options));
+	}
+
+	pub fn unregister(&mut self, handle: &Handle<Texture>) {
+		self.registered.retain(|x| x.0 != *handle);
+		self.removed.extend(self.ids.remove(handle));
+	}
+
+	pub fn get(&self, handle: &Handle<Texture>) -> Option<ImTexture> { self.ids.get(handle).cloned() }
+
+	pub fn is_loaded(&self, handle: &Handle<Texture>) -> bool { self.ids.contains_key(handle) }
+
+	/// The font atlas texture and its size in pixels, e.g. to show glyph UVs in a font debugger.
+	pub fn font_atlas(&self) -> Option<(ImTexture, (f32, f32))> { self.font_atlas }
+
+	/// Like `get`, but returns a checkerboard for textures that are still loading.
+	pub fn get_or_placeholder(&self, handle: &Handle<Texture>) -> Option<ImTexture> {
+		self.get(handle).or(self.placeholder)
+	}
+
+	pub(crate) fn registered_len(&self) -> usize { self.registered.len() }
+
+	pub(crate) fn has_placeholder(&self) -> bool { self.placeholder.is_some() }
+
+	pub(crate) fn sync(
+		&mut self,
+		renderer: &mut Renderer<Resources>,
+		storage: &AssetStorage<Texture>,
+		factory: &mut Factory,
+		encoder: &mut Encoder,
+	) {
+		for id in self.removed.drain(..) {
+			renderer.textures().remove(id);
+		}
+		self.font_atlas = unsafe {
+			let atlas = (*imgui::sys::igGetIO()).fonts;
+			if atlas.is_null() {
+				None
+			} else {
+				let atlas = &*atlas;
+				let size = (atlas.tex_width as f32, atlas.tex_height as f32);
+				Some((ImTexture::from(atlas.tex_id as usize), size))
+			}
+		};
+		if self.placeholder.is_none() {
+			let pixels = (0..64)
+				.map(|i| if (i % 8 / 4 + i / 32) % 2 == 0 { [204, 204, 204, 255] } else { [128, 128, 128, 255] })
+				.collect::<Vec<[u8; 4]>>();
+			let kind = Kind::D2(8, 8, AaMode::Single);
+			let texture = factory.create_texture_immutable::<gfx::format::Srgba8>(kind, Mipmap::Provided, &[&pixels]);
+			if let Ok((_, view)) = texture {
+				let sampler = factory.create_sampler(SamplerInfo::new(FilterMethod::Scale, WrapMode::Tile));
+				self.placeholder = Some(renderer.textures().insert((view, sampler)));
+			}
+		}
+		for (handle, options) in &self.registered {
+			if self.ids.contains_key(handle) {
+				continue;
+			}
+			if let Some(texture) = storage.get(handle) {
+				if options.generate_mipmaps {
+					encoder.generate_mipmap::<gfx::format::Rgba8>(texture.view());
+				}
+				let sampler = match options.sampler {
+					Some(info) => factory.create_sampler(info),
+					None => texture.sampler().clone(),
+				};
+				let id = renderer.textures().insert((texture.view().clone(), sampler));
+				self.ids.insert(handle.clone(), id);
+			}
+		}
+	}
+
+	/// The renderer was dropped together with everything uploaded to it.
+	pub(crate) fn reset(&mut self) {
+		self.ids.clear();
+		self.removed.clear();
+		self.placeholder = None;
+		self.font_atlas = None;
+	}
+}
+
+/// Draws a registered texture, or the checkerboard placeholder with a spinner while it's loading.
+pub fn image(ui: &Ui, textures: &ImguiTextures, handle: &Handle<Texture>, size: (f32, f32)) {
+	let id = match textures.get_or_placeholder(handle) {
+		Some(x) => x,
+		_ => return,
+	};
+	if textures.is_loaded(handle) {
+		ui.image(id, size).build();
+		return;
+	}
+	let start = ui.get_cursor_pos();
+	// Repeat the checker every 16 pixels
+	ui.image(id, size).uv1((size.0 / 16., size.1 / 16.)).build();
+	let end = ui.get_cursor_pos();
+	let frames = ["|", "/", "-", "\\"];
+	let frame = frames[(unsafe { imgui::sys::igGetTime() } * 8.) as usize % frames.len()];
+	ui.set_cursor_pos((start.0 + size.0 / 2. - 4., start.1 + size.1 / 2. - 8.));
+	ui.text(frame);
+	ui.set_cursor_pos(end);
+}
+
+/// The corners of `sprite` in its sheet's texture, as `uv0` and `uv1` for `ui.image`.
+pub fn sprite_uvs(sprite: &Sprite) -> ((f32, f32), (f32, f32)) {
+	let coords = &sprite.tex_coords;
+	((coords.left, coords.top), (coords.right, coords.bottom))
+}
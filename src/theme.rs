@@ -0,0 +1,240 @@
+use amethyst::ecs::prelude::*;
+use imgui::{sys, ImGuiCol, ImGuiCond, ImGuiStyle, ImVec4, Ui};
+
+/// Accent colors of imgui's dark style, and how much darker each one is than the accent.
+const ACCENTS: [(ImGuiCol, f32); 19] = [
+	(ImGuiCol::FrameBgHovered, 1.),
+	(ImGuiCol::FrameBgActive, 1.),
+	(ImGuiCol::TitleBgActive, 0.5),
+	(ImGuiCol::CheckMark, 1.),
+	(ImGuiCol::SliderGrab, 0.9),
+	(ImGuiCol::SliderGrabActive, 1.),
+	(ImGuiCol::Button, 1.),
+	(ImGuiCol::ButtonHovered, 1.),
+	(ImGuiCol::ButtonActive, 0.85),
+	(ImGuiCol::Header, 1.),
+	(ImGuiCol::HeaderHovered, 1.),
+	(ImGuiCol::HeaderActive, 1.),
+	(ImGuiCol::SeparatorHovered, 0.75),
+	(ImGuiCol::SeparatorActive, 0.75),
+	(ImGuiCol::ResizeGrip, 1.),
+	(ImGuiCol::ResizeGripHovered, 1.),
+	(ImGuiCol::ResizeGripActive, 1.),
+	(ImGuiCol::TextSelectedBg, 1.),
+	(ImGuiCol::NavHighlight, 1.),
+];
+
+/// Colors that have to stand out from the accent: hovered plots, histograms and drop targets.
+const HIGHLIGHTS: [(ImGuiCol, f32); 4] = [
+	(ImGuiCol::PlotLinesHovered, 1.),
+	(ImGuiCol::PlotHistogram, 0.85),
+	(ImGuiCol::PlotHistogramHovered, 1.),
+	(ImGuiCol::DragDropTarget, 1.),
+];
+
+/// Backgrounds made black and opaque by the high-contrast option.
+const BACKGROUNDS: [ImGuiCol; 9] = [
+	ImGuiCol::WindowBg,
+	ImGuiCol::ChildBg,
+	ImGuiCol::PopupBg,
+	ImGuiCol::FrameBg,
+	ImGuiCol::TitleBg,
+	ImGuiCol::TitleBgActive,
+	ImGuiCol::TitleBgCollapsed,
+	ImGuiCol::MenuBarBg,
+	ImGuiCol::ScrollbarBg,
+];
+
+pub(crate) fn gamma_to_linear(col: ImVec4) -> ImVec4 {
+	let x = col.x.powf(2.2);
+	let y = col.y.powf(2.2);
+	let z = col.z.powf(2.2);
+	let w = 1.0 - (1.0 - col.w).powf(2.2);
+	ImVec4::new(x, y, z, w)
+}
+
+fn multiply(matrix: &[[f32; 3]; 3], rgb: [f32; 3]) -> [f32; 3] {
+	let row = |i: usize| matrix[i][0] * rgb[0] + matrix[i][1] * rgb[1] + matrix[i][2] * rgb[2];
+	[row(0), row(1), row(2)]
+}
+
+/// A color vision deficiency to pick or remap the UI's colors for.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ColorBlindness {
+	Protanopia,
+	Deuteranopia,
+	Tritanopia,
+}
+
+impl ColorBlindness {
+	/// Machado et al.'s simulation at full severity, in linear RGB.
+	fn simulation(self) -> [[f32; 3]; 3] {
+		match self {
+			ColorBlindness::Protanopia => [
+				[0.152_286, 1.052_583, -0.204_868],
+				[0.114_503, 0.786_281, 0.099_216],
+				[-0.003_882, -0.048_116, 1.051_998],
+			],
+			ColorBlindness::Deuteranopia => [
+				[0.367_322, 0.860_646, -0.227_968],
+				[0.280_085, 0.672_501, 0.047_413],
+				[-0.011_820, 0.042_940, 0.968_881],
+			],
+			ColorBlindness::Tritanopia => [
+				[1.255_528, -0.076_749, -0.178_779],
+				[-0.078_411, 0.930_809, 0.147_602],
+				[0.004_733, 0.691_367, 0.303_900],
+			],
+		}
+	}
+
+	/// Where the difference lost to the deficiency is moved, to channels it can still tell apart.
+	fn shift(self) -> [[f32; 3]; 3] {
+		match self {
+			ColorBlindness::Protanopia | ColorBlindness::Deuteranopia => [[0., 0., 0.], [0.7, 1., 0.], [0.7, 0., 1.]],
+			ColorBlindness::Tritanopia => [[1., 0., 0.7], [0., 1., 0.7], [0., 0., 0.]],
+		}
+	}
+
+	/// Accent and highlight colors from the Okabe-Ito palette that stay apart with this deficiency.
+	fn palette(self) -> ([f32; 3], [f32; 3]) {
+		match self {
+			// Blue and orange
+			ColorBlindness::Protanopia | ColorBlindness::Deuteranopia => ([0., 0.447, 0.698], [0.902, 0.624, 0.]),
+			// Vermilion and sky blue
+			ColorBlindness::Tritanopia => ([0.835, 0.369, 0.], [0.337, 0.706, 0.914]),
+		}
+	}
+
+	/// Daltonizes `color`, given in gamma space: differences this deficiency can't see are added to ones it
+	/// can, so colors that only differed in hue for it stay distinguishable.
+	pub fn remap(self, color: ImVec4) -> ImVec4 {
+		let rgb = [color.x.powf(2.2), color.y.powf(2.2), color.z.powf(2.2)];
+		let seen = multiply(&self.simulation(), rgb);
+		let error = [rgb[0] - seen[0], rgb[1] - seen[1], rgb[2] - seen[2]];
+		let shift = multiply(&self.shift(), error);
+		let channel = |i: usize| (rgb[i] + shift[i]).max(0.).min(1.).powf(1. / 2.2);
+		ImVec4::new(channel(0), channel(1), channel(2), color.w)
+	}
+}
+
+/// Style colors to start from, set with `ImguiState::set_theme` or `ImguiConfig`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Theme {
+	Dark,
+	Light,
+	Classic,
+	/// The dark theme with accents that stay apart for this deficiency.
+	ColorBlind(ColorBlindness),
+}
+
+impl Default for Theme {
+	fn default() -> Self { Theme::Dark }
+}
+
+/// Theme and accessibility options. Insert it, e.g. loaded from a RON file, and `open_frame` applies it
+/// whenever it changes; `settings_window` edits it at runtime.
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImguiConfig {
+	pub theme: Theme,
+	/// Daltonize every style color for this deficiency, for themes that weren't designed for it.
+	pub remap: Option<ColorBlindness>,
+	/// Opaque black backgrounds, white text and borders, and borders around frames.
+	pub high_contrast: bool,
+	/// Don't blink the text cursor or fade in the Ctrl+Tab window highlight. Tools with animations of their
+	/// own should check it too.
+	pub reduced_motion: bool,
+}
+
+/// Sets `style`'s colors to `config`'s theme, then remaps them and converts them for sRGB framebuffers like
+/// `create_imgui` does.
+pub(crate) fn apply(style: &mut ImGuiStyle, config: &ImguiConfig, linear: bool) {
+	unsafe {
+		match config.theme {
+			Theme::Dark | Theme::ColorBlind(_) => sys::igStyleColorsDark(style),
+			Theme::Light => sys::igStyleColorsLight(style),
+			Theme::Classic => sys::igStyleColorsClassic(style),
+		}
+	}
+	if let Theme::ColorBlind(blindness) = config.theme {
+		let (accent, highlight) = blindness.palette();
+		for &(colors, palette) in &[(&ACCENTS[..], accent), (&HIGHLIGHTS[..], highlight)] {
+			for &(col, shade) in colors {
+				let color = &mut style.colors[col as usize];
+				*color = ImVec4::new(palette[0] * shade, palette[1] * shade, palette[2] * shade, color.w);
+			}
+		}
+	}
+	if config.high_contrast {
+		for &col in &BACKGROUNDS {
+			style.colors[col as usize] = ImVec4::new(0., 0., 0., 1.);
+		}
+		style.colors[ImGuiCol::Text as usize] = ImVec4::new(1., 1., 1., 1.);
+		style.colors[ImGuiCol::TextDisabled as usize] = ImVec4::new(0.75, 0.75, 0.75, 1.);
+		style.colors[ImGuiCol::Border as usize] = ImVec4::new(1., 1., 1., 1.);
+		style.colors[ImGuiCol::Separator as usize] = ImVec4::new(1., 1., 1., 1.);
+		for &(col, _) in &ACCENTS {
+			style.colors[col as usize].w = style.colors[col as usize].w.max(0.8);
+		}
+	}
+	// Imgui's default, which high contrast replaces
+	style.frame_border_size = if config.high_contrast { 1. } else { 0. };
+	if config.reduced_motion {
+		style.colors[ImGuiCol::NavWindowingHighlight as usize].w = 0.;
+	}
+	for color in style.colors.iter_mut() {
+		if let Some(blindness) = config.remap {
+			*color = blindness.remap(*color);
+		}
+		if linear {
+			*color = gamma_to_linear(*color);
+		}
+	}
+}
+
+const THEMES: [Theme; 6] = [
+	Theme::Dark,
+	Theme::Light,
+	Theme::Classic,
+	Theme::ColorBlind(ColorBlindness::Protanopia),
+	Theme::ColorBlind(ColorBlindness::Deuteranopia),
+	Theme::ColorBlind(ColorBlindness::Tritanopia),
+];
+
+const DEFICIENCIES: [ColorBlindness; 3] =
+	[ColorBlindness::Protanopia, ColorBlindness::Deuteranopia, ColorBlindness::Tritanopia];
+
+/// Edits `ImguiConfig`, with the signature `ToolWindows::register` takes.
+pub fn settings_window(ui: &Ui, world: &World, open: &mut bool) {
+	let mut config = match world.res.try_fetch_mut::<ImguiConfig>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let config = &mut *config;
+	ui.window(im_str!("Settings"))
+		.size((320., 160.), ImGuiCond::FirstUseEver)
+		.opened(open)
+		.build(|| {
+			let themes = [
+				im_str!("Dark"),
+				im_str!("Light"),
+				im_str!("Classic"),
+				im_str!("Protanopia"),
+				im_str!("Deuteranopia"),
+				im_str!("Tritanopia"),
+			];
+			let mut theme = THEMES.iter().position(|x| *x == config.theme).unwrap_or(0) as i32;
+			if ui.combo(im_str!("Theme"), &mut theme, &themes, 6) {
+				config.theme = THEMES[theme as usize];
+			}
+			let remaps = [im_str!("Off"), im_str!("Protanopia"), im_str!("Deuteranopia"), im_str!("Tritanopia")];
+			let remap = config.remap.and_then(|x| DEFICIENCIES.iter().position(|y| *y == x));
+			let mut remap = remap.map_or(0, |x| x + 1) as i32;
+			if ui.combo(im_str!("Remap colors"), &mut remap, &remaps, 4) {
+				config.remap = (remap as usize).checked_sub(1).map(|x| DEFICIENCIES[x]);
+			}
+			ui.checkbox(im_str!("High contrast"), &mut config.high_contrast);
+			ui.checkbox(im_str!("Reduced motion"), &mut config.reduced_motion);
+		});
+}
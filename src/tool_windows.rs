@@ -0,0 +1,131 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::Arc};
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImString, Ui};
+use ron;
+
+type DrawWindow = Arc<dyn Fn(&Ui, &World, &mut bool) + Send + Sync>;
+
+struct ToolWindow {
+	name: ImString,
+	open: bool,
+	draw: DrawWindow,
+}
+
+/// Named tool windows listed in a "Windows" menu of the main menu bar, drawn by `tool_windows`.
+///
+/// When using `MainMenu`, turn `show_menu` off and add `menu_items` to it instead.
+///
+/// ```rust,ignore
+/// world.write_resource::<ToolWindows>().register("Plots", |ui, world, open| {
+/// 	ui.window(im_str!("Plots")).opened(open).build(|| { /* ... */ });
+/// });
+/// ```
+pub struct ToolWindows {
+	windows: Vec<ToolWindow>,
+	/// Open state from a loaded layout for windows that weren't registered yet.
+	layout: HashMap<String, bool>,
+	pub show_menu: bool,
+}
+
+impl Default for ToolWindows {
+	fn default() -> Self {
+		Self {
+			windows: Vec::new(),
+			layout: HashMap::new(),
+			show_menu: true,
+		}
+	}
+}
+
+impl ToolWindows {
+	/// Adds a window, or replaces the one with the same name; `draw` only runs while it's open.
+	pub fn register<S, F>(&mut self, name: S, draw: F)
+	where
+		S: Into<String>,
+		F: Fn(&Ui, &World, &mut bool) + Send + Sync + 'static,
+	{
+		let name = name.into();
+		let open = match self.windows.iter().position(|x| x.name.to_str() == name) {
+			Some(index) => self.windows.remove(index).open,
+			None => self.layout.remove(&name).unwrap_or(false),
+		};
+		self.windows.push(ToolWindow {
+			name: ImString::new(name),
+			open,
+			draw: Arc::new(draw),
+		});
+	}
+
+	pub fn names(&self) -> impl Iterator<Item = &str> { self.windows.iter().map(|x| x.name.to_str()) }
+
+	pub fn unregister(&mut self, name: &str) { self.windows.retain(|x| x.name.to_str() != name); }
+
+	pub fn is_open(&self, name: &str) -> bool { self.windows.iter().any(|x| x.open && x.name.to_str() == name) }
+
+	pub fn set_open(&mut self, name: &str, open: bool) {
+		match self.windows.iter_mut().find(|x| x.name.to_str() == name) {
+			Some(window) => window.open = open,
+			None => {
+				self.layout.insert(name.to_owned(), open);
+			},
+		}
+	}
+
+	/// Saves which windows are open as RON.
+	pub fn save_layout<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let mut layout = self.layout.clone();
+		layout.extend(self.windows.iter().map(|x| (x.name.to_str().to_owned(), x.open)));
+		let source = ron::ser::to_string_pretty(&layout, Default::default())
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		fs::write(path, source)
+	}
+
+	pub fn load_layout<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+		let source = fs::read_to_string(path)?;
+		let layout: HashMap<String, bool> =
+			ron::de::from_str(&source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+		for (name, open) in layout {
+			self.set_open(&name, open);
+		}
+		Ok(())
+	}
+
+	/// Menu items toggling every registered window.
+	pub fn menu_items(&mut self, ui: &Ui) {
+		for window in &mut self.windows {
+			ui.menu_item(&window.name).selected(&mut window.open).build();
+		}
+	}
+}
+
+pub fn tool_windows(ui: &Ui, world: &World) {
+	let open_windows = {
+		let mut windows = match world.res.try_fetch_mut::<ToolWindows>() {
+			Some(x) => x,
+			_ => return,
+		};
+		if windows.show_menu {
+			ui.main_menu_bar(|| {
+				ui.menu(im_str!("Windows")).build(|| windows.menu_items(ui));
+			});
+		}
+		windows
+			.windows
+			.iter()
+			.filter(|x| x.open)
+			.map(|x| (x.name.to_str().to_owned(), x.draw.clone()))
+			.collect::<Vec<_>>()
+	};
+
+	// Draw without holding the resource, so windows can use it themselves
+	for (name, draw) in open_windows {
+		let mut open = true;
+		draw(ui, world, &mut open);
+		if !open {
+			if let Some(mut windows) = world.res.try_fetch_mut::<ToolWindows>() {
+				windows.set_open(&name, false);
+			}
+		}
+	}
+}
@@ -0,0 +1,236 @@
+use std::{
+	collections::VecDeque,
+	fmt::{self, Write as _},
+	sync::{Arc, Mutex},
+	time::Instant,
+};
+
+use amethyst::ecs::prelude::*;
+use imgui::{sys, ImGuiCond, ImString, Ui};
+use tracing_core::{
+	field::{Field, Visit},
+	span::Id,
+	Event,
+	Level,
+	Subscriber,
+};
+use tracing_subscriber::{
+	layer::{Context, Layer},
+	registry::LookupSpan,
+};
+
+use crate::{clipper::clipped_rows, profiler::Profiler, telemetry::Telemetry, watchdog::Watchdog};
+
+const LEVELS: [Level; 5] = [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE];
+
+/// A `tracing` event as shown by `tracing_window`.
+#[derive(Clone, Debug)]
+pub struct TracingEvent {
+	pub level: Level,
+	pub target: String,
+	/// The message followed by the other fields as `name=value`.
+	pub message: String,
+}
+
+type Events = Arc<Mutex<VecDeque<TracingEvent>>>;
+
+/// A `tracing-subscriber` layer timing spans into a `Profiler` and collecting events for a `TracingConsole`.
+///
+/// A span is recorded each time it's entered, from enter to exit.
+pub struct ImguiLayer {
+	profiler: Profiler,
+	events: Events,
+	capacity: usize,
+	/// Events since `tracing_window` last ran, to forward to `Telemetry`.
+	pending: Events,
+}
+
+struct Entered(Instant);
+
+#[derive(Default)]
+struct Message(String, String);
+
+impl Visit for Message {
+	fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+		if field.name() == "message" {
+			let _ = write!(self.0, "{:?}", value);
+		} else {
+			let _ = write!(self.1, " {}={:?}", field.name(), value);
+		}
+	}
+}
+
+impl<S> Layer<S> for ImguiLayer
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			span.extensions_mut().insert(Entered(Instant::now()));
+		}
+	}
+
+	fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			if let Some(Entered(start)) = span.extensions_mut().remove::<Entered>() {
+				self.profiler.record(span.name(), start, Instant::now());
+			}
+		}
+	}
+
+	fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+		let mut message = Message::default();
+		event.record(&mut message);
+		let event = TracingEvent {
+			level: *event.metadata().level(),
+			target: event.metadata().target().to_owned(),
+			message: message.0 + &message.1,
+		};
+		if let Ok(mut pending) = self.pending.lock() {
+			if pending.len() >= self.capacity {
+				pending.pop_front();
+			}
+			pending.push_back(event.clone());
+		}
+		if let Ok(mut events) = self.events.lock() {
+			if events.len() >= self.capacity {
+				events.pop_front();
+			}
+			events.push_back(event);
+		}
+	}
+}
+
+/// Shows events collected by its `ImguiLayer`, drawn by `tracing_window`.
+///
+/// ```rust,ignore
+/// let profiler = Profiler::default();
+/// let console = TracingConsole::default();
+/// tracing_subscriber::registry().with(console.layer(&profiler)).init();
+/// world.add_resource(profiler);
+/// world.add_resource(console);
+/// ```
+pub struct TracingConsole {
+	pub open: bool,
+	/// Most verbose level shown, as an index into error, warn, info, debug and trace.
+	pub level: usize,
+	/// Events kept by the layer.
+	pub capacity: usize,
+	events: Events,
+	pending: Events,
+	filter: ImString,
+}
+
+impl Default for TracingConsole {
+	fn default() -> Self {
+		Self {
+			open: false,
+			level: 2,
+			capacity: 1024,
+			events: Events::default(),
+			pending: Events::default(),
+			filter: ImString::with_capacity(64),
+		}
+	}
+}
+
+impl TracingConsole {
+	/// A layer feeding this console and timing spans into `profiler`.
+	pub fn layer(&self, profiler: &Profiler) -> ImguiLayer {
+		ImguiLayer {
+			profiler: profiler.clone(),
+			events: self.events.clone(),
+			capacity: self.capacity.max(1),
+			pending: self.pending.clone(),
+		}
+	}
+
+	pub fn clear(&self) {
+		if let Ok(mut events) = self.events.lock() {
+			events.clear();
+		}
+	}
+}
+
+fn level_color(level: &Level) -> [f32; 4] {
+	match *level {
+		Level::ERROR => [1., 0.35, 0.35, 1.],
+		Level::WARN => [1., 0.8, 0.3, 1.],
+		Level::INFO => [1., 1., 1., 1.],
+		_ => [0.6, 0.6, 0.6, 1.],
+	}
+}
+
+/// Forwards new events to the `Telemetry` and `Watchdog` logs and, while open, shows the console.
+pub fn tracing_window(ui: &Ui, world: &World) {
+	let mut console = match world.res.try_fetch_mut::<TracingConsole>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let console = &mut *console;
+	let pending = console.pending.lock().map(|mut x| x.drain(..).collect::<Vec<_>>()).unwrap_or_default();
+	let mut telemetry = world.res.try_fetch_mut::<Telemetry>();
+	let mut watchdog = world.res.try_fetch_mut::<Watchdog>();
+	for event in pending {
+		let line = format!("{} {}: {}", event.level, event.target, event.message);
+		if let Some(ref mut watchdog) = watchdog {
+			watchdog.log(line.as_str());
+		}
+		if let Some(ref mut telemetry) = telemetry {
+			telemetry.log(line);
+		}
+	}
+	if !console.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Tracing"))
+		.size((600., 320.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			let names = [im_str!("Error"), im_str!("Warn"), im_str!("Info"), im_str!("Debug"), im_str!("Trace")];
+			let mut level = console.level.min(LEVELS.len() - 1) as i32;
+			ui.push_item_width(100.);
+			ui.combo(im_str!("Level"), &mut level, &names, 5);
+			ui.pop_item_width();
+			console.level = level as usize;
+			ui.same_line(0.);
+			ui.input_text(im_str!("Filter"), &mut console.filter).build();
+			ui.same_line(0.);
+			if ui.small_button(im_str!("Clear")) {
+				console.clear();
+			}
+			ui.separator();
+
+			let filter = console.filter.to_str().to_lowercase();
+			let shown = &LEVELS[..=console.level];
+			let events = match console.events.lock() {
+				Ok(x) => x,
+				_ => return,
+			};
+			let events = events
+				.iter()
+				.filter(|x| shown.contains(&x.level))
+				.filter(|x| {
+					filter.is_empty()
+						|| x.message.to_lowercase().contains(&filter)
+						|| x.target.to_lowercase().contains(&filter)
+				})
+				.collect::<Vec<_>>();
+			ui.child_frame(im_str!("##log"), (0., 0.)).build(|| {
+				clipped_rows(events.len(), None, |i| {
+					let event = events[i];
+					ui.text_colored(level_color(&event.level), event.level.to_string());
+					ui.same_line(0.);
+					ui.text_disabled(&im_str!("{}", event.target));
+					ui.same_line(0.);
+					ui.text(event.message.as_str());
+				});
+				if unsafe { sys::igGetScrollY() >= sys::igGetScrollMaxY() } {
+					unsafe { sys::igSetScrollHere(1.) };
+				}
+			});
+		});
+	console.open = open;
+}
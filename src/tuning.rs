@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImString, Ui};
+
+use crate::{
+	debug_menus::{MenuValue, MenuValues},
+	tables::Table,
+};
+
+/// Named copies of the `MenuValues` tweaked through the debug menus, to switch between and diff while
+/// playing; drawn by `tuning_window`.
+pub struct TuningSnapshots {
+	pub open: bool,
+	snapshots: Vec<(String, MenuValues)>,
+	/// Snapshots compared in the diff.
+	a: usize,
+	b: usize,
+	/// The snapshot last applied, so A/B switches to the other one.
+	applied: Option<usize>,
+	changed_only: bool,
+	name: ImString,
+	table: Table,
+}
+
+impl Default for TuningSnapshots {
+	fn default() -> Self {
+		Self {
+			open: false,
+			snapshots: Vec::new(),
+			a: 0,
+			b: 1,
+			applied: None,
+			changed_only: true,
+			name: ImString::with_capacity(64),
+			table: Table::default(),
+		}
+	}
+}
+
+impl TuningSnapshots {
+	/// Copies `values` into a snapshot called `name`, replacing one with the same name.
+	pub fn take(&mut self, name: &str, values: &MenuValues) {
+		match self.snapshots.iter_mut().find(|x| x.0 == name) {
+			Some(snapshot) => snapshot.1 = values.clone(),
+			None => self.snapshots.push((name.to_owned(), values.clone())),
+		}
+	}
+
+	/// Writes a snapshot's values back; values added after it was taken keep their current value.
+	pub fn apply(&mut self, name: &str, values: &mut MenuValues) -> bool {
+		let index = match self.snapshots.iter().position(|x| x.0 == name) {
+			Some(x) => x,
+			_ => return false,
+		};
+		for (name, value) in self.snapshots[index].1.iter() {
+			values.set(name.as_str(), value.clone());
+		}
+		self.applied = Some(index);
+		true
+	}
+
+	pub fn remove(&mut self, name: &str) {
+		self.snapshots.retain(|x| x.0 != name);
+		self.applied = None;
+	}
+
+	pub fn names(&self) -> impl Iterator<Item = &str> { self.snapshots.iter().map(|x| x.0.as_str()) }
+}
+
+fn format(value: Option<&MenuValue>) -> String {
+	match value {
+		Some(MenuValue::Float(x)) => format!("{:.3}", x),
+		Some(MenuValue::Int(x)) => x.to_string(),
+		Some(MenuValue::Bool(x)) => x.to_string(),
+		Some(MenuValue::Text(x)) => format!("{:?}", x),
+		None => "-".to_owned(),
+	}
+}
+
+pub fn tuning_window(ui: &Ui, world: &World) {
+	let mut snapshots = match world.res.try_fetch_mut::<TuningSnapshots>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let snapshots = &mut *snapshots;
+	if !snapshots.open {
+		return;
+	}
+	let mut values = match world.res.try_fetch_mut::<MenuValues>() {
+		Some(x) => x,
+		_ => return,
+	};
+
+	let mut open = true;
+	ui.window(im_str!("Tuning snapshots"))
+		.size((520., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.input_text(im_str!("##name"), &mut snapshots.name).build();
+			ui.same_line(0.);
+			if ui.button(im_str!("Snapshot"), (0., 0.)) {
+				let name = match snapshots.name.to_str() {
+					"" => format!("Snapshot {}", snapshots.snapshots.len() + 1),
+					x => x.to_owned(),
+				};
+				snapshots.take(&name, &values);
+				snapshots.name.clear();
+			}
+
+			let mut apply = None;
+			let mut remove = None;
+			for (i, (name, _)) in snapshots.snapshots.iter().enumerate() {
+				let marker = if snapshots.applied == Some(i) { "*" } else { " " };
+				ui.text(format!("{} {}", marker, name));
+				ui.same_line(200.);
+				if ui.small_button(&im_str!("Apply##{}", i)) {
+					apply = Some(name.clone());
+				}
+				ui.same_line(0.);
+				if ui.small_button(&im_str!("x##{}", i)) {
+					remove = Some(name.clone());
+				}
+			}
+			if let Some(name) = remove {
+				snapshots.remove(&name);
+			}
+			if snapshots.snapshots.len() < 2 {
+				ui.text_disabled(im_str!("Take two snapshots to compare them"));
+			} else {
+				let names = snapshots.snapshots.iter().map(|x| ImString::new(x.0.as_str())).collect::<Vec<_>>();
+				let names = names.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+				let last = names.len() - 1;
+				let (mut a, mut b) = (snapshots.a.min(last) as i32, snapshots.b.min(last) as i32);
+				ui.separator();
+				ui.push_item_width(150.);
+				ui.combo(im_str!("A"), &mut a, &names, 10);
+				ui.same_line(0.);
+				ui.combo(im_str!("B"), &mut b, &names, 10);
+				ui.pop_item_width();
+				snapshots.a = a as usize;
+				snapshots.b = b as usize;
+				ui.same_line(0.);
+				if ui.button(im_str!("Switch A/B"), (0., 0.)) {
+					let next = if snapshots.applied == Some(snapshots.a) { snapshots.b } else { snapshots.a };
+					apply = Some(snapshots.snapshots[next].0.clone());
+				}
+				ui.checkbox(im_str!("Differences only"), &mut snapshots.changed_only);
+
+				let (a, b) = (&snapshots.snapshots[snapshots.a].1, &snapshots.snapshots[snapshots.b].1);
+				let names = a.iter().chain(b.iter()).map(|x| x.0.clone()).collect::<BTreeSet<_>>();
+				let mut rows = names
+					.into_iter()
+					.map(|name| (format(a.get(&name)), format(b.get(&name)), format(values.get(&name)), name))
+					.filter(|x| !snapshots.changed_only || x.0 != x.1)
+					.collect::<Vec<_>>();
+				snapshots.table.sort(&mut rows, |x, y, column| match column {
+					1 => x.0.cmp(&y.0),
+					2 => x.1.cmp(&y.1),
+					3 => x.2.cmp(&y.2),
+					_ => x.3.cmp(&y.3),
+				});
+				snapshots.table.begin(ui, im_str!("diff"), &["Value", "A", "B", "Current"]);
+				for (a, b, current, name) in &rows {
+					ui.text(name.as_str());
+					ui.next_column();
+					for value in &[a, b, current] {
+						if a != b {
+							ui.text_colored([1., 0.8, 0.3, 1.], value.as_str());
+						} else {
+							ui.text(value.as_str());
+						}
+						ui.next_column();
+					}
+				}
+				snapshots.table.end(ui);
+			}
+			if let Some(name) = apply {
+				snapshots.apply(&name, &mut values);
+			}
+		});
+	snapshots.open = open;
+}
@@ -0,0 +1,130 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use imgui::{sys, ImString, Ui};
+use serde_json::{Number, Value};
+
+thread_local! {
+	/// Key being typed for each map's "add" field, by imgui id.
+	static NEW_KEYS: RefCell<HashMap<sys::ImGuiID, ImString>> = RefCell::new(HashMap::new());
+}
+
+fn number(ui: &Ui, id: &ImString, x: &mut Number) -> bool {
+	let small_int = x.as_i64().filter(|x| *x >= i64::from(std::i32::MIN) && *x <= i64::from(std::i32::MAX));
+	if let Some(int) = small_int {
+		let mut int = int as i32;
+		if ui.input_int(id, &mut int).build() {
+			*x = Number::from(int);
+			return true;
+		}
+	} else if let Some(float) = x.as_f64() {
+		let mut float = float as f32;
+		if ui.input_float(id, &mut float).build() {
+			if let Some(number) = Number::from_f64(f64::from(float)) {
+				*x = number;
+				return true;
+			}
+		}
+	}
+	false
+}
+
+/// Edits any serde value in place: checkboxes, number and text inputs for scalars, collapsible nodes for arrays
+/// and maps with buttons to add and remove entries. Returns whether anything changed.
+///
+/// Typed data goes through `serde_json::to_value` and comes back with `serde_json::from_value`, which also
+/// checks edits still fit the type.
+pub fn edit_value(ui: &Ui, label: &str, value: &mut Value) -> bool {
+	// Ids follow the path of labels and keys, which stays put when values move in memory
+	unsafe { sys::igPushIDStr(ImString::new(label).as_ptr()) };
+	let changed = edit_node(ui, label, value);
+	unsafe { sys::igPopID() };
+	changed
+}
+
+fn edit_node(ui: &Ui, label: &str, value: &mut Value) -> bool {
+	let id = ImString::new(format!("{}##value", label));
+	match value {
+		Value::Null => {
+			ui.text(format!("{}: null", label));
+			false
+		},
+		Value::Bool(x) => ui.checkbox(&id, x),
+		Value::Number(x) => number(ui, &id, x),
+		Value::String(x) => {
+			let mut buffer = ImString::with_capacity(x.len() + 128);
+			buffer.push_str(x);
+			let changed = ui.input_text(&id, &mut buffer).build();
+			if changed {
+				*x = buffer.to_str().to_owned();
+			}
+			changed
+		},
+		Value::Array(items) => {
+			let mut changed = false;
+			ui.tree_node(&id).label(&im_str!("{} [{}]", label, items.len())).build(|| {
+				let mut removed = None;
+				for (i, item) in items.iter_mut().enumerate() {
+					unsafe { sys::igPushIDInt(i as i32) };
+					if ui.small_button(im_str!("x")) {
+						removed = Some(i);
+					}
+					ui.same_line(0.);
+					changed |= edit_value(ui, &i.to_string(), item);
+					unsafe { sys::igPopID() };
+				}
+				if let Some(i) = removed {
+					items.remove(i);
+					changed = true;
+				}
+				// New items copy the last one, so they have the right shape
+				if ui.small_button(im_str!("+")) {
+					let item = items.last().cloned().unwrap_or(Value::Null);
+					items.push(item);
+					changed = true;
+				}
+			});
+			changed
+		},
+		Value::Object(fields) => {
+			let mut changed = false;
+			ui.tree_node(&id).label(&im_str!("{} {{{}}}", label, fields.len())).build(|| {
+				let mut removed = None;
+				for (key, field) in fields.iter_mut() {
+					unsafe { sys::igPushIDStr(ImString::new(key.as_str()).as_ptr()) };
+					if ui.small_button(im_str!("x")) {
+						removed = Some(key.clone());
+					}
+					ui.same_line(0.);
+					changed |= edit_value(ui, key, field);
+					unsafe { sys::igPopID() };
+				}
+				if let Some(key) = removed {
+					fields.remove(&key);
+					changed = true;
+				}
+				let key = NEW_KEYS.with(|keys| {
+					let mut keys = keys.borrow_mut();
+					let new_key = im_str!("##new_key");
+					let buffer = keys.entry(unsafe { sys::igGetIDStr(new_key.as_ptr()) });
+					let buffer = buffer.or_insert_with(|| ImString::with_capacity(64));
+					ui.input_text(new_key, buffer).build();
+					ui.same_line(0.);
+					let key = buffer.to_str().to_owned();
+					if ui.small_button(im_str!("+")) && !key.is_empty() {
+						buffer.clear();
+						Some(key)
+					} else {
+						None
+					}
+				});
+				if let Some(key) = key {
+					// Like arrays, new entries copy an existing one
+					let field = fields.values().next().cloned().unwrap_or(Value::Null);
+					fields.entry(key).or_insert(field);
+					changed = true;
+				}
+			});
+			changed
+		},
+	}
+}
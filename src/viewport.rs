@@ -0,0 +1,74 @@
+use amethyst::ecs::prelude::*;
+use imgui::{ImGuiCond, ImTexture, Ui};
+
+/// Editor-style "Viewport" window showing the game rendered into a texture, drawn by `viewport_window`.
+///
+/// imgui 0.0.21 can't dock, so it's a regular window; the image keeps the game's aspect ratio and mouse
+/// positions over it are translated back into game pixels.
+#[derive(Default)]
+pub struct Viewport {
+	pub open: bool,
+	/// The texture the game is rendered into, e.g. from `ImguiTextures::get`.
+	pub texture: Option<ImTexture>,
+	/// Size of the game's framebuffer in pixels.
+	pub game_size: (f32, f32),
+	/// Where the image was drawn last frame, as screen-space top left corner and size.
+	pub rect: Option<((f32, f32), (f32, f32))>,
+	pub hovered: bool,
+	/// Mouse position in game pixels, while it's over the image.
+	pub mouse: Option<(f32, f32)>,
+}
+
+impl Viewport {
+	/// Translates a screen-space position to game pixels, if it's over the image.
+	pub fn to_game(&self, (x, y): (f32, f32)) -> Option<(f32, f32)> {
+		let ((left, top), (width, height)) = self.rect?;
+		let (u, v) = ((x - left) / width, (y - top) / height);
+		if u < 0. || v < 0. || u > 1. || v > 1. {
+			return None;
+		}
+		Some((u * self.game_size.0, v * self.game_size.1))
+	}
+}
+
+pub fn viewport_window(ui: &Ui, world: &World) {
+	let mut viewport = match world.res.try_fetch_mut::<Viewport>() {
+		Some(x) => x,
+		_ => return,
+	};
+	viewport.rect = None;
+	viewport.hovered = false;
+	viewport.mouse = None;
+	if !viewport.open {
+		return;
+	}
+
+	let mut open = viewport.open;
+	ui.window(im_str!("Viewport"))
+		.size((640., 400.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			let (texture, (game_width, game_height)) = match viewport.texture {
+				Some(x) if viewport.game_size.0 > 0. && viewport.game_size.1 > 0. => (x, viewport.game_size),
+				_ => {
+					ui.text_disabled("No game texture");
+					return;
+				},
+			};
+			let (available_width, available_height) = ui.get_content_region_avail();
+			let scale = (available_width / game_width).min(available_height / game_height).max(0.);
+			let size = (game_width * scale, game_height * scale);
+			let (x, y) = ui.get_cursor_screen_pos();
+			let offset = ((available_width - size.0) / 2., (available_height - size.1) / 2.);
+			let (cursor_x, cursor_y) = ui.get_cursor_pos();
+			ui.set_cursor_pos((cursor_x + offset.0, cursor_y + offset.1));
+			ui.image(texture, size).build();
+
+			viewport.rect = Some(((x + offset.0, y + offset.1), size));
+			viewport.hovered = ui.is_item_hovered();
+			if viewport.hovered {
+				viewport.mouse = viewport.to_game(ui.imgui().mouse_pos());
+			}
+		});
+	viewport.open = open;
+}
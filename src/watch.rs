@@ -0,0 +1,159 @@
+use std::{collections::VecDeque, fmt::Debug};
+
+use amethyst::{
+	core::timing::Time,
+	ecs::{
+		prelude::*,
+		storage::{ComponentEvent, Tracked},
+	},
+	shrev::ReaderId,
+};
+use imgui::{ImGuiCond, Ui};
+
+use crate::{context_menus::entity_context_menu, selection::SelectedEntity};
+
+const MAX_LOG: usize = 256;
+
+type Watcher = Box<dyn FnMut(&World, Option<Entity>) -> Vec<String> + Send + Sync>;
+
+/// "Break on change" for entities: logs, and optionally pauses time, when a registered component of the
+/// watched entity changes. Drawn by `watch_window`.
+///
+/// Only components with a tracked storage like `FlaggedStorage` can be registered. specs flags every mutable
+/// access, so writes that store the same value show up too.
+pub struct ChangeWatch {
+	pub open: bool,
+	pub pause: bool,
+	pub watched: Option<Entity>,
+	pub log: VecDeque<String>,
+	watchers: Vec<Watcher>,
+	/// Time scale to go back to when resuming.
+	paused_scale: Option<f32>,
+}
+
+impl Default for ChangeWatch {
+	fn default() -> Self {
+		Self {
+			open: false,
+			pause: false,
+			watched: None,
+			log: VecDeque::new(),
+			watchers: Vec::new(),
+			paused_scale: None,
+		}
+	}
+}
+
+impl ChangeWatch {
+	pub fn register<T>(&mut self, world: &World, name: &str)
+	where
+		T: Component + Debug,
+		T::Storage: Tracked,
+	{
+		let mut reader: ReaderId<ComponentEvent> = world.write_storage::<T>().register_reader();
+		let name = name.to_owned();
+		self.watchers.push(Box::new(move |world, watched| {
+			let storage = world.read_storage::<T>();
+			let mut out = Vec::new();
+			// Always drain the events so they don't pile up while nothing is watched
+			for event in storage.channel().read(&mut reader) {
+				let (id, what) = match *event {
+					ComponentEvent::Inserted(id) => (id, "inserted"),
+					ComponentEvent::Modified(id) => (id, "modified"),
+					ComponentEvent::Removed(id) => (id, "removed"),
+				};
+				if let Some(entity) = watched.filter(|x| x.id() == id) {
+					out.push(match storage.get(entity) {
+						Some(value) => format!("Entity {}: {} {}: {:?}", id, name, what, value),
+						None => format!("Entity {}: {} {}", id, name, what),
+					});
+				}
+			}
+			out
+		}));
+	}
+
+	pub fn is_paused(&self) -> bool { self.paused_scale.is_some() }
+
+	fn resume(&mut self, world: &World) {
+		if let (Some(scale), Some(mut time)) = (self.paused_scale.take(), world.res.try_fetch_mut::<Time>()) {
+			time.set_time_scale(scale);
+		}
+	}
+}
+
+pub fn watch_window(ui: &Ui, world: &World) {
+	let mut fetched = match world.res.try_fetch_mut::<ChangeWatch>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let watch = &mut *fetched;
+	if watch.watched.map_or(false, |x| !world.is_alive(x)) {
+		watch.watched = None;
+	}
+	let watched = watch.watched;
+	let changes = watch.watchers.iter_mut().flat_map(|x| x(world, watched)).collect::<Vec<_>>();
+	if !changes.is_empty() && watch.pause && watch.paused_scale.is_none() {
+		if let Some(mut time) = world.res.try_fetch_mut::<Time>() {
+			watch.paused_scale = Some(time.time_scale());
+			time.set_time_scale(0.);
+		}
+	}
+	for change in changes {
+		watch.log.push_back(change);
+		if watch.log.len() > MAX_LOG {
+			watch.log.pop_front();
+		}
+	}
+
+	if !watch.open {
+		return;
+	}
+	let selected = world.res.try_fetch::<SelectedEntity>().and_then(|x| x.0);
+	let mut open = true;
+	let mut resume = false;
+	let mut action = None;
+	ui.window(im_str!("Watch"))
+		.size((420., 300.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			match watch.watched {
+				Some(entity) => {
+					ui.text(format!("Watching entity {}", entity.id()));
+					action = entity_context_menu(ui, world, entity);
+					ui.same_line(0.);
+					if ui.small_button(im_str!("Stop")) {
+						watch.watched = None;
+					}
+				},
+				None => ui.text_disabled("Not watching"),
+			}
+			if let Some(entity) = selected.filter(|x| watch.watched != Some(*x)) {
+				if ui.button(&im_str!("Break on change (entity {})", entity.id()), (0., 0.)) {
+					watch.watched = Some(entity);
+				}
+			}
+			ui.checkbox(im_str!("Pause time on change"), &mut watch.pause);
+			if watch.paused_scale.is_some() {
+				ui.same_line(0.);
+				resume = ui.small_button(im_str!("Resume"));
+			}
+			ui.separator();
+			if ui.small_button(im_str!("Clear log")) {
+				watch.log.clear();
+			}
+			ui.child_frame(im_str!("##log"), (0., 0.)).build(|| {
+				for line in &watch.log {
+					ui.text(line);
+				}
+			});
+		});
+	watch.open = open;
+	if resume {
+		watch.resume(world);
+	}
+	drop(fetched);
+	if let Some(action) = action {
+		action(world);
+	}
+}
@@ -0,0 +1,181 @@
+use std::{
+	collections::VecDeque,
+	fs,
+	path::PathBuf,
+	time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use amethyst::{core::timing::Time, ecs::prelude::*};
+use imgui::{ImGuiCond, Ui};
+
+use crate::{
+	profiler::{Profiler, Span},
+	ImguiState,
+};
+
+/// What was going on during a frame longer than `Watchdog::threshold_ms`.
+#[derive(Clone, Debug)]
+pub struct HitchReport {
+	/// Seconds since the watchdog was created.
+	pub time: f32,
+	pub frame_ms: f32,
+	/// The profiler's spans for the frame, longest first.
+	pub spans: Vec<Span>,
+	/// The last `Watchdog::log_lines` lines logged before the hitch.
+	pub log: Vec<String>,
+	/// Overlay screenshot of the frame after the hitch.
+	pub screenshot: Option<PathBuf>,
+	/// Why the screenshot couldn't be taken.
+	pub screenshot_error: Option<String>,
+}
+
+/// Catches frames longer than `threshold_ms` and keeps a `HitchReport` of each, listed by `watchdog_window`.
+pub struct Watchdog {
+	pub open: bool,
+	pub enabled: bool,
+	pub threshold_ms: f32,
+	/// Frames ignored after creating the watchdog, to skip loading.
+	pub warmup_frames: u32,
+	pub screenshots: bool,
+	pub dir: PathBuf,
+	pub max_reports: usize,
+	pub log_lines: usize,
+	pub reports: VecDeque<HitchReport>,
+	log: VecDeque<String>,
+	frames: u32,
+	start: Instant,
+}
+
+impl Default for Watchdog {
+	fn default() -> Self {
+		Self {
+			open: false,
+			enabled: true,
+			threshold_ms: 50.,
+			warmup_frames: 60,
+			screenshots: false,
+			dir: PathBuf::from("hitches"),
+			max_reports: 32,
+			log_lines: 20,
+			reports: VecDeque::new(),
+			log: VecDeque::new(),
+			frames: 0,
+			start: Instant::now(),
+		}
+	}
+}
+
+impl Watchdog {
+	/// Keeps `message` for the log tail of the next reports; the tracing console forwards its events here.
+	pub fn log<S: Into<String>>(&mut self, message: S) {
+		while self.log.len() >= self.log_lines.max(1) {
+			self.log.pop_front();
+		}
+		self.log.push_back(message.into());
+	}
+
+	/// Checks the last frame's time; `watchdog_window` calls it every frame.
+	pub fn check(&mut self, world: &World) {
+		self.frames = self.frames.saturating_add(1);
+		let frame_ms = world.res.try_fetch::<Time>().map_or(0., |x| x.delta_real_seconds() * 1000.);
+		if !self.enabled || self.frames <= self.warmup_frames || frame_ms < self.threshold_ms {
+			return;
+		}
+
+		let mut spans = world.res.try_fetch::<Profiler>().map(|x| x.last_frame()).unwrap_or_default();
+		spans.sort_by(|a, b| b.duration.cmp(&a.duration));
+		let (screenshot, screenshot_error) = match self.screenshot(world) {
+			Ok(x) => (x, None),
+			Err(e) => (None, Some(e)),
+		};
+		let elapsed = self.start.elapsed();
+		if self.reports.len() >= self.max_reports.max(1) {
+			self.reports.pop_front();
+		}
+		self.reports.push_back(HitchReport {
+			time: elapsed.as_secs() as f32 + elapsed.subsec_millis() as f32 / 1000.,
+			frame_ms,
+			spans,
+			log: self.log.iter().cloned().collect(),
+			screenshot,
+			screenshot_error,
+		});
+	}
+
+	fn screenshot(&self, world: &World) -> Result<Option<PathBuf>, String> {
+		if !self.screenshots {
+			return Ok(None);
+		}
+		let mut imgui_state = match world.res.try_fetch_mut::<Option<ImguiState>>() {
+			Some(x) => x,
+			_ => return Ok(None),
+		};
+		let imgui_state = match *imgui_state {
+			Some(ref mut x) => x,
+			_ => return Ok(None),
+		};
+		fs::create_dir_all(&self.dir).map_err(|e| format!("Can't create {}: {}", self.dir.display(), e))?;
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+		let path = self.dir.join(format!("hitch-{}-{:03}.png", now.as_secs(), now.subsec_millis()));
+		imgui_state.capture_next_frame(path.clone());
+		Ok(Some(path))
+	}
+}
+
+/// Checks for a hitch and, while open, lists the reports.
+pub fn watchdog_window(ui: &Ui, world: &World) {
+	let mut watchdog = match world.res.try_fetch_mut::<Watchdog>() {
+		Some(x) => x,
+		_ => return,
+	};
+	let watchdog = &mut *watchdog;
+	watchdog.check(world);
+	if !watchdog.open {
+		return;
+	}
+
+	let mut open = true;
+	ui.window(im_str!("Hitches"))
+		.size((460., 420.), ImGuiCond::FirstUseEver)
+		.opened(&mut open)
+		.build(|| {
+			ui.checkbox(im_str!("Enabled"), &mut watchdog.enabled);
+			ui.same_line(0.);
+			ui.checkbox(im_str!("Screenshots"), &mut watchdog.screenshots);
+			ui.same_line(0.);
+			if ui.small_button(im_str!("Clear")) {
+				watchdog.reports.clear();
+			}
+			ui.slider_float(im_str!("Threshold (ms)"), &mut watchdog.threshold_ms, 16., 500.).build();
+			ui.separator();
+
+			for (i, report) in watchdog.reports.iter().enumerate().rev() {
+				let label = im_str!("{:.1}s: {:.1} ms##hitch{}", report.time, report.frame_ms, i);
+				if !ui.collapsing_header(&label).build() {
+					continue;
+				}
+				if report.spans.is_empty() {
+					ui.text_disabled(im_str!("No profiler spans"));
+				}
+				for span in &report.spans {
+					let ms = span.duration.as_secs() as f32 * 1000. + span.duration.subsec_nanos() as f32 / 1e6;
+					ui.text(format!("{:8.2} ms  {} ({})", ms, span.name, span.thread));
+				}
+				if !report.log.is_empty() {
+					ui.separator();
+					for line in &report.log {
+						ui.text_disabled(&im_str!("{}", line));
+					}
+				}
+				if let Some(ref path) = report.screenshot {
+					ui.separator();
+					ui.text(format!("Screenshot: {}", path.display()));
+				}
+				if let Some(ref error) = report.screenshot_error {
+					ui.separator();
+					ui.text_colored([1., 0.35, 0.35, 1.], format!("No screenshot: {}", error));
+				}
+			}
+		});
+	watchdog.open = open;
+}